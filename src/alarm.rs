@@ -0,0 +1,715 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use ncurses::*;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use tac::framebuffer::Attributes;
+
+use crate::calendar;
+use crate::charset;
+use crate::choices::{AlarmAction, Choice};
+use crate::config_edit::{edit_integer_value, edit_text_value, Config};
+use crate::history;
+use crate::hooks;
+use crate::palette::{self, ColorSpec};
+use crate::signals;
+use crate::time::{self, SessionClock};
+use crate::toast::Toasts;
+
+/// System-wide alarms every user of the machine gets on top of their own,
+/// such as the bells of a classroom: a JSON list of alarms in the format of
+/// the "alarms" setting.
+pub const SHARED_ALARMS_FILE: &str = "/etc/tac/alarms.json";
+
+/// How long an alarm keeps flashing and ringing when nobody dismisses it.
+const RING_MINUTES: i64 = 60;
+
+/// Bell period at the very start of a wake-up ramp and right before the alarm time.
+const SLOWEST_BELL_SECONDS: f64 = 60.0;
+const FASTEST_BELL_SECONDS: f64 = 5.0;
+
+/// How long before an alarm a toast announces it.
+const ANNOUNCE_MINUTES: i64 = 10;
+
+/// Columns of the day names and of each hour in the week overview.
+const WEEK_LABEL_WIDTH: i32 = 8;
+const WEEK_HOUR_WIDTH: i32 = 3;
+/// First row of the list of coming alarms, under the grid.
+const WEEK_LIST_ROW: i32 = 12;
+
+/// Alarm actions are written by name, like the choices of the config.
+impl Serialize for AlarmAction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for AlarmAction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        AlarmAction::ALL
+            .iter()
+            .find(|action| action.name() == name)
+            .copied()
+            .ok_or_else(|| de::Error::custom(format!("unknown alarm action \"{name}\"")))
+    }
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn no_action() -> AlarmAction {
+    AlarmAction::None
+}
+
+/// One item of the "alarms" config list.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Alarm {
+    /// Time of day, "HH:MM".
+    pub time: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub label: String,
+    /// Day of a one-shot alarm, "YYYY-MM-DD"; empty for every day.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub date: String,
+    /// Minutes of gradual wake-up before `time`; 0 for a plain alarm.
+    #[serde(default)]
+    pub ramp: i64,
+    #[serde(default = "no_action")]
+    pub action: AlarmAction,
+    /// Command or message, depending on `action`.
+    #[serde(default)]
+    pub argument: String,
+}
+
+impl Alarm {
+    fn new(time: &str) -> Self {
+        Self {
+            time: time.into(),
+            date: String::new(),
+            enabled: true,
+            label: String::new(),
+            ramp: 0,
+            action: AlarmAction::None,
+            argument: String::new(),
+        }
+    }
+}
+
+/// Where we are on the way to an alarm time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    /// Nothing to show.
+    Idle,
+    /// Wake-up ramp, `0.0` at the start of the ramp and `1.0` at the alarm time.
+    Ramp(f64),
+    /// Alarm time reached, flashing until dismissed or snoozed.
+    Ringing,
+}
+
+/// Parse a "HH:MM" string.
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+/// Read the "alarms" list; items that do not parse are skipped.
+fn personal_alarms(cfg: &Config) -> Vec<Alarm> {
+    cfg.get_list("alarms")
+        .into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()
+}
+
+/// The alarms of the user followed by the shared ones and those of the
+/// calendar.
+pub fn alarms(cfg: &Config) -> Vec<Alarm> {
+    let mut alarms = personal_alarms(cfg);
+    alarms.extend(shared_alarms());
+    alarms.extend(calendar::calendar_alarms(cfg));
+    alarms
+}
+
+/// The times the enabled alarms ring from `start` (included) to `end`
+/// (excluded), in order, with their alarm. Snoozes and dismissals are left
+/// out: this is the schedule, not what will ring.
+pub fn occurrences(
+    cfg: &Config,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<(NaiveDateTime, Alarm)> {
+    let mut found = Vec::new();
+    for alarm in alarms(cfg).into_iter().filter(|alarm| alarm.enabled) {
+        let Some(time) = parse_time(&alarm.time) else {
+            continue;
+        };
+        let date = NaiveDate::parse_from_str(&alarm.date, "%Y-%m-%d").ok();
+        for day in start
+            .date()
+            .iter_days()
+            .take_while(|&day| day <= end.date())
+        {
+            let occurrence = day.and_time(time);
+            if (alarm.date.is_empty() || date == Some(day)) && (start..end).contains(&occurrence) {
+                found.push((occurrence, alarm.clone()));
+            }
+        }
+    }
+    found.sort_by_key(|&(occurrence, _)| occurrence);
+    found
+}
+
+/// The alarms of [`SHARED_ALARMS_FILE`], read again whenever the file
+/// changes. A missing or unreadable file gives none.
+fn shared_alarms() -> Vec<Alarm> {
+    static CACHE: Mutex<Option<(SystemTime, Vec<Alarm>)>> = Mutex::new(None);
+    let Ok(modified) = fs::metadata(SHARED_ALARMS_FILE).and_then(|m| m.modified()) else {
+        return Vec::new();
+    };
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    match &*cache {
+        Some((read, alarms)) if *read == modified => alarms.clone(),
+        _ => {
+            let items: Vec<serde_json::Value> = fs::read_to_string(SHARED_ALARMS_FILE)
+                .ok()
+                .and_then(|text| serde_json::from_str(&text).ok())
+                .unwrap_or_default();
+            let alarms: Vec<Alarm> = items
+                .into_iter()
+                .filter_map(|item| serde_json::from_value(item).ok())
+                .collect();
+            *cache = Some((modified, alarms.clone()));
+            alarms
+        }
+    }
+}
+
+fn save_alarms(cfg: &mut Config, alarms: &[Alarm]) {
+    let items = alarms
+        .iter()
+        .filter_map(|alarm| serde_json::to_value(alarm).ok())
+        .collect();
+    cfg.set_list("alarms", items);
+}
+
+/// Runtime state of the alarms: which occurrences were dismissed, snoozed or
+/// already ran their action. An occurrence is identified by its date and time.
+///
+/// A wake-up alarm (ramp > 0) makes the dial go from dim to bold during its
+/// last minutes, with the bell ringing more and more often. Once the alarm
+/// time is reached the dial flashes in inverse video and the bell rings every
+/// second until the alarm is dismissed or snoozed.
+#[derive(Default)]
+pub struct Alarms {
+    dismissed: Vec<NaiveDateTime>,
+    /// Snoozed occurrence and the moment it rings again.
+    snoozed: Vec<(NaiveDateTime, NaiveDateTime)>,
+    acted: Vec<NaiveDateTime>,
+    announced: Vec<NaiveDateTime>,
+    last_bell: Option<NaiveDateTime>,
+}
+
+impl Alarms {
+    /// When an occurrence rings: its time, or the end of its snooze.
+    fn ring_start(&self, occurrence: NaiveDateTime) -> NaiveDateTime {
+        self.snoozed
+            .iter()
+            .find(|(o, _)| *o == occurrence)
+            .map(|&(_, until)| until)
+            .unwrap_or(occurrence)
+    }
+
+    /// The occurrence of `alarm` that `now` is related to, if any: the first
+    /// one (yesterday, today or tomorrow) that is neither dismissed nor over.
+    fn occurrence(&self, alarm: &Alarm, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        if !alarm.enabled {
+            return None;
+        }
+        let time = parse_time(&alarm.time)?;
+        let today = now.date().and_time(time);
+        let date = NaiveDate::parse_from_str(&alarm.date, "%Y-%m-%d").ok();
+        [-1, 0, 1]
+            .into_iter()
+            .map(|days| today + Duration::days(days))
+            .filter(|o| alarm.date.is_empty() || Some(o.date()) == date)
+            .find(|&o| {
+                !self.dismissed.contains(&o)
+                    && now < self.ring_start(o) + Duration::minutes(RING_MINUTES)
+            })
+    }
+
+    fn alarm_stage(&self, alarm: &Alarm, now: NaiveDateTime) -> Stage {
+        let occurrence = match self.occurrence(alarm, now) {
+            Some(occurrence) => occurrence,
+            None => return Stage::Idle,
+        };
+        let start = self.ring_start(occurrence);
+        if now >= start {
+            return Stage::Ringing;
+        }
+        if start != occurrence || alarm.ramp <= 0 {
+            // Snoozing, or a plain alarm not due yet
+            return Stage::Idle;
+        }
+        let ramp = Duration::minutes(alarm.ramp);
+        let ramp_start = occurrence - ramp;
+        if now < ramp_start {
+            return Stage::Idle;
+        }
+        let elapsed = (now - ramp_start).num_milliseconds() as f64;
+        Stage::Ramp(elapsed / ramp.num_milliseconds() as f64)
+    }
+
+    /// The most advanced alarm (ringing first, then the furthest into its ramp).
+    fn current(&self, cfg: &Config, now: NaiveDateTime) -> Option<(Alarm, Stage)> {
+        let rank = |stage: Stage| match stage {
+            Stage::Idle => -1.0,
+            Stage::Ramp(p) => p,
+            Stage::Ringing => 2.0,
+        };
+        alarms(cfg)
+            .into_iter()
+            .map(|alarm| {
+                let stage = self.alarm_stage(&alarm, now);
+                (alarm, stage)
+            })
+            .filter(|(_, stage)| *stage != Stage::Idle)
+            .max_by(|(_, x), (_, y)| rank(*x).total_cmp(&rank(*y)))
+    }
+
+    pub fn stage(&self, cfg: &Config, now: NaiveDateTime) -> Stage {
+        self.current(cfg, now)
+            .map(|(_, stage)| stage)
+            .unwrap_or(Stage::Idle)
+    }
+
+    /// When the next alarm rings (its time, or the end of its snooze), among
+    /// the enabled alarms not ringing yet.
+    pub fn next_occurrence(&self, cfg: &Config, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        alarms(cfg)
+            .iter()
+            .filter_map(|alarm| self.occurrence(alarm, now))
+            .map(|occurrence| self.ring_start(occurrence))
+            .filter(|&start| start > now)
+            .min()
+    }
+
+    /// Color of the dial border: the "circle color", shifting to the "alarm
+    /// approach color" during the last "alarm approach minutes" before the
+    /// next alarm.
+    pub fn border_color(&self, cfg: &Config, now: NaiveDateTime) -> ColorSpec {
+        let color = cfg.get_color("circle color");
+        let window = Duration::minutes(cfg.get_int("alarm approach minutes"));
+        match self.next_occurrence(cfg, now) {
+            Some(start) if window > Duration::zero() && start - now <= window => {
+                let left = (start - now).num_milliseconds() as f64;
+                let progress = 1.0 - left / window.num_milliseconds() as f64;
+                palette::blend(color, cfg.get_color("alarm approach color"), progress)
+            }
+            _ => color,
+        }
+    }
+
+    /// Attributes to apply to the whole dial for the current stage.
+    pub fn attributes(&self, cfg: &Config, now: NaiveDateTime) -> Attributes {
+        match self.stage(cfg, now) {
+            Stage::Idle => Attributes::NONE,
+            Stage::Ramp(p) if p < 1.0 / 3.0 => Attributes::DIM,
+            Stage::Ramp(p) if p < 2.0 / 3.0 => Attributes::NONE,
+            Stage::Ramp(_) => Attributes::BOLD,
+            // Flash once per second
+            Stage::Ringing if now.second().is_multiple_of(2) => {
+                Attributes::BOLD | Attributes::REVERSE
+            }
+            Stage::Ringing => Attributes::BOLD,
+        }
+    }
+
+    /// Ring the terminal bell when due and run the action of alarms that just
+    /// started ringing (once per occurrence, not again after a snooze). Alarms
+    /// coming within 10 minutes are announced with a toast. Returns the alarms
+    /// that just started ringing, for the caller to run the actions changing
+    /// the clock: presets and focus sessions.
+    pub fn tick(&mut self, cfg: &Config, now: NaiveDateTime, toasts: &mut Toasts) -> Vec<Alarm> {
+        // Forget occurrences that can no longer ring
+        let horizon = now - Duration::days(2);
+        self.dismissed.retain(|o| *o > horizon);
+        self.snoozed.retain(|(o, _)| *o > horizon);
+        self.acted.retain(|o| *o > horizon);
+        self.announced.retain(|o| *o > horizon);
+
+        let mut started = Vec::new();
+        for alarm in alarms(cfg) {
+            if let Some(occurrence) = self.occurrence(&alarm, now) {
+                let ahead = occurrence - now;
+                if ahead > Duration::zero()
+                    && ahead <= Duration::minutes(ANNOUNCE_MINUTES)
+                    && !self.announced.contains(&occurrence)
+                {
+                    self.announced.push(occurrence);
+                    let name = if alarm.label.is_empty() {
+                        &alarm.time
+                    } else {
+                        &alarm.label
+                    };
+                    let minutes = (ahead.num_seconds() + 59) / 60;
+                    toasts.show(format!("Alarm {name} in {minutes} min"));
+                }
+            }
+            if self.alarm_stage(&alarm, now) != Stage::Ringing {
+                continue;
+            }
+            if let Some(occurrence) = self.occurrence(&alarm, now) {
+                if !self.acted.contains(&occurrence) {
+                    self.acted.push(occurrence);
+                    run_action(cfg, &alarm);
+                    started.push(alarm);
+                }
+            }
+        }
+
+        let period = match self.stage(cfg, now) {
+            Stage::Idle => {
+                self.last_bell = None;
+                return started;
+            }
+            Stage::Ramp(p) => {
+                SLOWEST_BELL_SECONDS - (SLOWEST_BELL_SECONDS - FASTEST_BELL_SECONDS) * p
+            }
+            Stage::Ringing => 1.0,
+        };
+        let due = match self.last_bell {
+            Some(last) => (now - last).num_milliseconds() as f64 >= period * 1000.0,
+            None => true,
+        };
+        if due {
+            beep();
+            self.last_bell = Some(now);
+        }
+        started
+    }
+
+    /// Text to show on the dial: the message of a ringing "message" alarm,
+    /// otherwise the label of the ringing alarm.
+    pub fn message(&self, cfg: &Config, now: NaiveDateTime) -> Option<String> {
+        match self.current(cfg, now) {
+            Some((alarm, Stage::Ringing)) => {
+                let text = if alarm.action == AlarmAction::Message {
+                    alarm.argument
+                } else {
+                    alarm.label
+                };
+                Some(text).filter(|t| !t.is_empty())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a key press should go to the alarms rather than to the keymap.
+    pub fn is_active(&self, cfg: &Config, now: NaiveDateTime) -> bool {
+        self.stage(cfg, now) != Stage::Idle
+    }
+
+    /// Whether at least one alarm is ringing (and can therefore be snoozed).
+    pub fn is_ringing(&self, cfg: &Config, now: NaiveDateTime) -> bool {
+        self.stage(cfg, now) == Stage::Ringing
+    }
+
+    /// Silence every ramping or ringing alarm; they come back the next day.
+    pub fn dismiss(&mut self, cfg: &Config, now: NaiveDateTime) {
+        for alarm in alarms(cfg) {
+            if self.alarm_stage(&alarm, now) != Stage::Idle {
+                if let Some(occurrence) = self.occurrence(&alarm, now) {
+                    self.dismissed.push(occurrence);
+                }
+            }
+        }
+        self.last_bell = None;
+    }
+
+    /// Silence the ringing alarms for "snooze minutes".
+    pub fn snooze(&mut self, cfg: &Config, now: NaiveDateTime) {
+        let until = now + Duration::minutes(cfg.get_int("snooze minutes").max(1));
+        for alarm in alarms(cfg) {
+            if self.alarm_stage(&alarm, now) != Stage::Ringing {
+                continue;
+            }
+            if let Some(occurrence) = self.occurrence(&alarm, now) {
+                self.snoozed.retain(|(o, _)| *o != occurrence);
+                self.snoozed.push((occurrence, until));
+            }
+        }
+        self.last_bell = None;
+    }
+}
+
+/// Note the alarm in the history, post the webhook and run the action of
+/// type command; messages are drawn by the caller.
+fn run_action(cfg: &Config, alarm: &Alarm) {
+    let name = if alarm.label.is_empty() {
+        &alarm.time
+    } else {
+        &alarm.label
+    };
+    history::record(cfg, "alarm", name);
+    hooks::post_webhook(
+        cfg,
+        serde_json::json!({
+            "event": "alarm",
+            "time": alarm.time,
+            "label": alarm.label,
+        }),
+    );
+    if alarm.action == AlarmAction::Command {
+        hooks::run_command(
+            &alarm.argument,
+            "alarm",
+            &[
+                ("TAC_ALARM_TIME", alarm.time.clone()),
+                ("TAC_ALARM_LABEL", alarm.label.clone()),
+            ],
+        );
+    }
+}
+
+/// Draw the alarm management screen.
+fn draw_manage_screen(alarms: &[Alarm], selected: usize) {
+    erase();
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+
+    mvaddstr(0, 0, "Alarms");
+    mvaddstr(
+        1,
+        0,
+        "n: new  Enter: time  Space: on/off  l: label  r: wake-up ramp  a: action  t: action text  d: delete  w: week  h: history  Esc: back",
+    );
+
+    if alarms.is_empty() {
+        mvaddstr(3, 2, "No alarm yet, press n to add one.");
+    }
+    for (i, alarm) in alarms.iter().enumerate() {
+        let row = 3 + i as i32;
+        if row >= max_y - 3 {
+            break;
+        }
+        let mut line = format!(
+            "[{}] {:<5}  {:<20}",
+            if alarm.enabled { "on " } else { "off" },
+            alarm.time,
+            alarm.label
+        );
+        if !alarm.date.is_empty() {
+            line.push_str(&format!("  on {}", alarm.date));
+        }
+        if alarm.ramp > 0 {
+            line.push_str(&format!("  wake-up {} min", alarm.ramp));
+        }
+        match alarm.action {
+            AlarmAction::None => {}
+            AlarmAction::Pomodoro => line.push_str(&format!("  {}", alarm.action.name())),
+            action => line.push_str(&format!("  {}: {}", action.name(), alarm.argument)),
+        }
+        if i == selected {
+            attron(A_REVERSE());
+        }
+        mvaddstr(row, 2, &line);
+        if i == selected {
+            attroff(A_REVERSE());
+        }
+    }
+    let shared = shared_alarms().len();
+    if shared > 0 {
+        let note =
+            format!("+ {shared} shared alarm(s) from {SHARED_ALARMS_FILE}, not editable here");
+        mvaddstr(max_y - 2, 2, &note);
+    }
+    refresh();
+}
+
+/// Week overview, to check the reminders of the coming days at a glance: a
+/// row per day from today and a column per hour, each cell counting the
+/// alarms (personal, shared and from the calendar) ringing in that hour,
+/// then the list of the coming ones. Any key goes back.
+fn show_week(cfg: &Config) {
+    let now = time::effective_now(cfg, &SessionClock).naive_local();
+    let start = now.date().and_time(NaiveTime::MIN);
+    let week = occurrences(cfg, start, start + Duration::days(7));
+
+    erase();
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+    mvaddstr(0, 0, "Alarms of the week");
+    mvaddstr(
+        1,
+        0,
+        "Each cell is an hour, with the number of alarms ringing in it.  Any key: back",
+    );
+    for hour in (0..24).step_by(3) {
+        mvaddstr(
+            3,
+            WEEK_LABEL_WIDTH + hour * WEEK_HOUR_WIDTH,
+            &format!("{hour:02}"),
+        );
+    }
+    for day in 0..7 {
+        let date = now.date() + Duration::days(day);
+        let row = 4 + day as i32;
+        mvaddstr(row, 0, &date.format("%a %d").to_string());
+        for hour in 0..24 {
+            let count = week
+                .iter()
+                .filter(|(o, _)| o.date() == date && o.hour() == hour)
+                .count();
+            let cell = match count {
+                0 => charset::text("·").into_owned(),
+                1..=9 => count.to_string(),
+                _ => "+".to_string(),
+            };
+            let current = day == 0 && hour == now.hour();
+            if current {
+                attron(A_REVERSE());
+            }
+            mvaddstr(row, WEEK_LABEL_WIDTH + hour as i32 * WEEK_HOUR_WIDTH, &cell);
+            if current {
+                attroff(A_REVERSE());
+            }
+        }
+    }
+
+    let coming: Vec<_> = week.iter().filter(|(o, _)| *o >= now).collect();
+    if coming.is_empty() {
+        mvaddstr(WEEK_LIST_ROW, 2, "No alarm in the coming week.");
+    }
+    for (row, (occurrence, alarm)) in (WEEK_LIST_ROW..max_y - 1).zip(coming) {
+        let line = format!("{}  {}", occurrence.format("%a %d %H:%M"), alarm.label);
+        mvaddstr(row, 2, &line);
+    }
+    refresh();
+    getch();
+}
+
+/// History page: the alarms that rang and the pomodoro phases that ended,
+/// newest first, to check that an alarm did ring. Any key goes back.
+pub fn show_history() {
+    erase();
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+    mvaddstr(0, 0, "Recent alarms");
+    mvaddstr(
+        1,
+        0,
+        "Alarms that rang and pomodoro phases that ended.  Any key: back",
+    );
+    let firings = history::recent();
+    if firings.is_empty() {
+        mvaddstr(3, 2, "Nothing rang yet.");
+    }
+    for (row, firing) in (3..max_y - 1).zip(firings.iter().rev()) {
+        let line = format!("{}  {:<8}  {}", firing.at, firing.event, firing.label);
+        mvaddstr(row, 2, &line);
+    }
+    refresh();
+    getch();
+}
+
+/// Ask for an "HH:MM" time; `None` if cancelled or invalid.
+fn ask_time(initial: &str) -> Option<String> {
+    let mut text = initial.to_string();
+    edit_text_value("alarm time (HH:MM)", &mut text, Some(5));
+    parse_time(&text).map(|t| t.format("%H:%M").to_string())
+}
+
+/// Alarm management screen: add, edit, enable/disable and delete alarms.
+/// Every change is written to the config right away.
+pub fn manage(cfg: &mut Config) {
+    // Block on getch() while the screen is open
+    nodelay(stdscr(), false);
+    let mut list = personal_alarms(cfg);
+    let mut selected = 0;
+
+    loop {
+        selected = selected.min(list.len().saturating_sub(1));
+        draw_manage_screen(&list, selected);
+        let ch = getch();
+        if signals::quit_requested() {
+            break;
+        }
+        signals::suspend_if_asked();
+        let mut changed = true;
+        match ch {
+            KEY_UP => {
+                selected = selected.saturating_sub(1);
+                changed = false;
+            }
+            KEY_DOWN => {
+                if selected + 1 < list.len() {
+                    selected += 1;
+                }
+                changed = false;
+            }
+            // 'n' -> new alarm
+            110 => {
+                if let Some(time) = ask_time("07:00") {
+                    list.push(Alarm::new(&time));
+                    selected = list.len() - 1;
+                }
+            }
+            // 'w' -> week overview
+            119 => {
+                show_week(cfg);
+                changed = false;
+            }
+            // 'h' -> history
+            104 => {
+                show_history();
+                changed = false;
+            }
+            // 'q', Esc -> back to the clock
+            113 | 27 => break,
+            _ => {
+                let alarm = match list.get_mut(selected) {
+                    Some(alarm) => alarm,
+                    None => continue,
+                };
+                match ch {
+                    // Enter, 'e' -> edit time
+                    10 | 13 | 101 => {
+                        if let Some(time) = ask_time(&alarm.time) {
+                            alarm.time = time;
+                        }
+                    }
+                    // Space -> toggle
+                    32 => alarm.enabled = !alarm.enabled,
+                    // 'l' -> label
+                    108 => edit_text_value("alarm label", &mut alarm.label, Some(64)),
+                    // 'r' -> wake-up ramp
+                    114 => {
+                        edit_integer_value("wake-up ramp (minutes, 0 = off)", &mut alarm.ramp);
+                        alarm.ramp = alarm.ramp.clamp(0, 24 * 60);
+                    }
+                    // 'a' -> cycle action
+                    97 => alarm.action = alarm.action.next(),
+                    // 't' -> action text
+                    116 => edit_text_value("action text", &mut alarm.argument, Some(256)),
+                    // 'd' -> delete
+                    100 | KEY_DC => {
+                        list.remove(selected);
+                    }
+                    _ => changed = false,
+                }
+            }
+        }
+        if changed {
+            save_alarms(cfg, &list);
+        }
+    }
+    nodelay(stdscr(), true);
+}
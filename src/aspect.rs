@@ -0,0 +1,39 @@
+use std::mem;
+
+/// Height of a character cell divided by its width when the terminal does
+/// not tell its size in pixels.
+pub const DEFAULT: f64 = 2.0;
+
+/// Height of a character cell divided by its width, from the size of the
+/// terminal in pixels and in cells. Terminals that do not fill in the pixel
+/// size (tmux, the Linux console, many serial lines) give [`DEFAULT`].
+pub fn cell_aspect() -> f64 {
+    measured().unwrap_or(DEFAULT)
+}
+
+/// Columns and rows of the terminal on standard output, when it is one.
+pub fn terminal_size() -> Option<(i32, i32)> {
+    window_size()
+        .filter(|size| size.ws_col > 0 && size.ws_row > 0)
+        .map(|size| (size.ws_col as i32, size.ws_row as i32))
+}
+
+fn window_size() -> Option<libc::winsize> {
+    // SAFETY: winsize is plain data, and TIOCGWINSZ only writes into it
+    let mut size: libc::winsize = unsafe { mem::zeroed() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } != 0 {
+        return None;
+    }
+    Some(size)
+}
+
+fn measured() -> Option<f64> {
+    let size = window_size()?;
+    if size.ws_xpixel == 0 || size.ws_ypixel == 0 || size.ws_col == 0 || size.ws_row == 0 {
+        return None;
+    }
+    let width = size.ws_xpixel as f64 / size.ws_col as f64;
+    let height = size.ws_ypixel as f64 / size.ws_row as f64;
+    // Anything far from a text cell is a terminal reporting nonsense
+    Some(height / width).filter(|ratio| (1.0..=4.0).contains(ratio))
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, FixedOffset, Timelike};
+
+use tac::framebuffer::{FrameBuffer, Style};
+
+use crate::choices::{DigitWeight, DigitalDisplay, DisplaySeconds};
+use crate::config_edit::Config;
+use crate::dial::{BORDER_PAIR, HOURS_PAIR, MINUTES_PAIR, SECONDS_PAIR};
+
+/// Rows of every glyph.
+const GLYPH_HEIGHT: i32 = 5;
+
+/// Digits 0 to 9, 3 columns wide, `#` for a block.
+const DIGITS: [[&str; 5]; 10] = [
+    ["###", "# #", "# #", "# #", "###"],
+    ["  #", "  #", "  #", "  #", "  #"],
+    ["###", "  #", "###", "#  ", "###"],
+    ["###", "  #", "###", "  #", "###"],
+    ["# #", "# #", "###", "  #", "  #"],
+    ["###", "#  ", "###", "  #", "###"],
+    ["###", "#  ", "###", "# #", "###"],
+    ["###", "  #", "  #", "  #", "  #"],
+    ["###", "# #", "###", "# #", "###"],
+    ["###", "# #", "###", "  #", "###"],
+];
+
+/// Separator between hours, minutes and seconds, 1 column wide.
+const COLON: [&str; 5] = [" ", "#", " ", "#", " "];
+
+/// Character of the blocks of the "big digit weight".
+fn block(weight: DigitWeight) -> char {
+    match weight {
+        DigitWeight::Light => '░',
+        DigitWeight::Regular => '▒',
+        DigitWeight::Bold => '█',
+    }
+}
+
+/// Draw the time in digits made of blocks, as big as the frame allows,
+/// centred. Hours, minutes and seconds take the colors of their hands and
+/// the colons the color of the dial; the seconds are left out when the
+/// second hand is hidden, and the hours follow the "digital display" setting.
+/// The blocks are shaded as the "big digit weight" says.
+pub fn draw(frame: &mut FrameBuffer, cfg: &Config, now: &DateTime<FixedOffset>) {
+    let hour = match cfg.get::<DigitalDisplay>() {
+        DigitalDisplay::TwelveHours => (now.hour() + 11) % 12 + 1,
+        _ => now.hour(),
+    };
+    let mut groups = vec![(hour, HOURS_PAIR), (now.minute(), MINUTES_PAIR)];
+    if cfg.get::<DisplaySeconds>() != DisplaySeconds::NoDisplay {
+        groups.push((now.second(), SECONDS_PAIR));
+    }
+
+    // Glyphs and their colors, with one blank column between two of them
+    let mut glyphs: Vec<(&[&str; 5], i16)> = Vec::new();
+    for (i, &(value, pair)) in groups.iter().enumerate() {
+        if i > 0 {
+            glyphs.push((&COLON, BORDER_PAIR));
+        }
+        glyphs.push((&DIGITS[(value / 10) as usize], pair));
+        glyphs.push((&DIGITS[(value % 10) as usize], pair));
+    }
+    let columns: i32 =
+        glyphs.iter().map(|(g, _)| g[0].len() as i32).sum::<i32>() + glyphs.len() as i32 - 1;
+
+    // Cells are about twice as high as wide: a block is 2 columns per row
+    let scale_y = (frame.height() / GLYPH_HEIGHT)
+        .min(frame.width() / (2 * columns))
+        .max(1);
+    let scale_x = 2 * scale_y;
+    let left = (frame.width() - columns * scale_x) / 2;
+    let top = (frame.height() - GLYPH_HEIGHT * scale_y) / 2;
+
+    let block = block(cfg.get::<DigitWeight>());
+    let mut x = left;
+    for (glyph, pair) in glyphs {
+        let style = Style::pair(pair);
+        for (row, line) in glyph.iter().enumerate() {
+            for (column, ch) in line.chars().enumerate() {
+                if ch != '#' {
+                    continue;
+                }
+                for dy in 0..scale_y {
+                    for dx in 0..scale_x {
+                        frame.put(
+                            x + column as i32 * scale_x + dx,
+                            top + row as i32 * scale_y + dy,
+                            block,
+                            style,
+                        );
+                    }
+                }
+            }
+        }
+        x += (glyph[0].len() as i32 + 1) * scale_x;
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use tac::framebuffer::{FrameBuffer, Style};
+use tac::geometry::ellipse_points;
+
+use crate::choices::BorderLine;
+use crate::config_edit::Config;
+
+/// Everything needed to draw the full border of a dial.
+pub struct BorderStyle {
+    pub line: BorderLine,
+    pub characters: String,
+    pub style: Style,
+}
+
+impl BorderStyle {
+    pub fn from_config(cfg: &Config, style: Style) -> Self {
+        let line = cfg.get::<BorderLine>();
+        let characters = match cfg.get_string("border characters") {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => "*".to_string(),
+        };
+        Self {
+            line,
+            characters,
+            style,
+        }
+    }
+
+    /// Draw the border of the dial centred at (cx,cy) with radii `a` and `b`.
+    pub fn draw(&self, frame: &mut FrameBuffer, cx: i32, cy: i32, a: i32, b: i32) {
+        match self.line {
+            BorderLine::Characters => self.draw_ring(frame, cx, cy, a, b),
+            BorderLine::Ascii => self.draw_slopes(frame, cx, cy, a, b, ['-', '|', '/', '\\']),
+            BorderLine::BoxDrawing => self.draw_slopes(frame, cx, cy, a, b, ['─', '│', '╱', '╲']),
+            BorderLine::Braille => self.draw_braille(frame, cx, cy, a, b),
+            BorderLine::DoubleRing => {
+                self.draw_ring(frame, cx, cy, a, b);
+                if b > 2 {
+                    self.draw_ring(frame, cx, cy, a - 2, b - 1);
+                }
+            }
+        }
+    }
+
+    /// The border characters around the ellipse, clockwise from 12 o'clock.
+    fn draw_ring(&self, frame: &mut FrameBuffer, cx: i32, cy: i32, a: i32, b: i32) {
+        let angle = |&(x, y): &(i32, i32)| {
+            let angle = ((x - cx) as f64).atan2((cy - y) as f64);
+            if angle < 0.0 {
+                angle + 2.0 * PI
+            } else {
+                angle
+            }
+        };
+        let mut points = ellipse_points(cx, cy, a, b);
+        points.sort_by(|p, q| angle(p).total_cmp(&angle(q)));
+        points.dedup();
+        frame.draw_path(&points, &self.characters, self.style);
+    }
+
+    /// One of `lines` (horizontal, vertical, rising, falling) on each cell of
+    /// the ellipse, whichever is closest to the curve there.
+    fn draw_slopes(
+        &self,
+        frame: &mut FrameBuffer,
+        cx: i32,
+        cy: i32,
+        a: i32,
+        b: i32,
+        lines: [char; 4],
+    ) {
+        let (a2, b2) = ((a * a).max(1) as f64, (b * b).max(1) as f64);
+        for (x, y) in ellipse_points(cx, cy, a, b) {
+            let (dx, dy) = ((x - cx) as f64, (y - cy) as f64);
+            // Tangent, perpendicular to the gradient; cells are twice as high as wide
+            let (tx, ty) = (-dy / b2, dx / a2);
+            let slope = (2.0 * ty).atan2(tx).to_degrees().rem_euclid(180.0);
+            let ch = match slope {
+                s if !(22.5..157.5).contains(&s) => lines[0],
+                s if (67.5..112.5).contains(&s) => lines[1],
+                // Screen rows grow downwards
+                s if s > 90.0 => lines[2],
+                _ => lines[3],
+            };
+            frame.put(x, y, ch, self.style);
+        }
+    }
+
+    /// The ellipse plotted on the 2×4 dots of braille characters.
+    fn draw_braille(&self, frame: &mut FrameBuffer, cx: i32, cy: i32, a: i32, b: i32) {
+        // Bit of each dot of a braille character, by column and row
+        const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        let mut cells: HashMap<(i32, i32), u32> = HashMap::new();
+        // Enough steps for consecutive dots to touch
+        let steps = (2.0 * PI * (2 * a).max(4 * b) as f64 * 2.0).ceil().max(8.0) as usize;
+        for i in 0..steps {
+            let angle = 2.0 * PI * (i as f64) / (steps as f64);
+            let x = ((cx as f64 + 0.5 + a as f64 * angle.sin()) * 2.0).floor() as i32;
+            let y = ((cy as f64 + 0.5 - b as f64 * angle.cos()) * 4.0).floor() as i32;
+            *cells.entry((x.div_euclid(2), y.div_euclid(4))).or_default() |=
+                DOTS[x.rem_euclid(2) as usize][y.rem_euclid(4) as usize];
+        }
+        for ((x, y), bits) in cells {
+            if let Some(ch) = char::from_u32(0x2800 + bits) {
+                frame.put(x, y, ch, self.style);
+            }
+        }
+    }
+}
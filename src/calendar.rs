@@ -0,0 +1,135 @@
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::fs;
+use std::sync::Mutex;
+use std::time::{Duration as StdDuration, Instant};
+
+use tac::clock::FakeClock;
+
+use crate::alarm::Alarm;
+use crate::choices::AlarmAction;
+use crate::config_edit::Config;
+use crate::time::{self, SessionClock};
+
+/// How long the events read from the calendar are kept before reading it again.
+const REFRESH: StdDuration = StdDuration::from_secs(3600);
+
+/// How far ahead events become alarms.
+const HORIZON_HOURS: i64 = 24;
+
+/// An event of the calendar file, its start in the time zone of the clock.
+struct Event {
+    start: NaiveDateTime,
+    summary: String,
+}
+
+/// Lines of an ICS file, the folded ones (continued on lines starting with a
+/// space or a tab) joined back.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Start of an event from the parameters and value of its DTSTART line:
+/// UTC (`…Z`), in a `TZID`, or floating (the time zone of the clock). Events
+/// lasting whole days have no time and give `None`.
+fn start(cfg: &Config, params: &str, value: &str) -> Option<NaiveDateTime> {
+    let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()?;
+    let utc: DateTime<Utc> = if value.ends_with('Z') {
+        Utc.from_utc_datetime(&naive)
+    } else if let Some(zone) = params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+    {
+        let zone: Tz = zone.trim_matches('"').parse().ok()?;
+        zone.from_local_datetime(&naive).earliest()?.to_utc()
+    } else {
+        return Some(naive);
+    };
+    Some(time::effective_now(cfg, &FakeClock::new(utc)).naive_local())
+}
+
+/// The events of an ICS file. Recurrence rules are not followed: only the
+/// first occurrence of a repeating event is read.
+fn parse(cfg: &Config, text: &str) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut current: Option<(Option<NaiveDateTime>, String)> = None;
+    for line in unfold(text) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name.split_once(';').unwrap_or((name, ""));
+        match (name, &mut current) {
+            ("BEGIN", _) if value == "VEVENT" => current = Some((None, String::new())),
+            ("END", Some((start, summary))) if value == "VEVENT" => {
+                if let Some(start) = *start {
+                    events.push(Event {
+                        start,
+                        summary: summary.clone(),
+                    });
+                }
+                current = None;
+            }
+            ("DTSTART", Some((event_start, _))) => *event_start = start(cfg, params, value),
+            ("SUMMARY", Some((_, summary))) => {
+                *summary = value
+                    .replace("\\n", " ")
+                    .replace("\\,", ",")
+                    .replace("\\;", ";")
+                    .replace("\\\\", "\\");
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// One-shot alarms "calendar lead minutes" before the events of the
+/// "calendar file" starting within a day, labelled with their summary. The
+/// file is read at most once an hour.
+pub fn calendar_alarms(cfg: &Config) -> Vec<Alarm> {
+    static CACHE: Mutex<Option<(String, Instant, Vec<Event>)>> = Mutex::new(None);
+    let path = cfg.get_string("calendar file").unwrap_or_default();
+    if path.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let fresh = matches!(&*cache, Some((read, at, _)) if *read == path && at.elapsed() < REFRESH);
+    if !fresh {
+        let expanded = shellexpand::tilde(path.trim()).into_owned();
+        let events = fs::read_to_string(expanded)
+            .map(|text| parse(cfg, &text))
+            .unwrap_or_default();
+        *cache = Some((path, Instant::now(), events));
+    }
+    let Some((_, _, events)) = &*cache else {
+        return Vec::new();
+    };
+    let now = time::effective_now(cfg, &SessionClock).naive_local();
+    let lead = Duration::minutes(cfg.get_int("calendar lead minutes").max(0));
+    events
+        .iter()
+        .filter(|event| {
+            event.start > now - Duration::hours(1)
+                && event.start <= now + Duration::hours(HORIZON_HOURS)
+        })
+        .map(|event| {
+            let at = event.start - lead;
+            Alarm {
+                time: at.format("%H:%M").to_string(),
+                date: at.format("%Y-%m-%d").to_string(),
+                enabled: true,
+                label: event.summary.clone(),
+                ramp: 0,
+                action: AlarmAction::Message,
+                argument: format!("{} at {}", event.summary, event.start.format("%H:%M")),
+            }
+        })
+        .collect()
+}
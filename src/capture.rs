@@ -0,0 +1,69 @@
+use chrono::Local;
+use ncurses::has_colors;
+use std::fs;
+use std::path::PathBuf;
+
+use tac::framebuffer::{Attributes, Cell, FrameBuffer, Style};
+
+use crate::config_edit::Config;
+use crate::palette;
+
+/// SGR sequence reproducing the attributes and color pair of a cell.
+fn ansi_style(style: Style) -> String {
+    let mut params = vec!["0".to_string()];
+    for (attribute, code) in [
+        (Attributes::BOLD, "1"),
+        (Attributes::DIM, "2"),
+        (Attributes::BLINK, "5"),
+        (Attributes::REVERSE, "7"),
+    ] {
+        if style.attributes.contains(attribute) {
+            params.push(code.into());
+        }
+    }
+    if style.pair != 0 && has_colors() {
+        let (foreground, background) = palette::pair_colors(style.pair);
+        params.push(palette::sgr(foreground, 30));
+        params.push(palette::sgr(background, 40));
+    }
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// The frame as text with ANSI escape sequences. Trailing blanks of each
+/// line are dropped.
+fn ansi_text(frame: &FrameBuffer) -> String {
+    let mut ansi = String::new();
+    for y in 0..frame.height() {
+        let cells = frame.row(y);
+        let used = cells
+            .iter()
+            .rposition(|c| *c != Cell::BLANK)
+            .map_or(0, |last| last + 1);
+        let mut style = Style::default();
+        for cell in cells[..used].iter().filter(|c| !c.is_continuation()) {
+            if cell.style != style {
+                ansi.push_str(&ansi_style(cell.style));
+                style = cell.style;
+            }
+            ansi.push(cell.ch);
+        }
+        if style != Style::default() {
+            ansi.push_str("\x1b[0m");
+        }
+        ansi.push('\n');
+    }
+    ansi
+}
+
+/// Write the current frame next to the config file, as `tac-frame-<timestamp>.txt`
+/// (plain text) and `tac-frame-<timestamp>.ans` (with colors). Returns the path
+/// of the plain text file.
+pub fn capture_frame(cfg: &Config, frame: &FrameBuffer) -> Result<PathBuf, String> {
+    let (plain, ansi) = (frame.to_text(), ansi_text(frame));
+    let stem = format!("tac-frame-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let directory = cfg.directory();
+    let text_path = directory.join(format!("{stem}.txt"));
+    fs::write(&text_path, plain).map_err(|e| e.to_string())?;
+    fs::write(directory.join(format!("{stem}.ans")), ansi).map_err(|e| e.to_string())?;
+    Ok(text_path)
+}
@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::choices::CharacterSet;
+use crate::config_edit::Config;
+
+/// Whether the screen gets ASCII stand-ins instead of the characters beyond it.
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Whether the locale, read from the usual environment variables, encodes
+/// text in UTF-8. No locale at all is the C locale, which is ASCII.
+fn locale_is_utf8() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .is_some_and(|value| {
+            let value = value.to_ascii_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+}
+
+/// Follow the "character set" setting; "auto" falls back to ASCII when the
+/// locale is not UTF-8.
+pub fn configure(cfg: &Config) {
+    let ascii = match cfg.get::<CharacterSet>() {
+        CharacterSet::Auto => !locale_is_utf8(),
+        CharacterSet::Unicode => false,
+        CharacterSet::Ascii => true,
+    };
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+/// Whether characters beyond ASCII are replaced.
+pub fn is_ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// ASCII stand-in for `ch`: the closest shape for lines, blocks, braille and
+/// arrows, the letter without its accent, and '?' for anything else.
+fn fallback(ch: char) -> char {
+    match ch {
+        '─' | '━' | '═' | '╌' | '┄' => '-',
+        '│' | '┃' | '║' | '╎' | '┆' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' => {
+            '+'
+        }
+        '╭' | '╮' | '╯' | '╰' => '+',
+        '╱' => '/',
+        '╲' => '\\',
+        '█' | '▓' | '■' => '#',
+        '▒' => ':',
+        '░' => '.',
+        '▀' | '▔' => '"',
+        '▄' | '▁' => '_',
+        '▌' | '▐' => '|',
+        '⠀' => ' ',
+        '\u{2801}'..='\u{28ff}' => '.',
+        '←' | '◀' | '◂' => '<',
+        '→' | '▶' | '▸' => '>',
+        '↑' | '▲' | '▴' => '^',
+        '↓' | '▼' | '▾' => 'v',
+        '•' | '●' | '○' | '◯' | '°' => 'o',
+        '·' | '…' => '.',
+        '★' | '☆' | '✱' => '*',
+        '—' | '–' => '-',
+        '×' => 'x',
+        'à' | 'á' | 'â' | 'ä' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'î' | 'ï' => 'i',
+        'ô' | 'ö' => 'o',
+        'ù' | 'û' | 'ü' => 'u',
+        'À' | 'Â' => 'A',
+        'Ç' => 'C',
+        'È' | 'É' | 'Ê' | 'Ë' => 'E',
+        'Î' | 'Ï' => 'I',
+        'Ô' => 'O',
+        'Ù' | 'Û' | 'Ü' => 'U',
+        _ => '?',
+    }
+}
+
+/// `ch` as the screen should show it.
+pub fn glyph(ch: char) -> char {
+    match is_ascii() && !ch.is_ascii() {
+        true => fallback(ch),
+        false => ch,
+    }
+}
+
+/// `text` as the screen should show it, for the lines written to ncurses
+/// directly rather than through a frame.
+pub fn text(text: &str) -> Cow<'_, str> {
+    match is_ascii() && !text.is_ascii() {
+        true => Cow::Owned(text.chars().map(glyph).collect()),
+        false => Cow::Borrowed(text),
+    }
+}
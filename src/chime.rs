@@ -0,0 +1,69 @@
+use chrono::{Duration as ChronoDuration, NaiveDateTime, Timelike};
+use ncurses::beep;
+use std::time::{Duration, Instant};
+
+use crate::choices::ChimeMode;
+use crate::config_edit::Config;
+
+/// Time between two strokes, so that each one is heard.
+const STROKE_GAP: Duration = Duration::from_millis(700);
+
+/// Hourly chime rung with the terminal bell.
+///
+/// The last boundary rung is remembered, so each hour (or half hour) chimes
+/// exactly once however many frames are drawn during its first minute.
+#[derive(Default)]
+pub struct Chime {
+    last_boundary: Option<NaiveDateTime>,
+    strokes_left: u32,
+    next_stroke: Option<Instant>,
+}
+
+/// The last hour or half hour boundary before `now`, with its number of strokes.
+fn boundary(mode: ChimeMode, now: NaiveDateTime) -> (NaiveDateTime, u32) {
+    let half = mode != ChimeMode::OnTheHour && now.minute() >= 30;
+    let minute = if half { 30 } else { 0 };
+    let start = now.date().and_hms_opt(now.hour(), minute, 0).unwrap_or(now);
+    let strokes = match mode {
+        // A cuckoo clock counts the hours and calls once on the half hour
+        ChimeMode::Cuckoo if !half => match now.hour() % 12 {
+            0 => 12,
+            h => h,
+        },
+        _ => 1,
+    };
+    (start, strokes)
+}
+
+impl Chime {
+    /// Ring when a new boundary is reached. Call once per frame with the displayed time.
+    pub fn tick(&mut self, cfg: &Config, now: NaiveDateTime) {
+        let mode = cfg.get::<ChimeMode>();
+        if mode == ChimeMode::Off {
+            self.last_boundary = None;
+            self.strokes_left = 0;
+            return;
+        }
+        let (start, strokes) = boundary(mode, now);
+        // Starting up (or switching the chime on) rings nothing, and neither
+        // does landing mid-hour after a time zone change.
+        let reached = self.last_boundary.is_some_and(|last| last != start)
+            && now - start < ChronoDuration::minutes(1);
+        if reached {
+            self.strokes_left = strokes;
+            self.next_stroke = Some(Instant::now());
+        }
+        self.last_boundary = Some(start);
+
+        if self.strokes_left > 0 && self.next_stroke.is_some_and(|t| Instant::now() >= t) {
+            beep();
+            self.strokes_left -= 1;
+            self.next_stroke = Some(Instant::now() + STROKE_GAP);
+        }
+    }
+
+    /// Whether strokes of the current chime are still to come.
+    pub fn is_striking(&self) -> bool {
+        self.strokes_left > 0
+    }
+}
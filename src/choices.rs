@@ -0,0 +1,321 @@
+//! The options of every choice setting. The config file only stores the name
+//! of the selected option: the lists live here, so adding, removing or
+//! reordering options never shifts what an existing file selects.
+
+/// A setting with a fixed list of options, one enum variant each.
+pub trait Choice: Copy + PartialEq + 'static {
+    /// Key of the config entry.
+    const KEY: &'static str;
+    /// Every option, in the order the editor and the keys cycle through them.
+    const ALL: &'static [Self];
+
+    /// Name of the option in the config file and the editor.
+    fn name(self) -> &'static str;
+
+    /// One-line explanation of the option, shown by the editor.
+    fn help(self) -> &'static str;
+
+    /// Position of the option in [`Choice::ALL`].
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+
+    /// The option after this one, back to the first after the last.
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+}
+
+/// Names of the options of `C`, in order.
+pub fn names<C: Choice>() -> Vec<String> {
+    C::ALL.iter().map(|c| c.name().to_string()).collect()
+}
+
+/// Explanation of the option named `name` of the choice with config key `key`.
+pub fn help(key: &str, name: &str) -> Option<&'static str> {
+    fn find<C: Choice>(name: &str) -> Option<&'static str> {
+        C::ALL.iter().find(|c| c.name() == name).map(|c| c.help())
+    }
+    match key {
+        DisplayMode::KEY => find::<DisplayMode>(name),
+        StartupPage::KEY => find::<StartupPage>(name),
+        WordClockLanguage::KEY => find::<WordClockLanguage>(name),
+        DigitWeight::KEY => find::<DigitWeight>(name),
+        CharacterSet::KEY => find::<CharacterSet>(name),
+        ClockPosition::KEY => find::<ClockPosition>(name),
+        ClockBorder::KEY => find::<ClockBorder>(name),
+        BorderLine::KEY => find::<BorderLine>(name),
+        FaceFill::KEY => find::<FaceFill>(name),
+        DisplaySeconds::KEY => find::<DisplaySeconds>(name),
+        ProgressRing::KEY => find::<ProgressRing>(name),
+        NineOClockSubDial::KEY => find::<NineOClockSubDial>(name),
+        DateWindow::KEY => find::<DateWindow>(name),
+        UptimeGauge::KEY => find::<UptimeGauge>(name),
+        Numbers::KEY => find::<Numbers>(name),
+        NumbersStyle::KEY => find::<NumbersStyle>(name),
+        DigitalDisplay::KEY => find::<DigitalDisplay>(name),
+        CountdownDisplay::KEY => find::<CountdownDisplay>(name),
+        DateDisplay::KEY => find::<DateDisplay>(name),
+        ChimeMode::KEY => find::<ChimeMode>(name),
+        _ => None,
+    }
+}
+
+/// Define a choice enum, its config key, and the name and explanation of
+/// each option.
+macro_rules! choice {
+    (
+        $(#[$meta:meta])*
+        $name:ident = $key:literal {
+            $($variant:ident => $label:literal: $help:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $(#[doc = $help] $variant,)+
+        }
+
+        impl Choice for $name {
+            const KEY: &'static str = $key;
+            const ALL: &'static [Self] = &[$($name::$variant,)+];
+
+            fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => $label,)+
+                }
+            }
+
+            fn help(self) -> &'static str {
+                match self {
+                    $($name::$variant => $help,)+
+                }
+            }
+        }
+    };
+}
+
+choice! {
+    /// What the whole screen shows.
+    DisplayMode = "display mode" {
+        Analog => "analog": "the dial with its hands and readouts",
+        WorldClocks => "world clocks": "one small dial per zone of the \"world clocks\" list",
+        BigDigital => "big digital": "the time in digits as big as the terminal",
+        WordClock => "word clock": "the time spelled out in a grid of letters",
+        RadialChart => "radial chart": "the chart data as spokes around the dial, under the hands",
+    }
+}
+
+choice! {
+    /// What opens at startup.
+    StartupPage = "startup page" {
+        Last => "last": "the face of \"display mode\", as it was left",
+        Clock => "clock": "the dial with its hands and readouts",
+        WorldClocks => "world clocks": "one small dial per zone of the \"world clocks\" list",
+        Pomodoro => "pomodoro": "the dial with a focus session started",
+        Alarms => "alarms": "the list of alarms, the clock behind it",
+        History => "history": "the alarms that rang and the pomodoro phases that ended",
+    }
+}
+
+choice! {
+    /// Language of the word clock.
+    WordClockLanguage = "word clock language" {
+        English => "english": "IT IS QUARTER PAST THREE",
+        French => "french": "IL EST TROIS HEURES ET QUART",
+    }
+}
+
+choice! {
+    /// Characters the screen may use.
+    CharacterSet = "character set" {
+        Auto => "auto": "plain ASCII when the locale is not UTF-8, everything otherwise",
+        Unicode => "unicode": "box drawing, blocks, braille and arrows",
+        Ascii => "ascii": "plain ASCII stand-ins, for legacy terminals",
+    }
+}
+
+choice! {
+    /// Fill of the blocks of the big digits, to match the density of the terminal font.
+    DigitWeight = "big digit weight" {
+        Light => "light": "light shade blocks ░",
+        Regular => "regular": "medium shade blocks ▒",
+        Bold => "bold": "full blocks █",
+    }
+}
+
+choice! {
+    /// Where the dial sits on the screen, before the offsets.
+    ClockPosition = "clock position" {
+        Center => "center": "in the middle of the screen",
+        TopLeft => "top left": "against the top left corner",
+        TopRight => "top right": "against the top right corner",
+        BottomLeft => "bottom left": "against the bottom left corner",
+        BottomRight => "bottom right": "against the bottom right corner",
+    }
+}
+
+choice! {
+    /// What is drawn around the dial.
+    ClockBorder = "clock border" {
+        Full => "full": "the whole outline, drawn as \"border style\" says",
+        DotAndHours => "dot and hours": "a dot each minute and a tick each hour",
+        Hours => "hours": "a star at each hour, nothing in between",
+        NoBorder => "no border": "no outline",
+    }
+}
+
+choice! {
+    /// How the line of the full border is drawn.
+    BorderLine = "border style" {
+        Characters => "characters": "the border characters, repeated clockwise from 12 o'clock",
+        Ascii => "ascii lines": "-, |, / and \\ following the curve",
+        BoxDrawing => "box drawing": "box-drawing lines following the curve",
+        Braille => "braille": "braille dots, four times finer vertically and twice horizontally",
+        DoubleRing => "double ring": "two concentric rings of the border characters",
+    }
+}
+
+choice! {
+    /// What fills the inside of the dial.
+    FaceFill = "face fill" {
+        NoFill => "no fill": "the terminal background shows through",
+        Character => "character": "the face character everywhere inside the dial",
+        Shading => "shading": "the face character, denser towards the rim",
+    }
+}
+
+choice! {
+    /// Whether and how the second hand is drawn.
+    DisplaySeconds = "display seconds" {
+        NoDisplay => "no display": "no second hand, one redraw a second",
+        FullEachSecond => "full each second": "whole hand ticking once a second",
+        FullContinuous => "full continuous": "whole hand sweeping smoothly, ~33 fps",
+        EndEachSecond => "end of hand each second": "outer tip only, ticking once a second",
+        EndContinuous => "end of hand full continuous": "smooth outer tick, ~33 fps",
+    }
+}
+
+impl DisplaySeconds {
+    /// Whether the hand sweeps instead of ticking.
+    pub fn is_continuous(self) -> bool {
+        matches!(
+            self,
+            DisplaySeconds::FullContinuous | DisplaySeconds::EndContinuous
+        )
+    }
+
+    /// Whether only the end of the hand is drawn.
+    pub fn is_end_only(self) -> bool {
+        matches!(
+            self,
+            DisplaySeconds::EndEachSecond | DisplaySeconds::EndContinuous
+        )
+    }
+}
+
+choice! {
+    /// Ring inside the border filling up as the day or the hour goes by.
+    ProgressRing = "progress ring" {
+        Off => "off": "no ring",
+        Day => "day": "one turn a day, from midnight",
+        Hour => "hour": "one turn an hour, from the full hour",
+    }
+}
+
+choice! {
+    /// Sub-dial at 9 o'clock.
+    NineOClockSubDial = "9 o'clock sub-dial" {
+        Off => "off": "no sub-dial at 9 o'clock",
+        TwentyFourHours => "24 hours": "one turn a day, midnight at the top",
+        SecondTimeZone => "second time zone": "hour and minute hands in the \"sub-dial time zone\"",
+    }
+}
+
+choice! {
+    /// Day of the month in a small window of the dial, like on a watch.
+    DateWindow = "date window" {
+        Off => "off": "no date window",
+        ThreeOClock => "3 o'clock": "the day of the month in a box between the center and 3 o'clock",
+        SixOClock => "6 o'clock": "the day of the month in a box between the center and 6 o'clock",
+    }
+}
+
+choice! {
+    /// Power reserve style gauge of how long something has been running.
+    UptimeGauge = "uptime gauge" {
+        Off => "off": "no uptime gauge",
+        System => "system": "time since the computer started",
+        Clock => "clock": "time since the clock started",
+    }
+}
+
+choice! {
+    /// Markers of the hours.
+    Numbers = "numbers" {
+        Off => "no numbers": "nothing at the hours",
+        Stars => "stars": "a star at each hour",
+        Digits => "numbers": "hour numbers, written as \"numbers style\" says",
+    }
+}
+
+choice! {
+    /// Text of the hour numbers.
+    NumbersStyle = "numbers style" {
+        Arabic => "arabic": "1 to 12",
+        Roman => "roman": "I to XII",
+        TwentyFourHours => "24 hours": "13 to 24 in the afternoon",
+        QuartersOnly => "quarters only": "12, 3, 6 and 9",
+    }
+}
+
+choice! {
+    /// Digital readout below the dial.
+    DigitalDisplay = "digital display" {
+        Off => "off": "no digital time",
+        TwentyFourHours => "24 hours": "17:45:09 below the centre",
+        TwelveHours => "12 hours": "05:45:09 PM below the centre",
+    }
+}
+
+choice! {
+    /// Time left readout above the dial.
+    CountdownDisplay = "countdown display" {
+        Off => "off": "no countdown",
+        NextMinute => "next minute": "seconds left in the minute",
+        NextHour => "next hour": "minutes and seconds left in the hour",
+        NextDay => "next day": "hours and minutes left until midnight",
+    }
+}
+
+choice! {
+    /// Date window of the dial.
+    DateDisplay = "date display" {
+        Off => "off": "no date",
+        Iso => "ISO": "2024-05-17 at 3 o'clock",
+        Locale => "locale": "day and date in the language of the system",
+        Custom => "custom": "the \"date format\" (strftime) at 3 o'clock",
+    }
+}
+
+choice! {
+    /// When the terminal bell rings.
+    ChimeMode = "chime" {
+        Off => "off": "silent",
+        OnTheHour => "bell on the hour": "one bell each full hour",
+        OnTheHalfHour => "bell on the half hour": "one bell each full and half hour",
+        Cuckoo => "cuckoo": "one bell per hour on the hour, one on the half hour",
+    }
+}
+
+choice! {
+    /// What an alarm does when it starts ringing, besides flashing and ringing
+    /// the bell; "action" field of the items of the "alarms" list.
+    AlarmAction = "action" {
+        None => "none": "nothing more",
+        Command => "command": "run the action text as a shell command",
+        Message => "message": "show the action text on the dial",
+        Preset => "preset": "apply the preset named by the action text, e.g. a night theme",
+        Pomodoro => "pomodoro": "start a focus session",
+    }
+}
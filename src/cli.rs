@@ -0,0 +1,142 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::choices::{Choice, ClockBorder, DisplayMode, DisplaySeconds, StartupPage};
+use crate::config_edit::Config;
+use crate::presets;
+
+/// An analog clock for the terminal. The options below only change this run:
+/// the config file is left as it is.
+#[derive(Parser)]
+#[command(name = "tac", version)]
+pub struct Cli {
+    /// Config file to read and save, instead of $TAC_CONFIG or
+    /// ~/.config/terminal_analog_clock/config.json
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Apply the settings of the preset with this name
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+    /// Time zone to show, an IANA name such as Europe/Paris
+    #[arg(long, visible_alias = "tz", value_name = "TZ", value_parser = parse_timezone)]
+    pub timezone: Option<String>,
+    /// Show this moment instead of now, e.g. 1969-07-20T20:17, in the zone
+    /// shown; the clock goes on from there
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub date: Option<NaiveDateTime>,
+    /// What opens first: the last face, the dial, the world clocks, a focus
+    /// session, the alarms or their history
+    #[arg(long, value_name = "PAGE", value_parser = options::<StartupPage>())]
+    pub page: Option<String>,
+    /// How the second hand is drawn
+    #[arg(long, value_name = "MODE", value_parser = options::<DisplaySeconds>())]
+    pub seconds: Option<String>,
+    /// What is drawn around the dial
+    #[arg(long, value_name = "MODE", value_parser = options::<ClockBorder>())]
+    pub border: Option<String>,
+    /// Redraw at most this many times a second
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub fps: Option<u32>,
+    /// Draw everything in the default colors of the terminal
+    #[arg(long)]
+    pub no_color: bool,
+    /// Print the clock once as text and exit
+    #[arg(long)]
+    pub print: bool,
+    /// Ask for the "settings PIN" before opening the settings or the alarms
+    #[arg(long)]
+    pub kiosk: bool,
+    /// Minimal face for small embedded panes
+    #[arg(long)]
+    pub pane: bool,
+    /// List the problems of the config file and exit
+    #[arg(long)]
+    pub diagnose: bool,
+    /// Show how the hands are rasterized, and the CPU usage
+    #[arg(long)]
+    pub debug_render: bool,
+    /// Color the cells by how often they were redrawn lately
+    #[arg(long)]
+    pub heat_map: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, PartialEq, Eq)]
+pub enum Command {
+    /// Show every option of the display settings in turn
+    Gallery,
+}
+
+/// Names of the options of `C`, the only values accepted.
+fn options<C: Choice>() -> PossibleValuesParser {
+    PossibleValuesParser::new(C::ALL.iter().map(|c| c.name()))
+}
+
+fn parse_timezone(name: &str) -> Result<String, String> {
+    name.parse::<Tz>()
+        .map(|_| name.to_string())
+        .map_err(|_| format!("unknown time zone \"{name}\""))
+}
+
+/// "YYYY-MM-DD", at midnight, or with a time "THH:MM" or "THH:MM:SS" (a
+/// space may stand for the T).
+fn parse_date(text: &str) -> Result<NaiveDateTime, String> {
+    [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .map(|date| date.and_time(Default::default()))
+    })
+    .ok_or_else(|| format!("\"{text}\" is not a date like 1969-07-20T20:17"))
+}
+
+impl Cli {
+    /// Apply the theme, then the other settings given on the command line and
+    /// the face of the startup page, as session overrides: the saved "display
+    /// mode" stays. Fails on a theme no preset is named after.
+    pub fn apply(&self, cfg: &mut Config) -> Result<(), String> {
+        if let Some(theme) = &self.theme {
+            let presets = presets::presets(cfg);
+            let Some(preset) = presets.iter().find(|p| p.name.eq_ignore_ascii_case(theme)) else {
+                let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+                return Err(format!(
+                    "no preset named \"{theme}\" (presets: {})",
+                    names.join(", ")
+                ));
+            };
+            for (key, value) in &preset.settings {
+                cfg.override_value(key, value);
+            }
+        }
+        for (key, value) in [
+            ("timezone", &self.timezone),
+            (DisplaySeconds::KEY, &self.seconds),
+            (ClockBorder::KEY, &self.border),
+            (StartupPage::KEY, &self.page),
+        ] {
+            if let Some(value) = value {
+                cfg.override_value(key, value);
+            }
+        }
+        let face = match cfg.get::<StartupPage>() {
+            StartupPage::Clock | StartupPage::Pomodoro => Some(DisplayMode::Analog),
+            StartupPage::WorldClocks => Some(DisplayMode::WorldClocks),
+            _ => None,
+        };
+        if let Some(face) = face {
+            cfg.override_value(DisplayMode::KEY, face.name());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Where the current time comes from.
+//!
+//! Rendering never calls `Utc::now()` itself: it asks a [`Clock`], so that
+//! tests and demos can drive the dial with fixed, reproducible times.
+//!
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use tac::clock::{Clock, FakeClock};
+//!
+//! let clock = FakeClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 10, 9, 30).unwrap());
+//! clock.advance(chrono::Duration::seconds(30));
+//! assert_eq!(clock.now(), Utc.with_ymd_and_hms(2024, 1, 1, 10, 10, 0).unwrap());
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use std::cell::Cell;
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock standing still at a given instant until it is moved by hand.
+#[derive(Clone, Debug)]
+pub struct FakeClock {
+    now: Cell<DateTime<Utc>>,
+}
+
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+}
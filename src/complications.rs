@@ -0,0 +1,95 @@
+use chrono::{DateTime, FixedOffset, Locale, Timelike};
+use std::env;
+use std::fmt::Write;
+
+use tac::date;
+use tac::framebuffer::Style;
+
+use crate::choices::{CountdownDisplay, DateDisplay, DigitalDisplay};
+use crate::config_edit::Config;
+use crate::layout::{Layout, Slot};
+use crate::time;
+
+/// Color pair used by the digital readout.
+pub const DIGITAL_PAIR: i16 = 6;
+/// Color pair used by the date window.
+pub const DATE_PAIR: i16 = 7;
+/// Color pair used by the time zone caption.
+pub const CAPTION_PAIR: i16 = 8;
+
+/// Place the "digital display" readout (HH:MM:SS, 12 or 24 hours) under the analog face.
+pub fn add_digital(layout: &mut Layout, cfg: &Config, now: &DateTime<FixedOffset>) {
+    let text = match cfg.get::<DigitalDisplay>() {
+        DigitalDisplay::TwentyFourHours => now.format("%H:%M:%S").to_string(),
+        DigitalDisplay::TwelveHours => now.format("%I:%M:%S %p").to_string(),
+        DigitalDisplay::Off => return,
+    };
+    layout.add(Slot::South, 20, text, Style::pair(DIGITAL_PAIR));
+}
+
+/// Place the "countdown display" readout ("next hour in 17m 22s") above the analog face.
+pub fn add_countdown(layout: &mut Layout, cfg: &Config, now: &DateTime<FixedOffset>) {
+    let into_minute = now.second();
+    let into_hour = now.minute() * 60 + into_minute;
+    let text = match cfg.get::<CountdownDisplay>() {
+        CountdownDisplay::NextMinute => format!("next minute in {}s", 60 - into_minute),
+        CountdownDisplay::NextHour => {
+            let left = 3600 - into_hour;
+            format!("next hour in {}m {:02}s", left / 60, left % 60)
+        }
+        CountdownDisplay::NextDay => {
+            let now = now.naive_local();
+            let Some(midnight) = date::next_midnight(now) else {
+                return;
+            };
+            // Rounded up, like the other countdowns
+            let left = ((midnight - now).num_seconds() + 59) / 60;
+            format!("next day in {}h {:02}m", left / 60, left % 60)
+        }
+        CountdownDisplay::Off => return,
+    };
+    layout.add(Slot::North, 20, text, Style::pair(DIGITAL_PAIR));
+}
+
+/// Locale of dates, read from the usual environment variables ("fr_FR.UTF-8" → fr_FR).
+fn time_locale() -> Locale {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            let name = value.split(['.', '@']).next().unwrap_or_default();
+            Locale::try_from(name).ok()
+        })
+        .unwrap_or(Locale::POSIX)
+}
+
+/// Text of the date window, or `None` when it is off or the custom format is invalid.
+fn date_text(cfg: &Config, now: &DateTime<FixedOffset>) -> Option<String> {
+    let mut text = String::new();
+    let written = match cfg.get::<DateDisplay>() {
+        DateDisplay::Iso => write!(text, "{}", now.format("%Y-%m-%d")),
+        DateDisplay::Locale => write!(text, "{}", now.format_localized("%a %e", time_locale())),
+        DateDisplay::Custom => write!(text, "{}", now.format(&cfg.get_string("date format")?)),
+        DateDisplay::Off => return None,
+    };
+    // chrono reports unknown specifiers of a custom format as a formatting error
+    written.ok().map(|_| text.trim().to_string())
+}
+
+/// Place the "date display" window like a watch date complication: inside the dial,
+/// between the centre and the 3 o'clock marker.
+pub fn add_date(layout: &mut Layout, cfg: &Config, now: &DateTime<FixedOffset>) {
+    if let Some(text) = date_text(cfg, now) {
+        layout.add(Slot::East, 10, text, Style::pair(DATE_PAIR));
+    }
+}
+
+/// Place the "zone caption" ("Paris (UTC+01:00, CET)") under the dial, below the
+/// digital readout when both are shown.
+pub fn add_caption(layout: &mut Layout, cfg: &Config, now: &DateTime<FixedOffset>) {
+    if cfg.get_bool("zone caption") {
+        let text = time::zone_caption(cfg, now);
+        layout.add(Slot::South, 10, text, Style::pair(CAPTION_PAIR));
+    }
+}
@@ -1,7 +1,26 @@
 use ncurses::*;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fs;
-use std::path::Path;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::charset;
+use crate::choices::{
+    self, BorderLine, CharacterSet, ChimeMode, Choice, ClockBorder, ClockPosition,
+    CountdownDisplay, DateDisplay, DateWindow, DigitWeight, DigitalDisplay, DisplayMode,
+    DisplaySeconds, FaceFill, NineOClockSubDial, Numbers, NumbersStyle, ProgressRing, StartupPage,
+    UptimeGauge, WordClockLanguage,
+};
+use crate::keymap;
+use crate::labels;
+use crate::mouse::{self, MouseEvent};
+use crate::palette::{self, ColorSpec};
+use crate::render;
+use crate::signals;
+use crate::time::{self, SessionClock};
+use tac::face;
+use tac::geometry::{ellipse_points, line_points};
 
 const CATEGORY_PAIR: i16 = 1;
 const PAIR_BLACK: i16 = 2;
@@ -12,12 +31,20 @@ const PAIR_BLUE: i16 = 6;
 const PAIR_MAGENTA: i16 = 7;
 const PAIR_CYAN: i16 = 8;
 const PAIR_WHITE: i16 = 9;
+/// First pair showing custom colors, one per entry.
+const CUSTOM_PAIRS: i16 = 16;
+/// Pairs of the clock preview, after the custom colors: one per pair of the
+/// clock, pair 0 for its background.
+const PREVIEW_PAIRS: i16 = 16;
+/// Size of the clock preview in the top right corner.
+const PREVIEW_COLS: i32 = 26;
+const PREVIEW_ROWS: i32 = 12;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 static NEED_TO_INIT_NCURSES: AtomicBool = AtomicBool::new(false);
 static SAVE_WHEN_CHANGE: AtomicBool = AtomicBool::new(true);
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 enum Value {
     /// Free-text string
@@ -28,7 +55,12 @@ enum Value {
         maximum_size: Option<usize>,
     },
 
-    /// Choice in a list of options
+    /// Choice in a list of options. The options come from [`choices`]; the
+    /// file only stores the name of the selected one.
+    #[serde(
+        serialize_with = "serialize_choice",
+        deserialize_with = "deserialize_choice"
+    )]
     Choice {
         options: Vec<String>,
         selected: usize,
@@ -37,39 +69,354 @@ enum Value {
     /// Non-editable category/header
     Category,
 
-    /// Color choice, works like Choice but rendered with ncurses colors
+    /// Color choice, works like Choice but rendered with ncurses colors. The
+    /// file stores the name of the selected color.
+    #[serde(
+        serialize_with = "serialize_color",
+        deserialize_with = "deserialize_color"
+    )]
     Color {
         options: Vec<String>,
         selected: usize,
+        /// Palette index ("208") or hex RGB ("#ff8800") used instead of the
+        /// selected option
+        custom: Option<String>,
     },
 
     /// Integer value
     Integer { value: i64 },
 
+    /// Decimal value, e.g. a ratio
+    Float { value: f64 },
+
     /// Boolean value (toggled with Space, Enter, Left, or Right)
     Boolean { value: bool },
+
+    /// Keyboard shortcut (edited by pressing the desired key)
+    Shortcut { value: String },
+
+    /// List of items, each a JSON string or object (edited in the JSON file)
+    List { items: Vec<serde_json::Value> },
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl Value {
+    /// Name shown for a color entry: the custom color if set, else the
+    /// selected option.
+    fn color_name(&self) -> &str {
+        match self {
+            Value::Color {
+                custom: Some(custom),
+                ..
+            } => custom,
+            Value::Color {
+                options, selected, ..
+            } => options.get(*selected).map(|s| s.as_str()).unwrap_or("<?>"),
+            _ => "",
+        }
+    }
+
+    /// Go back from a custom color to the named options.
+    fn clear_custom_color(&mut self) {
+        if let Value::Color { custom, .. } = self {
+            *custom = None;
+        }
+    }
+
+    /// A choice among the options of `C`, `selected` selected.
+    fn choice<C: Choice>(selected: C) -> Value {
+        Value::Choice {
+            options: choices::names::<C>(),
+            selected: selected.index(),
+        }
+    }
+
+    /// This value as the kind of `default`, for a setting whose kind changed
+    /// between versions: integers become decimals, a text holding one key
+    /// (the shortcuts before they had a kind of their own) a shortcut. `None`
+    /// when it can't be.
+    fn converted(self, default: &Value) -> Option<Value> {
+        match (default, self) {
+            (current, value) if mem::discriminant(current) == mem::discriminant(&value) => {
+                Some(value)
+            }
+            (Value::Float { .. }, Value::Integer { value }) => Some(Value::Float {
+                value: value as f64,
+            }),
+            (Value::Shortcut { .. }, Value::Text { value, .. }) => {
+                let code = keymap::parse_key(&value)?;
+                Some(Value::Shortcut {
+                    value: keymap::key_name(code).unwrap_or(value),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A choice as written in the file: the name of the selected option. Files
+/// written by older versions hold the option list and the index instead.
+#[derive(Serialize, Deserialize)]
+struct StoredChoice {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    options: Vec<String>,
+    selected: StoredSelection,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredSelection {
+    Index(usize),
+    Name(String),
+}
+
+fn serialize_choice<S: Serializer>(
+    options: &[String],
+    selected: &usize,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let name = options.get(*selected).cloned().unwrap_or_default();
+    StoredChoice {
+        options: Vec::new(),
+        selected: StoredSelection::Name(name),
+    }
+    .serialize(serializer)
+}
+
+/// Read a choice of either format. A name alone gives a one-option list,
+/// replaced by the options of the code when the file is loaded.
+fn deserialize_choice<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(Vec<String>, usize), D::Error> {
+    let stored = StoredChoice::deserialize(deserializer)?;
+    Ok(match stored.selected {
+        StoredSelection::Index(index) => (stored.options, index),
+        StoredSelection::Name(name) => (vec![name], 0),
+    })
+}
+
+/// A color as written in the file, like [`StoredChoice`] with the custom color.
+#[derive(Serialize, Deserialize)]
+struct StoredColor {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    options: Vec<String>,
+    selected: StoredSelection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    custom: Option<String>,
+}
+
+fn serialize_color<S: Serializer>(
+    options: &[String],
+    selected: &usize,
+    custom: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let name = options.get(*selected).cloned().unwrap_or_default();
+    StoredColor {
+        options: Vec::new(),
+        selected: StoredSelection::Name(name),
+        custom: custom.clone(),
+    }
+    .serialize(serializer)
+}
+
+/// Read a color of either format, a name alone like [`deserialize_choice`].
+fn deserialize_color<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(Vec<String>, usize, Option<String>), D::Error> {
+    let stored = StoredColor::deserialize(deserializer)?;
+    Ok(match stored.selected {
+        StoredSelection::Index(index) => (stored.options, index, stored.custom),
+        StoredSelection::Name(name) => (vec![name], 0, stored.custom),
+    })
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 struct Entry {
     key: String,
     value: Value,
+    /// What the entry does, shown under the list in the editor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Entries of one category, exported on their own to share a theme or a keymap.
+#[derive(Serialize, Deserialize, Debug)]
+struct Snippet {
+    category: String,
+    entries: Vec<Entry>,
+}
+
+/// File name of the snippet of a category: "Keyboard shortcuts" → tac-keyboard-shortcuts.json.
+fn snippet_file_name(category: &str) -> String {
+    let name: Vec<String> = category
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    format!("tac-{}.json", name.join("-"))
+}
+
+/// Modification time of the file at `filename`, if it can be read.
+fn modified(filename: &str) -> Option<SystemTime> {
+    fs::metadata(filename).and_then(|m| m.modified()).ok()
+}
+
+/// Whether the config file at `filename` is written in TOML rather than JSON.
+fn is_toml(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"))
+}
+
+/// Key of an entry in a TOML file: "circle color" → circle_color, quoted when
+/// it holds other characters ("9 o'clock sub-dial").
+fn toml_key(key: &str) -> String {
+    toml_name(&key.replace(' ', "_"))
+}
+
+/// `name` as a TOML key: bare when it can be, quoted otherwise.
+fn toml_name(name: &str) -> String {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        name.to_string()
+    } else {
+        toml::Value::String(name.into()).to_string()
+    }
+}
+
+/// Append one `key = value` line per entry to `text`, under a comment with
+/// its description, a comment heading each category.
+fn push_toml_entries(text: &mut String, entries: &[Entry]) {
+    for entry in entries {
+        let value = match &entry.value {
+            Value::Category => {
+                text.push_str(&format!("\n# {}\n", entry.key));
+                continue;
+            }
+            Value::Text { value, .. } | Value::Shortcut { value } => {
+                toml::Value::String(value.clone())
+            }
+            Value::Choice { options, selected } => {
+                toml::Value::String(options.get(*selected).cloned().unwrap_or_default())
+            }
+            Value::Color { .. } => toml::Value::String(entry.value.color_name().into()),
+            Value::Integer { value } => toml::Value::Integer(*value),
+            Value::Float { value } => toml::Value::Float(*value),
+            Value::Boolean { value } => toml::Value::Boolean(*value),
+            // TOML has no null: items holding one are left out
+            Value::List { items } => toml::Value::Array(
+                items
+                    .iter()
+                    .filter_map(|item| toml::Value::try_from(item).ok())
+                    .collect(),
+            ),
+        };
+        if let Some(description) = &entry.description {
+            text.push_str(&format!("# {description}\n"));
+        }
+        text.push_str(&format!("{} = {value}\n", toml_key(&entry.key)));
+    }
+}
+
+/// Index in [`palette::BASIC_NAMES`] of the default color of `pair`, one of
+/// the pairs of the dial.
+fn dial_color(pair: i16) -> usize {
+    face::DEFAULT_COLORS
+        .iter()
+        .find(|&&(p, _)| p == pair)
+        .and_then(|(_, name)| {
+            palette::BASIC_NAMES
+                .iter()
+                .position(|basic| basic.eq_ignore_ascii_case(name))
+        })
+        .unwrap_or(0)
+}
+
+/// Version of the entries written by this build: the number of steps in
+/// [`MIGRATIONS`]. Files without one are version 0.
+const CONFIG_VERSION: u32 = 3;
+
+/// Upgrades of a config file, the one at index `n` from version `n` to `n + 1`.
+/// Add a step (and raise [`CONFIG_VERSION`]) when a key is renamed or its value
+/// changes meaning; new keys need none, they are added with their defaults.
+const MIGRATIONS: [fn(&mut Config); CONFIG_VERSION as usize] = [
+    Config::rename_legacy_entries,
+    Config::convert_changed_kinds,
+    Config::hand_lengths_as_fractions,
+];
+
+/// Name of the profile of a file written before there were profiles.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Default entry named `key`, as in a new config.
+fn default_entry(filename: &str, key: &str) -> Option<Entry> {
+    Config::default(filename)
+        .entries
+        .into_iter()
+        .find(|e| e.key == key && !matches!(e.value, Value::Category))
+}
+
+fn default_profile() -> String {
+    DEFAULT_PROFILE.into()
+}
+
+/// A named full set of entries, e.g. "work" or "night", kept in the file
+/// while another profile is in use.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Profile {
+    name: String,
+    entries: Vec<Entry>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     filename: String,
+    #[serde(default)]
+    version: u32,
+    /// Name of the profile in use, whose entries are `entries`
+    #[serde(default = "default_profile")]
+    profile: String,
     entries: Vec<Entry>,
+    /// The other profiles, in the order [`Config::next_profile`] goes through them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    profiles: Vec<Profile>,
+    /// Session-only values hiding the saved ones (never written to the file)
+    #[serde(skip)]
+    overrides: Vec<Entry>,
+    /// Modification time of the file when it was last read or written here
+    #[serde(skip)]
+    modified: Option<SystemTime>,
+    /// What had to be left out or replaced by defaults to load the file
+    #[serde(skip)]
+    repairs: Vec<String>,
+    /// Entries of the profile in use as last read from or written to the file
+    #[serde(skip)]
+    saved: Vec<Entry>,
 }
 
 impl Config {
     pub fn default(filename_str: &str) -> Self {
         Self {
             filename: String::from(filename_str),
+            version: CONFIG_VERSION,
+            profile: default_profile(),
+            profiles: Vec::new(),
+            overrides: Vec::new(),
+            modified: None,
+            repairs: Vec::new(),
+            saved: Vec::new(),
             entries: vec![
                 Entry {
                     key: "Colors".into(),
                     value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "monochrome".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("No colors: bold hands, a dim border and plain digits instead; on when NO_COLOR is set".into()),
                 },
                 Entry {
                     key: "background color".into(),
@@ -85,7 +432,9 @@ impl Config {
                             "WHITE".into(),
                         ],
                         selected: 0, // BLACK
+                        custom: None,
                     },
+                    description: Some("Color behind the whole clock; text is drawn black or white to stay readable on it".into()),
                 },
                 Entry {
                     key: "circle color".into(),
@@ -100,8 +449,46 @@ impl Config {
                             "CYAN".into(),
                             "WHITE".into(),
                         ],
-                        selected: 2, // GREEN
+                        selected: dial_color(face::BORDER_PAIR),
+                        custom: None,
+                    },
+                    description: Some("Color of the border of the dial".into()),
+                },
+                Entry {
+                    key: "face color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: dial_color(face::FACE_PAIR),
+                        custom: None,
                     },
+                    description: Some("Color of the face fill and of the face character".into()),
+                },
+                Entry {
+                    key: "progress ring color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: dial_color(face::PROGRESS_PAIR),
+                        custom: None,
+                    },
+                    description: Some("Color of the progress ring just inside the border".into()),
                 },
                 Entry {
                     key: "seconds color".into(),
@@ -116,8 +503,10 @@ impl Config {
                             "CYAN".into(),
                             "WHITE".into(),
                         ],
-                        selected: 6, // CYAN
+                        selected: dial_color(face::SECONDS_PAIR),
+                        custom: None,
                     },
+                    description: Some("Color of the second hand".into()),
                 },
                 Entry {
                     key: "digits color".into(),
@@ -132,8 +521,10 @@ impl Config {
                             "CYAN".into(),
                             "WHITE".into(),
                         ],
-                        selected: 7, // WHITE
+                        selected: dial_color(face::DIGITS_PAIR),
+                        custom: None,
                     },
+                    description: Some("Color of the hour numbers and of the sub-dial labels".into()),
                 },
                 Entry {
                     key: "minutes color".into(),
@@ -148,11 +539,85 @@ impl Config {
                             "CYAN".into(),
                             "WHITE".into(),
                         ],
-                        selected: 3, // YELLOW
+                        selected: dial_color(face::MINUTES_PAIR),
+                        custom: None,
                     },
+                    description: Some("Color of the minute hand".into()),
                 },
                 Entry {
                     key: "hours color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: dial_color(face::HOURS_PAIR),
+                        custom: None,
+                    },
+                    description: Some("Color of the hour hand".into()),
+                },
+                Entry {
+                    key: "digital color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: 7, // WHITE
+                        custom: None,
+                    },
+                    description: Some("Color of the digital readout of the time".into()),
+                },
+                Entry {
+                    key: "date color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: 6, // CYAN
+                        custom: None,
+                    },
+                    description: Some("Color of the date readout".into()),
+                },
+                Entry {
+                    key: "caption color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: 7, // WHITE
+                        custom: None,
+                    },
+                    description: Some("Color of the caption naming the time zone".into()),
+                },
+                Entry {
+                    key: "pomodoro work color".into(),
                     value: Value::Color {
                         options: vec![
                             "BLACK".into(),
@@ -165,11 +630,32 @@ impl Config {
                             "WHITE".into(),
                         ],
                         selected: 1, // RED
+                        custom: None,
                     },
+                    description: Some("Color of the arc of a pomodoro work phase".into()),
+                },
+                Entry {
+                    key: "pomodoro break color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: 2, // GREEN
+                        custom: None,
+                    },
+                    description: Some("Color of the arc of a pomodoro break".into()),
                 },
                 Entry {
                     key: "Hand labels".into(),
                     value: Value::Category,
+                    description: None,
                 },
                 Entry {
                     key: "hour hand label".into(),
@@ -177,6 +663,7 @@ impl Config {
                         value: "HOURS".into(),
                         maximum_size: Some(32),
                     },
+                    description: Some("Text repeated along the hour hand".into()),
                 },
                 Entry {
                     key: "minute hand label".into(),
@@ -184,6 +671,7 @@ impl Config {
                         value: "minutes".into(),
                         maximum_size: Some(32),
                     },
+                    description: Some("Text repeated along the minute hand".into()),
                 },
                 Entry {
                     key: "second hand label".into(),
@@ -191,113 +679,1301 @@ impl Config {
                         value: ".".into(),
                         maximum_size: Some(32),
                     },
+                    description: Some("Text repeated along the second hand".into()),
+                },
+                Entry {
+                    key: "Hand lengths".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "hour hand length".into(),
+                    value: Value::Float {
+                        value: face::HOUR_HAND_LENGTH,
+                    },
+                    description: Some("Length of the hour hand as a fraction of the dial radius, 0.1 to 1".into()),
+                },
+                Entry {
+                    key: "minute hand length".into(),
+                    value: Value::Float {
+                        value: face::MINUTE_HAND_LENGTH,
+                    },
+                    description: Some("Length of the minute hand as a fraction of the dial radius, 0.1 to 1".into()),
+                },
+                Entry {
+                    key: "second hand length".into(),
+                    value: Value::Float {
+                        value: face::SECOND_HAND_LENGTH,
+                    },
+                    description: Some("Length of the second hand as a fraction of the dial radius, 0.1 to 1".into()),
+                },
+                Entry {
+                    key: "hand tail length".into(),
+                    value: Value::Float {
+                        value: face::HAND_TAIL_LENGTH,
+                    },
+                    description: Some("Part of the hour and minute hands behind the centre, as a fraction of their length, 0 to 0.5".into()),
+                },
+                Entry {
+                    key: "second hand tip start".into(),
+                    value: Value::Float { value: 0.8 },
+                    description: Some("Where \"end of hand\" second hands start, as a fraction of their length, 0 to 0.9".into()),
+                },
+                Entry {
+                    key: "center hub size".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Rows of the filled hub over the centre, 0 for none".into()),
+                },
+                Entry {
+                    key: "Display modes".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "clock border".into(),
+                    value: Value::choice(ClockBorder::DotAndHours),
+                    description: Some("What is drawn around the dial: a full line, minute dots and hour ticks, hour marks or nothing".into()),
+                },
+                Entry {
+                    key: "border style".into(),
+                    value: Value::choice(BorderLine::Characters),
+                    description: Some("Line of the full border: repeated characters, ASCII or box-drawing slopes, braille or a double ring".into()),
+                },
+                Entry {
+                    key: "border characters".into(),
+                    value: Value::Text {
+                        value: "*".into(),
+                        maximum_size: Some(16),
+                    },
+                    description: Some("Repeated around the full border by the \"characters\" and \"double ring\" styles".into()),
+                },
+                Entry {
+                    key: "minute track file".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("File of up to 60 values, one per minute, coloring the rim and feeding the radial chart".into()),
+                },
+                Entry {
+                    key: "face fill".into(),
+                    value: Value::choice(FaceFill::NoFill),
+                    description: Some("Inside of the dial: empty, the face character, or shades of it getting denser towards the rim".into()),
+                },
+                Entry {
+                    key: "face character".into(),
+                    value: Value::Text {
+                        value: "·".into(),
+                        maximum_size: Some(1),
+                    },
+                    description: Some("Character filling the face when \"face fill\" is on".into()),
+                },
+                Entry {
+                    key: "display seconds".into(),
+                    value: Value::choice(DisplaySeconds::FullEachSecond),
+                    description: Some("How the second hand is drawn: whole or only its end, jumping each second or sweeping".into()),
+                },
+                Entry {
+                    key: "numbers".into(),
+                    value: Value::choice(Numbers::Off),
+                    description: Some("Markers at the hours: nothing, stars or numbers".into()),
+                },
+                Entry {
+                    key: "numbers style".into(),
+                    value: Value::choice(NumbersStyle::Arabic),
+                    description: Some("How hour numbers are written: arabic, Roman, 24-hour or only at the quarters".into()),
+                },
+                Entry {
+                    key: "digital display".into(),
+                    value: Value::choice(DigitalDisplay::Off),
+                    description: Some("Digital readout of the time next to the dial".into()),
+                },
+                Entry {
+                    key: "countdown display".into(),
+                    value: Value::choice(CountdownDisplay::Off),
+                    description: Some("Time left until the next minute, hour or day, next to the dial".into()),
+                },
+                Entry {
+                    key: "progress ring".into(),
+                    value: Value::choice(ProgressRing::Off),
+                    description: Some("Arc just inside the border showing the part of the day or hour gone by".into()),
+                },
+                Entry {
+                    key: "seconds sub-dial".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("Small dial at 6 o'clock carrying the second hand".into()),
+                },
+                Entry {
+                    key: "9 o'clock sub-dial".into(),
+                    value: Value::choice(NineOClockSubDial::Off),
+                    description: Some("Small dial at 9 o'clock: a 24-hour hand or the time of another zone".into()),
+                },
+                Entry {
+                    key: "sub-dial time zone".into(),
+                    value: Value::Text {
+                        value: "UTC".into(),
+                        maximum_size: Some(64),
+                    },
+                    description: Some("IANA zone of the 9 o'clock sub-dial, such as Asia/Tokyo".into()),
+                },
+                Entry {
+                    key: "day of week sub-dial".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("Small dial at 3 o'clock pointing at the day of the week".into()),
+                },
+                Entry {
+                    key: "date window".into(),
+                    value: Value::choice(DateWindow::Off),
+                    description: Some("Day of the month in a small box at 3 or 6 o'clock, like on a watch".into()),
+                },
+                Entry {
+                    key: "uptime gauge".into(),
+                    value: Value::choice(UptimeGauge::Off),
+                    description: Some("Arc near 12 o'clock filling up with the uptime of the computer or of the clock".into()),
+                },
+                Entry {
+                    key: "uptime gauge hours".into(),
+                    value: Value::Integer { value: 24 },
+                    description: Some("Hours after which the uptime gauge is full".into()),
                 },
                 Entry {
-                    key: "Display modes".into(),
+                    key: "date display".into(),
+                    value: Value::choice(DateDisplay::Off),
+                    description: Some("Date readout next to the dial".into()),
+                },
+                Entry {
+                    key: "date format".into(),
+                    value: Value::Text {
+                        value: "%a %d".into(),
+                        maximum_size: Some(32),
+                    },
+                    description: Some("strftime format of the date readout, such as %a %d".into()),
+                },
+                Entry {
+                    key: "labels".into(),
+                    value: Value::List { items: vec![] },
+                    description: Some("Text drawn on every frame, such as a name plate under the dial; Enter edits the list".into()),
+                },
+                Entry {
+                    key: "clock width".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Columns added to (or taken from) the horizontal radius of the dial".into()),
+                },
+                Entry {
+                    key: "clock height".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Rows added to (or taken from) the vertical radius of the dial".into()),
+                },
+                Entry {
+                    key: "clock position".into(),
+                    value: Value::choice(ClockPosition::Center),
+                    description: Some("Where the dial sits on the screen: centre, a side or a corner".into()),
+                },
+                Entry {
+                    key: "clock column offset".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Columns to the right of the position, moved by the arrow keys".into()),
+                },
+                Entry {
+                    key: "clock row offset".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Rows below the position, moved by the arrow keys".into()),
+                },
+                Entry {
+                    key: "layout per terminal size".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("Keep the size and place of the dial set with the keys or the mouse for each terminal size".into()),
+                },
+                Entry {
+                    key: "terminal sizes".into(),
+                    value: Value::List { items: vec![] },
+                    description: Some("Size and place of the dial adjusted at each terminal size, such as 200x50".into()),
+                },
+                Entry {
+                    key: "timezone".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(64),
+                    },
+                    description: Some("IANA zone the clock shows, such as Europe/Paris; empty for the local time".into()),
+                },
+                Entry {
+                    key: "zone caption".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("Name and offset of the zone under the dial".into()),
+                },
+                Entry {
+                    key: "local time offset minutes".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Minutes added to the time shown, e.g. to run a few minutes fast".into()),
+                },
+                Entry {
+                    key: "ntp servers".into(),
+                    value: Value::List {
+                        items: vec!["pool.ntp.org".into(), "time.cloudflare.com".into()],
+                    },
+                    description: Some("Servers the network time page compares the system clock with, as host or host:port".into()),
+                },
+                Entry {
+                    key: "continuous minutes".into(),
+                    value: Value::Boolean { value: true },
+                    description: Some("Move the minute hand a little every second instead of once a minute".into()),
+                },
+                Entry {
+                    key: "minute hand glide".into(),
+                    value: Value::Boolean { value: false },
+                    description: Some("Without continuous minutes, the minute hand glides to its new position".into()),
+                },
+                Entry {
+                    key: "cpu budget percent".into(),
+                    value: Value::Float { value: 0.0 },
+                    description: Some("Percent of a core the clock may use before it slows down, 0 for no limit".into()),
+                },
+                Entry {
+                    key: "character set".into(),
+                    value: Value::choice(CharacterSet::Auto),
+                    description: Some("Characters the screen may use: ASCII stand-ins for lines, blocks and braille on legacy terminals".into()),
+                },
+                Entry {
+                    key: "display mode".into(),
+                    value: Value::choice(DisplayMode::Analog),
+                    description: Some("What the screen shows: the dial, world clocks, big digits, a word clock or a radial chart".into()),
+                },
+                Entry {
+                    key: "startup page".into(),
+                    value: Value::choice(StartupPage::Last),
+                    description: Some("What opens at startup: the last face, the dial, world clocks, a focus session, the alarms or their history".into()),
+                },
+                Entry {
+                    key: "big digit weight".into(),
+                    value: Value::choice(DigitWeight::Bold),
+                    description: Some("Fill of the big digits: light ░, regular ▒ or bold █ blocks".into()),
+                },
+                Entry {
+                    key: "word clock language".into(),
+                    value: Value::choice(WordClockLanguage::English),
+                    description: Some("Language of the word clock".into()),
+                },
+                Entry {
+                    key: "world clocks".into(),
+                    value: Value::List {
+                        items: vec![
+                            "Europe/Paris".into(),
+                            "America/New_York".into(),
+                            "Asia/Tokyo".into(),
+                        ],
+                    },
+                    description: Some("Zones of the world clock mode, as names or objects with a label, size and place".into()),
+                },
+                Entry {
+                    key: "Alarms".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "alarms".into(),
+                    value: Value::List { items: vec![] },
+                    description: Some("Alarms, edited in the alarm list (key a)".into()),
+                },
+                Entry {
+                    key: "snooze minutes".into(),
+                    value: Value::Integer { value: 9 },
+                    description: Some("Minutes a snoozed alarm waits before ringing again".into()),
+                },
+                Entry {
+                    key: "alarm approach minutes".into(),
+                    value: Value::Integer { value: 0 },
+                    description: Some("Minutes before an alarm during which the border shifts to the approach color, 0 for none".into()),
+                },
+                Entry {
+                    key: "alarm approach color".into(),
+                    value: Value::Color {
+                        options: vec![
+                            "BLACK".into(),
+                            "RED".into(),
+                            "GREEN".into(),
+                            "YELLOW".into(),
+                            "BLUE".into(),
+                            "MAGENTA".into(),
+                            "CYAN".into(),
+                            "WHITE".into(),
+                        ],
+                        selected: 1, // RED
+                        custom: None,
+                    },
+                    description: Some("Color the border shifts to as the next alarm nears".into()),
+                },
+                Entry {
+                    key: "webhook url".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("Receives a JSON POST when an alarm rings or a pomodoro phase ends (network builds)".into()),
+                },
+                Entry {
+                    key: "control socket".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("Unix socket scripts send commands to, such as countdown banners; read at startup".into()),
+                },
+                Entry {
+                    key: "calendar file".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("ICS file whose events ring \"calendar lead minutes\" before they start".into()),
+                },
+                Entry {
+                    key: "calendar lead minutes".into(),
+                    value: Value::Integer { value: 5 },
+                    description: Some("Minutes before a calendar event its alarm rings".into()),
+                },
+                Entry {
+                    key: "chime".into(),
+                    value: Value::choice(ChimeMode::Off),
+                    description: Some("Bell at the hours or quarters".into()),
+                },
+                Entry {
+                    key: "Pomodoro".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "pomodoro work minutes".into(),
+                    value: Value::Integer { value: 25 },
+                    description: Some("Length of a pomodoro work phase".into()),
+                },
+                Entry {
+                    key: "pomodoro break minutes".into(),
+                    value: Value::Integer { value: 5 },
+                    description: Some("Length of a pomodoro break".into()),
+                },
+                Entry {
+                    key: "confirm quit during pomodoro".into(),
+                    value: Value::Boolean { value: true },
+                    description: Some("Ask before q ends a running or paused pomodoro session".into()),
+                },
+                Entry {
+                    key: "on focus start".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("Shell command run as a work phase starts, e.g. to turn on do-not-disturb".into()),
+                },
+                Entry {
+                    key: "on focus end".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("Shell command run as a work phase ends".into()),
+                },
+                Entry {
+                    key: "Keyboard shortcuts".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "change clock border".into(),
+                    value: Value::Shortcut { value: "c".into() },
+                    description: Some("Key cycling the clock border".into()),
+                },
+                Entry {
+                    key: "change number display".into(),
+                    value: Value::Shortcut { value: "n".into() },
+                    description: Some("Key cycling the hour markers".into()),
+                },
+                Entry {
+                    key: "change seconds display".into(),
+                    value: Value::Shortcut { value: "s".into() },
+                    description: Some("Key cycling the ways of drawing the second hand".into()),
+                },
+                Entry {
+                    key: "toggle continuous minutes".into(),
+                    value: Value::Shortcut { value: "m".into() },
+                    description: Some("Key switching continuous minutes on and off".into()),
+                },
+                Entry {
+                    key: "increase clock width".into(),
+                    value: Value::Shortcut { value: "+".into() },
+                    description: Some("Key making the dial wider".into()),
+                },
+                Entry {
+                    key: "decrease clock width".into(),
+                    value: Value::Shortcut { value: "-".into() },
+                    description: Some("Key making the dial narrower".into()),
+                },
+                Entry {
+                    key: "increase clock height".into(),
+                    value: Value::Shortcut { value: ">".into() },
+                    description: Some("Key making the dial taller".into()),
+                },
+                Entry {
+                    key: "decrease clock height".into(),
+                    value: Value::Shortcut { value: "<".into() },
+                    description: Some("Key making the dial shorter".into()),
+                },
+                Entry {
+                    key: "change countdown display".into(),
+                    value: Value::Shortcut { value: "o".into() },
+                    description: Some("Key cycling the countdown readout".into()),
+                },
+                Entry {
+                    key: "toggle world clocks".into(),
+                    value: Value::Shortcut { value: "w".into() },
+                    description: Some("Key switching between the dial and the world clocks".into()),
+                },
+                Entry {
+                    key: "change display mode".into(),
+                    value: Value::Shortcut { value: "d".into() },
+                    description: Some("Key cycling the display modes".into()),
+                },
+                Entry {
+                    key: "manage alarms".into(),
+                    value: Value::Shortcut { value: "a".into() },
+                    description: Some("Key opening the alarm list".into()),
+                },
+                Entry {
+                    key: "snooze alarm".into(),
+                    value: Value::Shortcut { value: "z".into() },
+                    description: Some("Key snoozing a ringing alarm".into()),
+                },
+                Entry {
+                    key: "start/pause pomodoro".into(),
+                    value: Value::Shortcut { value: "p".into() },
+                    description: Some("Key starting, pausing or resuming the pomodoro timer".into()),
+                },
+                Entry {
+                    key: "reset pomodoro".into(),
+                    value: Value::Shortcut { value: "r".into() },
+                    description: Some("Key resetting the pomodoro timer".into()),
+                },
+                Entry {
+                    key: "capture frame".into(),
+                    value: Value::Shortcut { value: "f".into() },
+                    description: Some("Key saving the screen to a text file".into()),
+                },
+                Entry {
+                    key: "show help".into(),
+                    value: Value::Shortcut { value: "h".into() },
+                    description: Some("Key showing the keyboard shortcuts".into()),
+                },
+                Entry {
+                    key: "next profile".into(),
+                    value: Value::Shortcut { value: "P".into() },
+                    description: Some("Key switching to the next settings profile".into()),
+                },
+                Entry {
+                    key: "compare network time".into(),
+                    value: Value::Shortcut { value: "t".into() },
+                    description: Some("Key opening the network time page".into()),
+                },
+                Entry {
+                    key: "quit".into(),
+                    value: Value::Shortcut { value: "q".into() },
+                    description: Some("Key quitting the clock".into()),
+                },
+                Entry {
+                    key: "session-only shortcuts".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(256),
+                    },
+                    description: Some("Shortcuts whose changes are not saved, such as change seconds display, separated by commas; move clock for the arrows".into()),
+                },
+                Entry {
+                    key: "Presets".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "presets".into(),
+                    value: Value::List {
+                        items: vec![
+                            serde_json::json!({
+                                "key": "1",
+                                "name": "minimal",
+                                "settings": {
+                                    "clock border": "hours",
+                                    "numbers": "no numbers",
+                                    "display seconds": "no display",
+                                    "digital display": "off",
+                                    "date display": "off",
+                                    "countdown display": "off",
+                                    "progress ring": "off",
+                                },
+                            }),
+                            serde_json::json!({
+                                "key": "2",
+                                "name": "full detail",
+                                "settings": {
+                                    "clock border": "full",
+                                    "numbers": "numbers",
+                                    "display seconds": "full continuous",
+                                    "digital display": "24 hours",
+                                    "date display": "ISO",
+                                    "countdown display": "next hour",
+                                    "zone caption": true,
+                                },
+                            }),
+                            serde_json::json!({
+                                "key": "3",
+                                "name": "presentation timer",
+                                "settings": {
+                                    "display seconds": "end of hand full continuous",
+                                    "digital display": "24 hours",
+                                    "countdown display": "next hour",
+                                    "progress ring": "hour",
+                                },
+                            }),
+                        ],
+                    },
+                    description: Some("Settings applied together with keys 1 to 9, each with a key, a name and settings".into()),
+                },
+                Entry {
+                    key: "Kiosk".into(),
+                    value: Value::Category,
+                    description: None,
+                },
+                Entry {
+                    key: "settings PIN".into(),
+                    value: Value::Text {
+                        value: "".into(),
+                        maximum_size: Some(16),
+                    },
+                    description: Some("Asked before the settings and the alarms open with --kiosk, empty for none".into()),
+                },
+            ],
+        }
+    }
+    pub fn load(filename: &str) -> Self {
+        Config::open(filename, true)
+    }
+
+    /// Read the file as [`Config::load`] does, upgraded in memory only: the
+    /// file is left as it is, for `tac --diagnose`.
+    pub fn inspect(filename: &str) -> Self {
+        Config::open(filename, false)
+    }
+
+    /// Read the file, written back once upgraded when `save_upgrade`.
+    fn open(filename: &str, save_upgrade: bool) -> Self {
+        if !Path::new(filename).exists() {
+            return Config::default(filename);
+        }
+        let parsed = fs::read_to_string(filename)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Config::parse(filename, &text));
+        match parsed {
+            Ok(mut cfg) => {
+                // The file may have been moved since it was saved
+                cfg.filename = filename.into();
+                cfg.adopt_choice_options();
+                cfg.modified = modified(filename);
+                if cfg.migrate() && save_upgrade {
+                    let _ = cfg.save();
+                }
+                cfg.saved = cfg.entries.clone();
+                cfg
+            }
+            Err(err) => {
+                let mut cfg = Config::default(filename);
+                cfg.repairs
+                    .push(format!("unreadable file, defaults used ({err})"));
+                // Only a new edit of the file is worth reloading
+                cfg.modified = modified(filename);
+                cfg
+            }
+        }
+    }
+
+    /// Whether the file was changed by another program (e.g. a text editor)
+    /// since it was last read or written here.
+    pub fn changed_on_disk(&self) -> bool {
+        modified(&self.filename).is_some_and(|time| Some(time) != self.modified)
+    }
+
+    /// Read the file again after [`Config::changed_on_disk`], keeping the
+    /// session overrides. On error (e.g. a file saved halfway) the current
+    /// settings stay until the next change.
+    pub fn reload(&mut self) -> Result<(), String> {
+        self.modified = modified(&self.filename);
+        let text = fs::read_to_string(&self.filename).map_err(|e| e.to_string())?;
+        let mut cfg = Config::parse(&self.filename, &text)?;
+        cfg.adopt_choice_options();
+        self.version = cfg.version;
+        self.profile = cfg.profile;
+        self.entries = cfg.entries;
+        self.profiles = cfg.profiles;
+        self.repairs = cfg.repairs;
+        if self.migrate() {
+            let _ = self.save();
+        }
+        self.saved = self.entries.clone();
+        Ok(())
+    }
+
+    /// Read the text of a config file, TOML when its name ends in `.toml`,
+    /// JSON otherwise.
+    fn parse(filename: &str, text: &str) -> Result<Config, String> {
+        if is_toml(filename) {
+            Config::from_toml(filename, text)
+        } else {
+            Config::from_json(filename, text)
+        }
+    }
+
+    /// Read a JSON config entry by entry: an entry that doesn't parse, or
+    /// whose kind isn't one its key has had, is left out (and put back with
+    /// its default value by [`Config::migrate`]) rather than failing the
+    /// whole file. Keys the clock doesn't know are kept.
+    fn from_json(filename: &str, text: &str) -> Result<Config, String> {
+        let mut file: serde_json::Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let Some(serde_json::Value::Array(items)) = file.get_mut("entries").map(mem::take) else {
+            return Err("no \"entries\" list".into());
+        };
+        let mut cfg = Config {
+            version: file["version"]
+                .as_u64()
+                .map_or(0, |v| v.min(u32::MAX.into()) as u32),
+            profile: file["profile"]
+                .as_str()
+                .map_or_else(default_profile, str::to_string),
+            entries: Vec::new(),
+            ..Config::default(filename)
+        };
+        cfg.entries = cfg.entries_from_json(items, "");
+        if let serde_json::Value::Array(profiles) = mem::take(&mut file["profiles"]) {
+            for mut profile in profiles {
+                let (Some(name), serde_json::Value::Array(items)) = (
+                    profile["name"].as_str().map(str::to_string),
+                    mem::take(&mut profile["entries"]),
+                ) else {
+                    cfg.repairs
+                        .push("a profile without name or entries was left out".into());
+                    continue;
+                };
+                let entries = cfg.entries_from_json(items, &format!("profile \"{name}\": "));
+                cfg.profiles.push(Profile { name, entries });
+            }
+        }
+        Ok(cfg)
+    }
+
+    /// The entries of a JSON list that parse and have the kind of their key,
+    /// or one it had in an older version, the others noted in the repairs,
+    /// after `context`.
+    fn entries_from_json(&mut self, items: Vec<serde_json::Value>, context: &str) -> Vec<Entry> {
+        let defaults = Config::default(&self.filename).entries;
+        let mut entries = Vec::new();
+        for item in items {
+            let key = item["key"].as_str().unwrap_or("?").to_string();
+            match serde_json::from_value::<Entry>(item) {
+                // A file written before the kind changed is converted by the migrations
+                Ok(entry)
+                    if defaults.iter().any(|d| {
+                        d.key == entry.key
+                            && mem::discriminant(&d.value) != mem::discriminant(&entry.value)
+                            && (self.version >= CONFIG_VERSION
+                                || entry.value.clone().converted(&d.value).is_none())
+                    }) =>
+                {
+                    self.repairs
+                        .push(format!("{context}\"{key}\" had the wrong kind, reset"));
+                }
+                Ok(entry) => entries.push(entry),
+                Err(_) => self
+                    .repairs
+                    .push(format!("{context}\"{key}\" was invalid, reset")),
+            }
+        }
+        entries
+    }
+
+    /// Read a TOML config: one `circle_color = "green"` line per entry,
+    /// underscores standing for the spaces of the keys. Entries left out keep
+    /// their defaults; unknown keys and values that don't fit are reported and
+    /// skipped.
+    fn from_toml(filename: &str, text: &str) -> Result<Config, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        Ok(Config::from_toml_table(filename, table))
+    }
+
+    /// The config of a parsed TOML file, or of one of its `[profiles.name]`
+    /// tables. Only the entries set in the table are kept, for
+    /// [`Config::migrate`] to upgrade them before the defaults fill in the
+    /// others.
+    fn from_toml_table(filename: &str, table: toml::Table) -> Config {
+        let mut cfg = Config::default(filename);
+        let mut read = Vec::new();
+        for (name, value) in table {
+            match (name.as_str(), value) {
+                ("version", value) => {
+                    cfg.version = value
+                        .as_integer()
+                        .map_or(0, |v| v.clamp(0, u32::MAX.into()) as u32);
+                    continue;
+                }
+                ("profile", toml::Value::String(profile)) => {
+                    cfg.profile = profile;
+                    continue;
+                }
+                ("profiles", toml::Value::Table(profiles)) => {
+                    for (name, value) in profiles {
+                        let toml::Value::Table(table) = value else {
+                            continue;
+                        };
+                        let profile = Config::from_toml_table(filename, table);
+                        let context = format!("profile \"{name}\": ");
+                        cfg.repairs
+                            .extend(profile.repairs.iter().map(|r| format!("{context}{r}")));
+                        cfg.profiles.push(Profile {
+                            name,
+                            entries: profile.entries,
+                        });
+                    }
+                    continue;
+                }
+                (_, value) => read.extend(cfg.set_toml_value(&name, value)),
+            }
+        }
+        cfg.entries.retain(|e| read.contains(&e.key));
+        cfg
+    }
+
+    /// Set the entry named `name` (underscores for spaces) from a TOML value.
+    /// Returns its key once set.
+    fn set_toml_value(&mut self, name: &str, value: toml::Value) -> Option<String> {
+        let key = name.replace('_', " ");
+        let parsed = match value {
+            toml::Value::String(text) => self.parsed_entry(&key, &text),
+            toml::Value::Integer(n) => self.parsed_entry(&key, &n.to_string()),
+            toml::Value::Float(x) => self.parsed_entry(&key, &x.to_string()),
+            toml::Value::Boolean(b) => self.parsed_entry(&key, &b.to_string()),
+            toml::Value::Array(items) => self.list_entry(&key, items),
+            _ => None,
+        };
+        let Some(parsed) = parsed else {
+            let reason = if self.entries.iter().any(|e| e.key == key) {
+                "invalid value for"
+            } else {
+                "unknown key"
+            };
+            self.repairs.push(format!("{reason} \"{name}\" ignored"));
+            return None;
+        };
+        let entry = self.entries.iter_mut().find(|e| e.key == key)?;
+        *entry = parsed;
+        Some(key)
+    }
+
+    /// Copy of the list entry `key` holding `items`.
+    fn list_entry(&self, key: &str, items: Vec<toml::Value>) -> Option<Entry> {
+        let mut entry = self.entries.iter().find(|e| e.key == key)?.clone();
+        let Value::List { items: list } = &mut entry.value else {
+            return None;
+        };
+        *list = items
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        Some(entry)
+    }
+
+    /// The config as TOML, a comment heading each category and a
+    /// `[profiles.name]` table for each other profile.
+    fn to_toml(&self) -> String {
+        let mut text = format!(
+            "version = {}\nprofile = {}\n",
+            self.version,
+            toml::Value::String(self.profile.clone())
+        );
+        push_toml_entries(&mut text, &self.entries);
+        for profile in &self.profiles {
+            text.push_str(&format!("\n[profiles.{}]\n", toml_name(&profile.name)));
+            push_toml_entries(&mut text, &profile.entries);
+        }
+        text
+    }
+
+    /// Name of the profile in use.
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Switch to the next profile, the one in use going last, and save if
+    /// changes are saved as they are made.
+    /// Returns the name of the new profile, `None` when there is only one.
+    pub fn next_profile(&mut self) -> Option<String> {
+        if self.profiles.is_empty() {
+            return None;
+        }
+        let next = self.profiles.remove(0);
+        self.profiles.push(Profile {
+            name: mem::replace(&mut self.profile, next.name),
+            entries: mem::replace(&mut self.entries, next.entries),
+        });
+        if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+            let _ = self.save();
+        }
+        Some(self.profile.clone())
+    }
+
+    /// Start a profile named `name` as a copy of the one in use, and switch
+    /// to it. The name must be new.
+    pub fn duplicate_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("empty profile name".into());
+        }
+        if name == self.profile || self.profiles.iter().any(|p| p.name == name) {
+            return Err(format!("profile \"{name}\" already exists"));
+        }
+        self.profiles.push(Profile {
+            name: mem::replace(&mut self.profile, name.into()),
+            entries: self.entries.clone(),
+        });
+        self.save()
+    }
+
+    /// Put the entry at `selected` back to its default value. Fails for an
+    /// entry the clock has no default for, such as a misspelled key.
+    fn reset_entry(&mut self, selected: usize) -> Result<(), String> {
+        let Some(entry) = self.entries.get(selected) else {
+            return Err("no entry selected".into());
+        };
+        let Some(default) = default_entry(&self.filename, &entry.key) else {
+            return Err(format!("no default for \"{}\"", entry.key));
+        };
+        self.overrides.retain(|e| e.key != default.key);
+        self.entries[selected] = default;
+        if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Put every entry of the profile in use back to its default value.
+    fn reset_all(&mut self) -> Result<(), String> {
+        self.entries = Config::default(&self.filename).entries;
+        self.overrides.clear();
+        if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Give the entries of every profile the descriptions, and the choices and
+    /// colors the options, defined in the code, keeping the selected option by
+    /// name. An option the code doesn't know selects the default.
+    fn adopt_choice_options(&mut self) {
+        let defaults = Config::default(&self.filename).entries;
+        let profiles = self.profiles.iter_mut().flat_map(|p| p.entries.iter_mut());
+        for entry in self.entries.iter_mut().chain(profiles) {
+            let Some(default) = defaults.iter().find(|d| d.key == entry.key) else {
+                continue;
+            };
+            entry.description = default.description.clone();
+            let (Value::Choice { options, selected }
+            | Value::Color {
+                options, selected, ..
+            }) = &mut entry.value
+            else {
+                continue;
+            };
+            let (Value::Choice {
+                options: known,
+                selected: default,
+            }
+            | Value::Color {
+                options: known,
+                selected: default,
+                ..
+            }) = &default.value
+            else {
+                continue;
+            };
+            let name = options.get(*selected);
+            *selected = name
+                .and_then(|name| known.iter().position(|o| o == name))
+                .unwrap_or(*default);
+            *options = known.clone();
+        }
+    }
+
+    /// Upgrade a file written by an older version: run the steps of
+    /// [`MIGRATIONS`] it hasn't seen on every profile, then add the entries
+    /// each lacks with their default values. Returns true if the file should
+    /// be written again: TOML files leave settings out on purpose, they are
+    /// only rewritten for an upgrade.
+    fn migrate(&mut self) -> bool {
+        let from = self.version as usize;
+        let mut changed = from < MIGRATIONS.len();
+        // The steps work on `entries`: each profile takes its turn there
+        for i in 0..=self.profiles.len() {
+            if i > 0 {
+                mem::swap(&mut self.entries, &mut self.profiles[i - 1].entries);
+            }
+            for step in MIGRATIONS.iter().skip(from) {
+                step(self);
+            }
+            changed |= self.insert_missing() && !is_toml(&self.filename);
+            if i > 0 {
+                mem::swap(&mut self.entries, &mut self.profiles[i - 1].entries);
+            }
+        }
+        self.version = CONFIG_VERSION;
+        changed
+    }
+
+    /// Migration to version 1, for files written before the config had a
+    /// version: keys that were renamed or replaced.
+    fn rename_legacy_entries(&mut self) {
+        // "local time offset" was a whole number of hours
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.key == "local time offset")
+        {
+            if let Value::Integer { value } = entry.value {
+                entry.key = "local time offset minutes".into();
+                entry.value = Value::Integer { value: value * 60 };
+            }
+        }
+        // "world clock mode" became an option of "display mode"
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.key == "world clock mode")
+        {
+            if let Value::Boolean { value } = entry.value {
+                entry.key = DisplayMode::KEY.into();
+                entry.value = Value::choice(if value {
+                    DisplayMode::WorldClocks
+                } else {
+                    DisplayMode::Analog
+                });
+            }
+        }
+        // The single "wake-up ..." alarm became an item of the "alarms" list
+        if self.entries.iter().any(|e| e.key == "wake-up alarm") {
+            let mut alarm = serde_json::json!({
+                "time": self.get_string("wake-up time").unwrap_or_else(|| "07:00".into()),
+                "enabled": self.get_bool("wake-up alarm"),
+                "ramp": self.get_int("wake-up ramp"),
+            });
+            let action = self
+                .get_string("wake-up action")
+                .unwrap_or_else(|| "none".into());
+            alarm["action"] = action.into();
+            alarm["argument"] = self
+                .get_string("wake-up action argument")
+                .unwrap_or_default()
+                .into();
+            let is_old = |e: &Entry| e.key.starts_with("wake-up ") || e.key == "Wake-up alarm";
+            let pos = self.entries.iter().take_while(|e| !is_old(e)).count();
+            self.entries.retain(|e| !is_old(e));
+            let alarm_entries = vec![
+                Entry {
+                    key: "Alarms".into(),
                     value: Value::Category,
+                    description: None,
                 },
                 Entry {
-                    key: "clock border".into(),
-                    value: Value::Choice {
-                        options: vec![
-                            "full".into(),
-                            "dot and hours".into(),
-                            "hours".into(),
-                            "no border".into(),
-                        ],
-                        selected: 1,
-                    },
+                    key: "alarms".into(),
+                    value: Value::List { items: vec![alarm] },
+                    description: None,
                 },
                 Entry {
-                    key: "display seconds".into(),
-                    value: Value::Choice {
-                        options: vec![
-                            "no display".into(),
-                            "full each second".into(),
-                            "full continuous".into(),
-                            "end of hand each second".into(),
-                            "end of hand full continuous".into(),
-                        ],
-                        selected: 1,
-                    },
+                    key: "snooze minutes".into(),
+                    value: Value::Integer { value: 9 },
+                    description: None,
                 },
-                Entry {
-                    key: "numbers".into(),
-                    value: Value::Choice {
-                        options: vec!["no numbers".into(), "stars".into(), "numbers".into()],
-                        selected: 0,
+            ];
+            self.entries.splice(pos..pos, alarm_entries);
+        }
+    }
+
+    /// Migration to version 2: the shortcuts were texts of one character and
+    /// some integer settings became decimals, given the kind they have now. An entry that can't be converted is
+    /// reset by [`Config::insert_missing`].
+    fn convert_changed_kinds(&mut self) {
+        let defaults = Config::default(&self.filename).entries;
+        self.entries.retain_mut(|entry| {
+            let Some(default) = defaults.iter().find(|d| d.key == entry.key) else {
+                return true;
+            };
+            match mem::replace(&mut entry.value, Value::Category).converted(&default.value) {
+                Some(value) => {
+                    entry.value = value;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    /// Migration to version 3: the hand lengths were whole percentages. Files
+    /// of version 2 still hold integers, older ones got decimals from
+    /// [`Config::convert_changed_kinds`].
+    fn hand_lengths_as_fractions(&mut self) {
+        const KEYS: [&str; 5] = [
+            "hour hand length",
+            "minute hand length",
+            "second hand length",
+            "hand tail length",
+            "second hand tip start",
+        ];
+        for entry in self
+            .entries
+            .iter_mut()
+            .filter(|e| KEYS.contains(&e.key.as_str()))
+        {
+            let percent = match entry.value {
+                Value::Integer { value } => value as f64,
+                Value::Float { value } => value,
+                _ => continue,
+            };
+            entry.value = Value::Float {
+                value: percent / 100.0,
+            };
+        }
+    }
+
+    /// Add the default entries missing from the file, each after the entry
+    /// preceding it in the defaults, so new settings show up in their
+    /// category. Returns whether any was added.
+    fn insert_missing(&mut self) -> bool {
+        let defaults = Config::default(&self.filename).entries;
+        let mut changed = false;
+        for (i, default) in defaults.iter().enumerate() {
+            if self.entries.iter().any(|e| e.key == default.key) {
+                continue;
+            }
+            let pos = defaults[..i]
+                .iter()
+                .rev()
+                .find_map(|d| self.entries.iter().position(|e| e.key == d.key))
+                .map_or(0, |p| p + 1);
+            self.entries.insert(pos, default.clone());
+            changed = true;
+        }
+        changed
+    }
+
+    /// Problems a hand-edited file may have: entries repaired while loading,
+    /// keys appearing twice (only the first one is used), keys that no part
+    /// of the clock reads, usually typos, shortcuts naming no key and
+    /// session-only shortcuts naming no shortcut.
+    pub fn problems(&self) -> Vec<String> {
+        let known = Config::default(&self.filename).entries;
+        let mut problems = self.repairs.clone();
+        for (i, entry) in self.entries.iter().enumerate() {
+            if self.entries[..i].iter().any(|e| e.key == entry.key) {
+                problems.push(format!("duplicate key \"{}\"", entry.key));
+            } else if !known.iter().any(|e| e.key == entry.key) {
+                problems.push(format!("unknown key \"{}\"", entry.key));
+            }
+            if let Value::Shortcut { value } = &entry.value {
+                if let Some(name) = value
+                    .split_whitespace()
+                    .find(|name| keymap::parse_key(name).is_none())
+                {
+                    problems.push(format!("\"{}\": no key is named \"{name}\"", entry.key));
+                }
+            }
+        }
+        for name in keymap::unknown_session_only(self) {
+            problems.push(format!(
+                "\"session-only shortcuts\": no shortcut is named \"{name}\""
+            ));
+        }
+        problems
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let text = if is_toml(&self.filename) {
+            self.to_toml()
+        } else {
+            serde_json::to_string_pretty(self).map_err(|e| e.to_string())?
+        };
+        fs::write(&self.filename, text).map_err(|e| e.to_string())?;
+        self.modified = modified(&self.filename);
+        self.saved = self.entries.clone();
+        Ok(())
+    }
+
+    /// Whether an entry differs from the file, which only happens while
+    /// changes aren't saved as they are made.
+    fn is_modified(&self) -> bool {
+        self.entries != self.saved
+    }
+
+    /// Category header of the entry at `index`: the closest one above it.
+    fn category_of(&self, index: usize) -> Option<usize> {
+        (0..=index.min(self.entries.len().saturating_sub(1)))
+            .rev()
+            .find(|&i| matches!(self.entries[i].value, Value::Category))
+    }
+
+    /// Write the category of the entry at `selected` (e.g. every color) to a
+    /// snippet file next to the config file, to share it. Returns its path.
+    pub fn export_category(&self, selected: usize) -> Result<PathBuf, String> {
+        let start = self
+            .category_of(selected)
+            .ok_or("the entry is not in a category")?;
+        let entries = self.entries[start + 1..]
+            .iter()
+            .take_while(|e| !matches!(e.value, Value::Category))
+            .cloned()
+            .collect();
+        let category = self.entries[start].key.clone();
+        let path = self.directory().join(snippet_file_name(&category));
+        let snippet = Snippet { category, entries };
+        let json = serde_json::to_string_pretty(&snippet).map_err(|e| e.to_string())?;
+        fs::write(&path, json).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+
+    /// Copy the values of a snippet file into the config. Entries with an
+    /// unknown key or another kind, and options this version doesn't have,
+    /// are skipped. Returns how many entries were imported.
+    pub fn import_snippet(&mut self, path: &Path) -> Result<usize, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let snippet: Snippet = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        let mut count = 0;
+        for imported in snippet.entries {
+            let Some(entry) = self.entries.iter_mut().find(|e| e.key == imported.key) else {
+                continue;
+            };
+            let value = match (&entry.value, imported.value) {
+                // Options are matched by name, the lists may differ between versions
+                (
+                    Value::Choice { options, .. },
+                    Value::Choice {
+                        options: theirs,
+                        selected,
                     },
-                },
-                Entry {
-                    key: "clock width".into(),
-                    value: Value::Integer { value: 5 },
-                },
-                Entry {
-                    key: "local time offset".into(),
-                    value: Value::Integer { value: 0 },
-                },
-                Entry {
-                    key: "continuous minutes".into(),
-                    value: Value::Boolean { value: true },
-                },
-                Entry {
-                    key: "Keyboard shortcuts".into(),
-                    value: Value::Category,
-                },
-                Entry {
-                    key: "change clock border".into(),
-                    value: Value::Text {
-                        value: "c".into(),
-                        maximum_size: Some(1),
+                ) => match theirs
+                    .get(selected)
+                    .and_then(|o| options.iter().position(|x| x == o))
+                {
+                    Some(selected) => Value::Choice {
+                        options: options.clone(),
+                        selected,
                     },
+                    None => continue,
                 },
-                Entry {
-                    key: "change number display".into(),
-                    value: Value::Text {
-                        value: "n".into(),
-                        maximum_size: Some(1),
+                (
+                    Value::Color { options, .. },
+                    Value::Color {
+                        options: theirs,
+                        selected,
+                        custom,
                     },
-                },
-                Entry {
-                    key: "change seconds display".into(),
-                    value: Value::Text {
-                        value: "s".into(),
-                        maximum_size: Some(1),
+                ) => match theirs
+                    .get(selected)
+                    .and_then(|o| options.iter().position(|x| x == o))
+                {
+                    Some(selected) => Value::Color {
+                        options: options.clone(),
+                        selected,
+                        custom,
                     },
+                    None => continue,
                 },
-                Entry {
-                    key: "quit".into(),
-                    value: Value::Text {
-                        value: "q".into(),
-                        maximum_size: Some(1),
-                    },
+                (Value::Category, _) | (Value::Choice { .. }, _) | (Value::Color { .. }, _) => {
+                    continue
+                }
+                // A snippet from an older version may hold a kind since changed
+                (current, value) => match value.converted(current) {
+                    Some(value) => value,
+                    None => continue,
                 },
-            ],
+            };
+            entry.value = value;
+            self.overrides.retain(|e| e.key != imported.key);
+            count += 1;
         }
+        Ok(count)
     }
-    pub fn load(filename: &str) -> Self {
-        if Path::new(filename).exists() {
-            match fs::read_to_string(filename) {
-                Ok(text) => match serde_json::from_str(&text) {
-                    Ok(cfg) => cfg,
-                    Err(err) => {
-                        eprintln!("Failed to parse JSON (using defaults): {err}");
-                        Config::default(filename)
+
+    /// Directory holding the config file.
+    pub fn directory(&self) -> PathBuf {
+        Path::new(&self.filename)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    /// Previous entry that is not a category, or `selected` at the top of the list.
+    fn entry_above(&self, selected: usize) -> usize {
+        (0..selected.min(self.entries.len()))
+            .rev()
+            .find(|&i| !matches!(self.entries[i].value, Value::Category))
+            .unwrap_or(selected)
+    }
+
+    /// Next entry that is not a category, or `selected` at the bottom of the list.
+    fn entry_below(&self, selected: usize) -> usize {
+        (selected + 1..self.entries.len())
+            .find(|&i| !matches!(self.entries[i].value, Value::Category))
+            .unwrap_or(selected)
+    }
+
+    /// Space/Enter on an entry: choices, colors and booleans take their next
+    /// value, the other kinds are edited.
+    fn activate_entry(&mut self, selected: usize) {
+        if let Some(entry) = self.entries.get_mut(selected) {
+            self.overrides.retain(|e| e.key != entry.key);
+            entry.value.clear_custom_color();
+            match &mut entry.value {
+                Value::Choice {
+                    ref options,
+                    ref mut selected,
+                }
+                | Value::Color {
+                    ref options,
+                    ref mut selected,
+                    ..
+                } => {
+                    if options.is_empty() {
+                        return;
                     }
-                },
-                Err(err) => {
-                    eprintln!("Failed to read config (using defaults): {err}");
-                    Config::default(filename)
+                    let len = options.len();
+                    *selected = (*selected + 1) % len;
+                }
+                Value::Boolean { ref mut value } => {
+                    *value = !*value;
+                }
+                // Text, Integer, Float, Shortcut, Category -> use regular edit_entry behavior
+                _ => {
+                    edit_entry(entry);
                 }
             }
-        } else {
-            Config::default(filename)
+        }
+        if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+            let _ = self.save();
         }
     }
 
-    pub fn save(&mut self) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
-        fs::write(&self.filename, json).map_err(|e| e.to_string())
+    /// Pick the color of a color entry in the color grid.
+    fn pick_entry_color(&mut self, selected: usize) {
+        // The grid pairs come after the ones of the entries
+        let first_pair = CUSTOM_PAIRS + self.entries.len() as i16 + PREVIEW_PAIRS;
+        if let Some(entry) = self.entries.get_mut(selected) {
+            self.overrides.retain(|e| e.key != entry.key);
+            pick_color(&entry.key, &mut entry.value, first_pair);
+        }
+        if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+            let _ = self.save();
+        }
     }
 
     /// Draw the whole screen: header, list (scrolling, selected centered), and bottom status line.
@@ -309,7 +1985,13 @@ impl Config {
         getmaxyx(stdscr(), &mut max_y, &mut max_x);
 
         // Header at the top
-        let header = format!("Key/Value editor  |  file: {}", path);
+        let mut header = format!(
+            "Key/Value editor  |  file: {}  |  profile: {}",
+            path, self.profile
+        );
+        if self.is_modified() {
+            header.push_str("  [modified]");
+        }
         mvprintw(0, 0, &header);
         clrtoeol();
 
@@ -318,20 +2000,19 @@ impl Config {
             mvprintw(
                 1,
                 0,
-                "↑/↓: move   Enter/e: edit text/int / next choice   ←/→: change choice/color/bool q: quit",
+                &charset::text("↑/↓: move   Enter/e: edit text/int/key / next choice / color grid   ←/→: change choice/color/bool   d/D: reset entry/all to default   x/i: export/import category   p/n: next/new profile   q: quit"),
             );
         } else {
             mvprintw(
                 1,
                 0,
-                "↑/↓: move   Enter/e: edit text/int / next choice   ←/→: change choice/color/bool   s: save   q: quit",
+                &charset::text("↑/↓: move   Enter/e: edit text/int/key / next choice / color grid   ←/→: change choice/color/bool   d/D: reset entry/all to default   x/i: export/import category   p/n: next/new profile   s: save   q: quit"),
             );
         }
         clrtoeol();
 
         // Area reserved for the list
-        let list_top = 3;
-        let list_bottom = max_y - 3; // keep a few lines at the bottom for status/edit
+        let (list_top, list_bottom) = list_area(max_y);
 
         // Pre-render entry lines to compute max width for horizontal centering
         let mut rendered_lines = Vec::with_capacity(self.entries.len());
@@ -352,18 +2033,28 @@ impl Config {
                     // For width computation, just consider the key as the "line"
                     entry.key.clone()
                 }
-                Value::Color { options, selected } => {
-                    let current = options.get(*selected).map(|s| s.as_str()).unwrap_or("<?>");
-                    let value_str = format!("[{}]", current);
+                Value::Color { .. } => {
+                    let value_str = format!("[{}]", entry.value.color_name());
                     format!("{:<20} = {}", entry.key, value_str)
                 }
                 Value::Integer { value } => {
                     format!("{:<20} = {}", entry.key, value)
                 }
+                Value::Float { value } => {
+                    format!("{:<20} = {}", entry.key, value)
+                }
                 Value::Boolean { value } => {
                     let value_str = format!("[{}]", value);
                     format!("{:<20} = {}", entry.key, value_str)
                 }
+                Value::Shortcut { value } => {
+                    let value_str = format!("<{}>", value);
+                    format!("{:<20} = {}", entry.key, value_str)
+                }
+                Value::List { items } => {
+                    let value_str = format!("[{} items]", items.len());
+                    format!("{:<20} = {}", entry.key, value_str)
+                }
             };
 
             let width = line.chars().count();
@@ -373,21 +2064,19 @@ impl Config {
             rendered_lines.push(line);
         }
 
-        // Horizontally center
-        let start_col: i32 = if (max_x as usize) > max_width {
+        // Horizontally center, left of the clock preview when there is room for it
+        let mut start_col: i32 = if (max_x as usize) > max_width {
             ((max_x as usize - max_width) / 2) as i32
         } else {
             0
         };
+        let preview = max_x >= max_width as i32 + PREVIEW_COLS + 2 && max_y >= PREVIEW_ROWS + 7;
+        if preview {
+            start_col = start_col.min(max_x - PREVIEW_COLS - 2 - max_width as i32);
+        }
 
         // Vertically: the selected entry is always on the "center row"
-        let mut center_row = max_y / 2;
-        if center_row < list_top {
-            center_row = list_top;
-        }
-        if center_row > list_bottom {
-            center_row = list_bottom;
-        }
+        let center_row = list_center_row(max_y);
 
         let has_color = has_colors();
 
@@ -451,12 +2140,9 @@ impl Config {
                     mv(row, start_col + bar_width as i32);
                     clrtoeol();
                 }
-                Value::Color {
-                    options,
-                    selected: color_idx,
-                } => {
+                Value::Color { custom, .. } => {
                     // Color entry: key field + " = [" + colored name + "]"
-                    let current = options.get(*color_idx).map(|s| s.as_str()).unwrap_or("<?>");
+                    let current = entry.value.color_name();
 
                     let prefix = format!("{:<20} = [", entry.key);
                     let suffix = "]";
@@ -477,7 +2163,17 @@ impl Config {
 
                     // Colored name, no reverse, just color pair
                     if has_color {
-                        if let Some(pair) = color_name_to_pair(current) {
+                        let custom_pair = custom
+                            .as_deref()
+                            .and_then(ColorSpec::parse)
+                            .map(|spec| {
+                                // One pair per entry, after the fixed ones
+                                let pair = CUSTOM_PAIRS + i as i16;
+                                palette::set_pair(pair, spec.resolve(), -1);
+                                pair
+                            })
+                            .filter(|&pair| (pair as i32) < COLOR_PAIRS());
+                        if let Some(pair) = custom_pair.or_else(|| color_name_to_pair(current)) {
                             attron(COLOR_PAIR(pair));
                             mvprintw(row, col, current);
                             attroff(COLOR_PAIR(pair));
@@ -504,13 +2200,13 @@ impl Config {
                     clrtoeol();
                 }
                 _ => {
-                    // Text, Choice, Integer, Boolean (already rendered into `line`)
+                    // Text, Choice, Integer, Float, Boolean (already rendered into `line`)
                     if i == selected && !matches!(entry.value, Value::Category) {
                         attron(A_REVERSE());
-                        mvprintw(row, start_col, line);
+                        mvprintw(row, start_col, &charset::text(line));
                         attroff(A_REVERSE());
                     } else {
-                        mvprintw(row, start_col, line);
+                        mvprintw(row, start_col, &charset::text(line));
                     }
 
                     mv(row, start_col + line.chars().count() as i32);
@@ -519,38 +2215,167 @@ impl Config {
             }
         }
 
+        // Over the ends of the list rows, which were cleared to the end of the line
+        if preview {
+            self.draw_preview(max_x - PREVIEW_COLS, 3);
+        }
+
+        // What the selected entry is for, on its own line under the list
+        let description = self
+            .entries
+            .get(selected)
+            .and_then(|e| e.description.as_deref());
+        let width = (max_x - 1).max(0) as usize;
+        mv(max_y - 3, 0);
+        clrtoeol();
+        if let Some(description) = description {
+            let text: String = charset::text(description).chars().take(width).collect();
+            attron(A_DIM());
+            mvprintw(max_y - 3, 0, &text);
+            attroff(A_DIM());
+        }
+
+        // What the highlighted option of a choice does, on the status line
+        if let Some(Entry {
+            key,
+            value: Value::Choice { options, selected },
+            ..
+        }) = self.entries.get(selected)
+        {
+            if let Some(name) = options.get(*selected) {
+                if let Some(help) = choices::help(key, name) {
+                    show_status(&format!("{name}: {help}"));
+                }
+            }
+        }
+
         // Status/help line at the very bottom; content is updated by show_status()
 
         if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
             mvprintw(max_y - 1, 0, "Press escape to quit");
         } else {
-            mvprintw(max_y - 1, 0, "Press escape to quit, s to save…");
+            mvprintw(
+                max_y - 1,
+                0,
+                &charset::text("Press escape to quit, s to save…"),
+            );
         }
         clrtoeol();
 
         refresh();
     }
 
+    /// Miniature clock drawn from the values being edited, top left corner at
+    /// (left,top), so changes show before leaving the editor.
+    fn draw_preview(&self, left: i32, top: i32) {
+        let now = time::effective_now(self, &SessionClock);
+        let frame = crate::draw_frame(self, &now, PREVIEW_COLS, PREVIEW_ROWS);
+        let first = CUSTOM_PAIRS + self.entries.len() as i16;
+        let colored = has_colors() && ((first + PREVIEW_PAIRS) as i32) <= COLOR_PAIRS();
+        if colored {
+            let background = self.get_color("background color");
+            let text = match background.is_light() {
+                true => COLOR_BLACK as i32,
+                false => COLOR_WHITE as i32,
+            };
+            palette::set_pair(first, text, background.resolve());
+            for (pair, key) in crate::CLOCK_COLORS {
+                palette::set_pair(
+                    first + pair,
+                    self.get_color(key).resolve(),
+                    background.resolve(),
+                );
+            }
+        }
+        render::draw_at(&frame, left, top, |pair| match colored {
+            true if pair < PREVIEW_PAIRS => first + pair,
+            _ => 0,
+        });
+    }
+
+    /// Entry of a key, session overrides first.
+    fn find(&self, key: &str) -> Option<&Entry> {
+        self.overrides
+            .iter()
+            .chain(self.entries.iter())
+            .find(|e| e.key == key)
+    }
+
+    /// Override the value of an entry for this session only, without writing
+    /// to the file: `value` is parsed like the text typed in the editor (option
+    /// name for choices/colors, "true"/"false", number or text).
+    ///
+    /// Setting the key later (e.g. from a keyboard shortcut) drops the override.
+    /// Returns `None` if the key is missing or the value doesn't fit its kind.
+    pub fn override_value(&mut self, key: &str, value: &str) -> Option<bool> {
+        let entry = self.parsed_entry(key, value)?;
+        self.overrides.retain(|e| e.key != key);
+        self.overrides.push(entry);
+        Some(true)
+    }
+
+    /// Copy of the entry `key` holding `value`, parsed for its kind.
+    fn parsed_entry(&self, key: &str, value: &str) -> Option<Entry> {
+        let mut entry = self.entries.iter().find(|e| e.key == key)?.clone();
+        match &mut entry.value {
+            Value::Choice { options, selected } => {
+                *selected = options.iter().position(|o| o.eq_ignore_ascii_case(value))?;
+            }
+            Value::Color {
+                options,
+                selected,
+                custom,
+            } => match options.iter().position(|o| o.eq_ignore_ascii_case(value)) {
+                Some(index) => {
+                    *selected = index;
+                    *custom = None;
+                }
+                None => *custom = Some(ColorSpec::parse(value).map(|_| value.to_string())?),
+            },
+            Value::Integer { value: v } => *v = value.parse().ok()?,
+            Value::Float { value: v } => *v = value.parse().ok().filter(|v: &f64| v.is_finite())?,
+            Value::Boolean { value: v } => *v = value.parse().ok()?,
+            Value::Text { value: v, .. } | Value::Shortcut { value: v } => *v = value.into(),
+            Value::Category | Value::List { .. } => return None,
+        }
+        Some(entry)
+    }
+
+    /// Drop the session override of one key, if any.
+    pub fn drop_override(&mut self, key: &str) {
+        self.overrides.retain(|e| e.key != key);
+    }
+
     /// Get the string value associated with a key, if any.
     ///
-    /// - For `text`: returns the text (`value`).
-    /// - For `choice`/`color`: returns the currently selected option string.
-    /// - For `integer`/`boolean`: returns a string representation ("42", "true", "false").
-    /// - For `category` or missing key: returns `None`.
+    /// - For `text`/`shortcut`: returns the text (`value`).
+    /// - For `choice`: returns the currently selected option string.
+    /// - For `color`: returns the custom color if any, else the selected option string.
+    /// - For `integer`/`float`/`boolean`: returns a string representation ("42", "0.5", "true").
+    /// - For `category`/`list` or missing key: returns `None`.
     #[allow(dead_code)]
     pub fn get_string(&self, key: &str) -> Option<String> {
-        self.entries
-            .iter()
-            .find(|e| e.key == key)
+        self.find(key).and_then(|entry| match &entry.value {
+            Value::Text { value, .. } | Value::Shortcut { value } => Some(value.clone()),
+            Value::Choice { options, selected } => options.get(*selected).cloned(),
+            Value::Color { .. } => Some(entry.value.color_name().to_string()),
+            Value::Integer { value } => Some(value.to_string()),
+            Value::Float { value } => Some(value.to_string()),
+            Value::Boolean { value } => Some(value.to_string()),
+            Value::Category | Value::List { .. } => None,
+        })
+    }
+
+    /// Get the color of a color entry: the custom palette index or RGB value
+    /// if there is a valid one, else the selected basic color. Missing keys
+    /// and other kinds give black.
+    pub fn get_color(&self, key: &str) -> ColorSpec {
+        self.find(key)
             .and_then(|entry| match &entry.value {
-                Value::Text { value, .. } => Some(value.clone()),
-                Value::Choice { options, selected } | Value::Color { options, selected } => {
-                    options.get(*selected).cloned()
-                }
-                Value::Integer { value } => Some(value.to_string()),
-                Value::Boolean { value } => Some(value.to_string()),
-                Value::Category => None,
+                Value::Color { .. } => ColorSpec::parse(entry.value.color_name()),
+                _ => None,
             })
+            .unwrap_or(ColorSpec::Basic(0))
     }
 
     /// Get the selected option index associated with a key, if it is a choice/color.
@@ -559,9 +2384,7 @@ impl Config {
     /// - For `text`/`integer`/`boolean`/`category` or missing key: returns `None`.
     #[allow(dead_code)]
     pub fn get_option(&self, key: &str) -> usize {
-        self.entries
-            .iter()
-            .find(|e| e.key == key)
+        self.find(key)
             .map(|entry| match &entry.value {
                 Value::Choice { selected, .. } | Value::Color { selected, .. } => *selected,
                 _ => 0,
@@ -569,15 +2392,37 @@ impl Config {
             .unwrap_or(0)
     }
 
+    /// Get the selected option of the choice `C`; the first option when the
+    /// entry is missing.
+    pub fn get<C: Choice>(&self) -> C {
+        C::ALL
+            .get(self.get_option(C::KEY))
+            .copied()
+            .unwrap_or(C::ALL[0])
+    }
+
+    /// Select `value` in the choice `C` and save, like [`Config::set_option`].
+    pub fn set<C: Choice>(&mut self, value: C) -> Option<i64> {
+        self.set_option(C::KEY, value.index() as i64)
+    }
+
+    /// Get the option names of a choice/color, or an empty list for other kinds or a missing key.
+    pub fn get_choices(&self, key: &str) -> Vec<String> {
+        self.find(key)
+            .map(|entry| match &entry.value {
+                Value::Choice { options, .. } | Value::Color { options, .. } => options.clone(),
+                _ => Vec::new(),
+            })
+            .unwrap_or_default()
+    }
+
     /// Get the integer value associated with a key, if it is an integer.
     ///
     /// - For `integer`: returns `Some(value)`.
     /// - For other kinds or missing key: returns `None`.
     #[allow(dead_code)]
     pub fn get_int(&self, key: &str) -> i64 {
-        self.entries
-            .iter()
-            .find(|e| e.key == key)
+        self.find(key)
             .map(|entry| match &entry.value {
                 Value::Integer { value } => *value,
                 _ => 0,
@@ -585,15 +2430,28 @@ impl Config {
             .unwrap_or(0)
     }
 
+    /// Get the decimal value associated with a key.
+    ///
+    /// - For `float`: returns the value; for `integer`, the integer as a decimal.
+    /// - For other kinds or missing key: returns 0.
+    #[allow(dead_code)]
+    pub fn get_float(&self, key: &str) -> f64 {
+        self.find(key)
+            .map(|entry| match &entry.value {
+                Value::Float { value } => *value,
+                Value::Integer { value } => *value as f64,
+                _ => 0.0,
+            })
+            .unwrap_or(0.0)
+    }
+
     /// Get the boolean value associated with a key, if it is a boolean.
     ///
     /// - For `boolean`: returns `Some(value)`.
     /// - For other kinds or missing key: returns `None`.
     #[allow(dead_code)]
     pub fn get_bool(&self, key: &str) -> bool {
-        self.entries
-            .iter()
-            .find(|e| e.key == key)
+        self.find(key)
             .map(|entry| match &entry.value {
                 Value::Boolean { value } => *value,
                 _ => false,
@@ -601,6 +2459,36 @@ impl Config {
             .unwrap_or(false)
     }
 
+    /// Get the items of a list, or an empty list for other kinds or a missing key.
+    pub fn get_list(&self, key: &str) -> Vec<serde_json::Value> {
+        self.find(key)
+            .map(|entry| match &entry.value {
+                Value::List { items } => items.clone(),
+                _ => Vec::new(),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replace the items of a list, adding the entry at the end if it is missing.
+    ///
+    /// - On success: updates the JSON file and returns `Some(true)`.
+    /// - If the key holds another kind or save fails: returns `None`.
+    pub fn set_list(&mut self, key: &str, items: Vec<serde_json::Value>) -> Option<bool> {
+        self.overrides.retain(|e| e.key != key);
+        match self.entries.iter_mut().find(|e| e.key == key) {
+            Some(entry) => match &mut entry.value {
+                Value::List { items: ref mut v } => *v = items,
+                _ => return None,
+            },
+            None => self.entries.push(Entry {
+                key: key.into(),
+                value: Value::List { items },
+                description: default_entry(&self.filename, key).and_then(|e| e.description),
+            }),
+        }
+        self.save().ok().map(|_| true)
+    }
+
     /// Set the selected option index for a choice or color.
     ///
     /// - On success: updates the JSON file and returns `Some(new_index)`.
@@ -611,8 +2499,10 @@ impl Config {
             return None;
         }
         let idx = value as usize;
+        self.overrides.retain(|e| e.key != key);
 
         if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
+            entry.value.clear_custom_color();
             match &mut entry.value {
                 Value::Choice {
                     ref options,
@@ -621,6 +2511,7 @@ impl Config {
                 | Value::Color {
                     ref options,
                     ref mut selected,
+                    ..
                 } => {
                     if idx >= options.len() {
                         return None;
@@ -646,6 +2537,7 @@ impl Config {
     /// - If key not found, kind mismatch, or save fails: returns `None`.
     #[allow(dead_code)]
     pub fn set_int(&mut self, key: &str, value: i64) {
+        self.overrides.retain(|e| e.key != key);
         if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
             match &mut entry.value {
                 Value::Integer { value: ref mut v } => {
@@ -664,12 +2556,28 @@ impl Config {
         };
     }
 
+    /// Set the decimal value for a float field.
+    ///
+    /// - On success: updates the JSON file and returns `Some(new_value)`.
+    /// - If key not found, kind mismatch, value not finite, or save fails: returns `None`.
+    #[allow(dead_code)]
+    pub fn set_float(&mut self, key: &str, value: f64) -> Option<f64> {
+        self.overrides.retain(|e| e.key != key);
+        let entry = self.entries.iter_mut().find(|e| e.key == key)?;
+        match &mut entry.value {
+            Value::Float { value: ref mut v } if value.is_finite() => *v = value,
+            _ => return None,
+        }
+        self.save().ok().map(|_| value)
+    }
+
     /// Set the boolean value for a boolean field.
     ///
     /// - On success: updates the JSON file and returns `Some(new_value)`.
     /// - If key not found, kind mismatch, or save fails: returns `None`.
     #[allow(dead_code)]
     pub fn set_bool(&mut self, key: &str, value: bool) -> Option<bool> {
+        self.overrides.retain(|e| e.key != key);
         if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
             match &mut entry.value {
                 Value::Boolean { value: ref mut v } => {
@@ -694,6 +2602,7 @@ impl Config {
     /// - If key not found, kind mismatch, value too long for maximum_size, or save fails: returns `None`.
     #[allow(dead_code)]
     pub fn set_string(&mut self, key: &str, value: &str) -> Option<bool> {
+        self.overrides.retain(|e| e.key != key);
         if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
             match &mut entry.value {
                 Value::Text {
@@ -701,7 +2610,7 @@ impl Config {
                     maximum_size,
                 } => {
                     if let Some(max) = maximum_size {
-                        if value.len() > *max {
+                        if value.chars().count() > *max {
                             return None;
                         }
                     }
@@ -738,18 +2647,23 @@ impl Config {
         if has_colors() {
             start_color();
             use_default_colors();
-            init_pair(CATEGORY_PAIR, COLOR_GREEN, -1);
+            palette::set_pair(CATEGORY_PAIR, COLOR_GREEN as i32, -1);
 
             // Color name pairs
-            init_pair(PAIR_BLACK, COLOR_BLACK, COLOR_WHITE); // black text on white background
-            init_pair(PAIR_RED, COLOR_RED, -1);
-            init_pair(PAIR_GREEN, COLOR_GREEN, -1);
-            init_pair(PAIR_YELLOW, COLOR_YELLOW, -1);
-            init_pair(PAIR_BLUE, COLOR_BLUE, -1);
-            init_pair(PAIR_MAGENTA, COLOR_MAGENTA, -1);
-            init_pair(PAIR_CYAN, COLOR_CYAN, -1);
-            init_pair(PAIR_WHITE, COLOR_WHITE, -1);
+            palette::set_pair(PAIR_BLACK, COLOR_BLACK as i32, COLOR_WHITE as i32); // black text on white background
+            palette::set_pair(PAIR_RED, COLOR_RED as i32, -1);
+            palette::set_pair(PAIR_GREEN, COLOR_GREEN as i32, -1);
+            palette::set_pair(PAIR_YELLOW, COLOR_YELLOW as i32, -1);
+            palette::set_pair(PAIR_BLUE, COLOR_BLUE as i32, -1);
+            palette::set_pair(PAIR_MAGENTA, COLOR_MAGENTA as i32, -1);
+            palette::set_pair(PAIR_CYAN, COLOR_CYAN as i32, -1);
+            palette::set_pair(PAIR_WHITE, COLOR_WHITE as i32, -1);
         }
+        // The clock's background color would clash with the terminal-default
+        // backgrounds used here
+        bkgd(' ' as chtype);
+
+        mouse::enable();
 
         // Initial selection: first non-category entry, if any
         let mut selected: usize = 0;
@@ -768,75 +2682,70 @@ impl Config {
             display_screen = true;
             let ch = getch();
 
-            match ch {
-                KEY_UP => {
-                    if self.entries.is_empty() {
-                        continue;
-                    }
-                    // Move up, skipping categories
-                    let mut new = selected;
-                    while new > 0 {
-                        new -= 1;
-                        if !matches!(self.entries[new].value, Value::Category) {
-                            selected = new;
-                            break;
+            match ch {
+                KEY_UP => selected = self.entry_above(selected),
+                KEY_DOWN => selected = self.entry_below(selected),
+                KEY_MOUSE => match mouse::event() {
+                    Some(MouseEvent::ScrollUp) => selected = self.entry_above(selected),
+                    Some(MouseEvent::ScrollDown) => selected = self.entry_below(selected),
+                    // A click selects an entry, a click on the selected entry changes it
+                    Some(MouseEvent::Click(_, y)) => {
+                        let mut max_y = 0;
+                        let mut max_x = 0;
+                        getmaxyx(stdscr(), &mut max_y, &mut max_x);
+                        let (list_top, list_bottom) = list_area(max_y);
+                        let index = selected as i64 + (y - list_center_row(max_y)) as i64;
+                        let clicked = usize::try_from(index)
+                            .ok()
+                            .filter(|_| (list_top..=list_bottom).contains(&y))
+                            .filter(|&i| {
+                                self.entries
+                                    .get(i)
+                                    .is_some_and(|e| !matches!(e.value, Value::Category))
+                            });
+                        match clicked {
+                            Some(i) if i == selected => self.activate_entry(selected),
+                            Some(i) => selected = i,
+                            None => {}
                         }
                     }
+                    None => display_screen = false,
+                },
+                // Enter on the labels opens their list
+                10 | 13 | 101
+                    if self
+                        .entries
+                        .get(selected)
+                        .is_some_and(|e| e.key == "labels") =>
+                {
+                    labels::manage(self);
                 }
-                KEY_DOWN => {
-                    if self.entries.is_empty() {
-                        continue;
-                    }
-                    // Move down, skipping categories
-                    let mut new = selected;
-                    while new + 1 < self.entries.len() {
-                        new += 1;
-                        if !matches!(self.entries[new].value, Value::Category) {
-                            selected = new;
-                            break;
-                        }
-                    }
+                // Enter on a color opens the color grid
+                10 | 13
+                    if matches!(
+                        self.entries.get(selected).map(|e| &e.value),
+                        Some(Value::Color { .. })
+                    ) =>
+                {
+                    self.pick_entry_color(selected)
                 }
                 // Space, Enter: for choice/color/bool, change value; for text/int/category, edit_entry
-                32 | 10 | 13 => {
-                    if let Some(entry) = self.entries.get_mut(selected) {
-                        match &mut entry.value {
-                            Value::Choice {
-                                ref options,
-                                ref mut selected,
-                            }
-                            | Value::Color {
-                                ref options,
-                                ref mut selected,
-                            } => {
-                                if options.is_empty() {
-                                    continue;
-                                }
-                                let len = options.len();
-                                *selected = (*selected + 1) % len;
-                            }
-                            Value::Boolean { ref mut value } => {
-                                *value = !*value;
-                            }
-                            // Text, Integer, Category -> use regular edit_entry behavior
-                            _ => {
-                                edit_entry(entry);
-                            }
-                        }
-                    }
-                    if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
-                        let _ = self.save();
-                    }
-                }
+                32 | 10 | 13 => self.activate_entry(selected),
                 // 'e' -> edit_entry (text/int editor or status messages)
                 101 => {
                     if let Some(entry) = self.entries.get_mut(selected) {
+                        self.overrides.retain(|e| e.key != entry.key);
                         edit_entry(entry);
                     }
+                    if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+                        let _ = self.save();
+                    }
                 }
                 // Left / Right to change a choice, color, or boolean
                 KEY_LEFT | KEY_RIGHT => {
                     if let Some(entry) = self.entries.get_mut(selected) {
+                        self.overrides.retain(|e| e.key != entry.key);
+                        entry.value.clear_custom_color();
                         match &mut entry.value {
                             Value::Choice {
                                 ref options,
@@ -845,6 +2754,7 @@ impl Config {
                             | Value::Color {
                                 ref options,
                                 ref mut selected,
+                                ..
                             } => {
                                 if options.is_empty() {
                                     continue;
@@ -867,16 +2777,131 @@ impl Config {
                             _ => {}
                         }
                     }
+                    if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+                        let _ = self.save();
+                    }
                 }
                 // 's' -> save
-                115 => match self.save() {
-                    Ok(()) => show_status("Saved configuration."),
-                    Err(err) => show_status(&format!("Save failed: {err}")),
+                115 => {
+                    match self.save() {
+                        Ok(()) => show_status("Saved configuration."),
+                        Err(err) => show_status(&format!("Save failed: {err}")),
+                    }
+                    // Keep the status line until the next key
+                    display_screen = false;
+                }
+                // 'x' -> export the category of the selected entry
+                120 => {
+                    match self.export_category(selected) {
+                        Ok(path) => show_status(&format!("Exported to {}", path.display())),
+                        Err(err) => show_status(&format!("Export failed: {err}")),
+                    }
+                    display_screen = false;
+                }
+                // 'i' -> import a snippet
+                105 => {
+                    let mut name = self
+                        .category_of(selected)
+                        .map(|c| snippet_file_name(&self.entries[c].key))
+                        .unwrap_or_default();
+                    edit_text_value("snippet file to import", &mut name, None);
+                    if !name.is_empty() {
+                        let path = self.directory().join(&name);
+                        match self.import_snippet(&path) {
+                            Ok(count) => {
+                                if SAVE_WHEN_CHANGE.load(Ordering::SeqCst) {
+                                    let _ = self.save();
+                                }
+                                self.draw_screen(selected, &self.filename);
+                                show_status(&format!("Imported {count} settings."));
+                            }
+                            Err(err) => show_status(&format!("Import failed: {err}")),
+                        }
+                        display_screen = false;
+                    }
+                }
+                // 'p' -> switch to the next profile
+                112 => {
+                    match self.next_profile() {
+                        Some(name) => {
+                            self.draw_screen(selected, &self.filename);
+                            show_status(&format!("Profile \"{name}\"."));
+                        }
+                        None => show_status("No other profile, press d to make one."),
+                    }
+                    display_screen = false;
+                }
+                // 'd' -> reset the selected entry to its default
+                100 => {
+                    let key = self.entries.get(selected).map(|e| e.key.clone());
+                    match self.reset_entry(selected) {
+                        Ok(()) => {
+                            self.draw_screen(selected, &self.filename);
+                            show_status(&format!(
+                                "\"{}\" reset to its default.",
+                                key.unwrap_or_default()
+                            ));
+                        }
+                        Err(err) => show_status(&format!("Reset failed: {err}")),
+                    }
+                    display_screen = false;
+                }
+                // 'D' -> reset the whole profile, once confirmed
+                68 => {
+                    let question = format!(
+                        "Reset every setting of profile \"{}\" to its default? (y/n)",
+                        self.profile
+                    );
+                    if matches!(capture_key(&question), Some('y' | 'Y')) {
+                        match self.reset_all() {
+                            Ok(()) => {
+                                selected = selected.min(self.entries.len().saturating_sub(1));
+                                self.draw_screen(selected, &self.filename);
+                                show_status("Every setting reset to its default.");
+                            }
+                            Err(err) => show_status(&format!("Reset failed: {err}")),
+                        }
+                        display_screen = false;
+                    }
+                }
+                // 'n' -> new profile, a copy of the current one
+                110 => {
+                    let mut name = String::new();
+                    edit_text_value("name of the new profile", &mut name, Some(32));
+                    if !name.trim().is_empty() {
+                        match self.duplicate_profile(&name) {
+                            Ok(()) => {
+                                self.draw_screen(selected, &self.filename);
+                                show_status(&format!("Profile \"{}\" created.", name.trim()));
+                            }
+                            Err(err) => show_status(&format!("Duplicate failed: {err}")),
+                        }
+                        display_screen = false;
+                    }
+                }
+                // Escape -> quit, asking first about unsaved changes
+                27 if self.is_modified() => match capture_key("Save changes? (y/n/cancel)") {
+                    Some('y' | 'Y') => match self.save() {
+                        Ok(()) => break,
+                        Err(err) => {
+                            show_status(&format!("Save failed: {err}"));
+                            display_screen = false;
+                        }
+                    },
+                    Some('n' | 'N') => {
+                        self.entries = self.saved.clone();
+                        break;
+                    }
+                    _ => {}
                 },
-                // 'q' -> quit
                 27 => {
                     break;
                 }
+                // Redraw at the new size, the selected entry centred again
+                KEY_RESIZE => {}
+                // A signal interrupted the wait: quit, or come back from Ctrl-Z
+                _ if signals::quit_requested() => break,
+                _ if signals::suspend_if_asked() => {}
                 _ => {
                     display_screen = false;
                     napms(10);
@@ -889,6 +2914,17 @@ impl Config {
     }
 }
 
+/// First and last rows of the entry list, keeping a few lines at the bottom for status/edit.
+fn list_area(max_y: i32) -> (i32, i32) {
+    (3, max_y - 4)
+}
+
+/// Row of the selected entry: the list scrolls so that it stays in the middle.
+fn list_center_row(max_y: i32) -> i32 {
+    let (list_top, list_bottom) = list_area(max_y);
+    (max_y / 2).max(list_top).min(list_bottom)
+}
+
 fn color_name_to_pair(name: &str) -> Option<i16> {
     let upper = name.to_ascii_uppercase();
     match upper.as_str() {
@@ -910,7 +2946,7 @@ fn show_status(msg: &str) {
     getmaxyx(stdscr(), &mut max_y, &mut max_x);
 
     let width = if max_x > 1 { (max_x - 1) as usize } else { 1 };
-    let text: String = msg.chars().take(width).collect();
+    let text: String = charset::text(msg).chars().take(width).collect();
 
     mv(max_y - 2, 0);
     clrtoeol();
@@ -919,7 +2955,7 @@ fn show_status(msg: &str) {
 }
 
 /// Edit a text value in-place at the bottom of the screen, enforcing an optional maximum size.
-fn edit_text_value(key: &str, value: &mut String, maximum_size: Option<usize>) {
+pub fn edit_text_value(key: &str, value: &mut String, maximum_size: Option<usize>) {
     let mut max_y = 0;
     let mut max_x = 0;
     getmaxyx(stdscr(), &mut max_y, &mut max_x);
@@ -953,43 +2989,37 @@ fn edit_text_value(key: &str, value: &mut String, maximum_size: Option<usize>) {
         clrtoeol();
 
         let max_len = if max_x > 1 { (max_x - 1) as usize } else { 1 };
-        let visible = if input.len() > max_len {
-            let start = input.len().saturating_sub(max_len);
-            &input[start..]
-        } else {
-            &input
-        };
+        // The end of the input, as many characters as fit
+        let count = input.chars().count();
+        let visible: String = input.chars().skip(count.saturating_sub(max_len)).collect();
 
-        mvprintw(max_y - 1, 0, visible);
+        mvaddstr(max_y - 1, 0, &visible);
         refresh();
 
-        let ch = getch();
-
-        match ch {
+        // Wide characters: text may hold any character, e.g. "●" as a hand label
+        match get_wch() {
             // Enter
-            10 | 13 => {
+            Some(WchResult::Char(10 | 13)) => {
                 *value = input.clone();
                 break;
             }
             // Esc
-            27 => {
+            Some(WchResult::Char(27)) => {
                 // Cancel, keep old value
                 break;
             }
             // Backspace (handle a couple of common codes)
-            KEY_BACKSPACE | 127 | 8 => {
+            Some(WchResult::KeyCode(KEY_BACKSPACE)) | Some(WchResult::Char(127 | 8)) => {
                 input.pop();
             }
-            _ => {
-                // Printable ASCII (for simplicity)
-                if (32..=126).contains(&ch) {
-                    if let Some(c) = std::char::from_u32(ch as u32) {
-                        if input.len() < limit {
-                            input.push(c);
-                        }
+            Some(WchResult::Char(code)) => {
+                if let Some(c) = char::from_u32(code).filter(|c| !c.is_control()) {
+                    if input.chars().count() < limit {
+                        input.push(c);
                     }
                 }
             }
+            _ => {}
         }
     }
 
@@ -1006,7 +3036,7 @@ fn edit_text_value(key: &str, value: &mut String, maximum_size: Option<usize>) {
 }
 
 /// Edit an integer value similarly to text, but only allowing digits and an optional leading '-'.
-fn edit_integer_value(key: &str, value: &mut i64) {
+pub fn edit_integer_value(key: &str, value: &mut i64) {
     let mut max_y = 0;
     let mut max_x = 0;
     getmaxyx(stdscr(), &mut max_y, &mut max_x);
@@ -1071,7 +3101,92 @@ fn edit_integer_value(key: &str, value: &mut i64) {
                     if input.len() >= limit {
                         continue;
                     }
-                    if c.is_ascii_digit() || ( c == '-' && input.is_empty()) {
+                    if c.is_ascii_digit() || (c == '-' && input.is_empty()) {
+                        input.push(c);
+                    }
+                }
+            }
+        }
+    }
+
+    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+
+    // Clear edit area
+    mv(max_y - 3, 0);
+    clrtoeol();
+    mv(max_y - 2, 0);
+    clrtoeol();
+    mv(max_y - 1, 0);
+    clrtoeol();
+    refresh();
+}
+
+/// Edit a decimal value in-place at the bottom of the screen, like
+/// [`edit_integer_value`] but accepting one decimal point.
+pub fn edit_float_value(key: &str, value: &mut f64) {
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+
+    let prompt = format!("Editing '{}': Enter=save, Esc=cancel (decimal)", key);
+    mv(max_y - 3, 0);
+    clrtoeol();
+    mvprintw(max_y - 3, 0, &prompt);
+
+    mv(max_y - 2, 0);
+    clrtoeol();
+    mvprintw(max_y - 2, 0, "Current value (editable decimal):");
+
+    mv(max_y - 1, 0);
+    clrtoeol();
+
+    let mut input = value.to_string();
+    curs_set(CURSOR_VISIBILITY::CURSOR_VISIBLE);
+
+    let limit = 32usize;
+
+    loop {
+        mv(max_y - 1, 0);
+        clrtoeol();
+
+        let max_len = if max_x > 1 { (max_x - 1) as usize } else { 1 };
+        let start = input.len().saturating_sub(max_len);
+        mvprintw(max_y - 1, 0, &input[start..]);
+        refresh();
+
+        let ch = getch();
+
+        match ch {
+            // Enter
+            10 | 13 => {
+                if let Ok(v) = input.parse::<f64>() {
+                    if v.is_finite() {
+                        *value = v;
+                    }
+                } else if input.chars().all(|c| c == '-' || c == '.') {
+                    // Treat empty, lone '-' or '.' as 0
+                    *value = 0.0;
+                }
+                break;
+            }
+            // Esc
+            27 => {
+                // Cancel, keep old value
+                break;
+            }
+            // Backspace (handle a couple of common codes)
+            KEY_BACKSPACE | 127 | 8 => {
+                input.pop();
+            }
+            _ => {
+                if let Some(c) = std::char::from_u32(ch as u32) {
+                    if input.len() >= limit {
+                        continue;
+                    }
+                    if c.is_ascii_digit()
+                        || (c == '-' && input.is_empty())
+                        || (c == '.' && !input.contains('.'))
+                    {
                         input.push(c);
                     }
                 }
@@ -1091,6 +3206,319 @@ fn edit_integer_value(key: &str, value: &mut i64) {
     refresh();
 }
 
+/// Wait for a printable key press; Escape cancels.
+fn capture_key(prompt: &str) -> Option<char> {
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+
+    mv(max_y - 2, 0);
+    clrtoeol();
+    mvprintw(max_y - 2, 0, prompt);
+    refresh();
+
+    let key = loop {
+        let ch = getch();
+        match ch {
+            // Esc
+            27 => break None,
+            // Enter
+            10 | 13 => break Some('\n'),
+            // No input yet (the clock runs the screen in nodelay mode)
+            ERR => {
+                napms(10);
+            }
+            // Printable ASCII only, like the text editor
+            _ if (33..=126).contains(&ch) => {
+                if let Some(c) = std::char::from_u32(ch as u32) {
+                    break Some(c);
+                }
+            }
+            _ => {}
+        }
+    };
+
+    mv(max_y - 2, 0);
+    clrtoeol();
+    refresh();
+    key
+}
+
+/// Wait for a key to bind on the status line, after `prompt`: its code,
+/// `Some(10)` for Enter, `None` for Escape. Keys that can't be shortcuts are
+/// ignored.
+fn capture_key_code(prompt: &str) -> Option<i32> {
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+
+    mv(max_y - 2, 0);
+    clrtoeol();
+    mvprintw(max_y - 2, 0, prompt);
+    refresh();
+
+    let key = loop {
+        let ch = getch();
+        match ch {
+            27 => break None,
+            10 | 13 | KEY_ENTER => break Some(10),
+            // No input yet (the clock runs the screen in nodelay mode)
+            ERR => {
+                napms(10);
+            }
+            _ if keymap::key_name(ch).is_some() => break Some(ch),
+            _ => {}
+        }
+    };
+
+    mv(max_y - 2, 0);
+    clrtoeol();
+    refresh();
+    key
+}
+
+/// Ask for the new shortcut: one key, then optionally a second key making a
+/// chord ("t n"). The keys are stored by their canonical names, such as
+/// "F5" or "C-b". Escape cancels.
+fn capture_shortcut(key: &str, value: &mut String) {
+    let prompt = format!(
+        "Press the new key for '{}', F-keys and Ctrl combinations included (Esc=cancel)",
+        key
+    );
+    let Some(first) = capture_key_code(&prompt).and_then(keymap::key_name) else {
+        return;
+    };
+    let prompt = format!(
+        "'{}': press a second key for a chord, or Enter for just '{}'",
+        key, first
+    );
+    match capture_key_code(&prompt) {
+        Some(10) => *value = first,
+        Some(second) => {
+            if let Some(second) = keymap::key_name(second) {
+                *value = format!("{first} {second}");
+            }
+        }
+        None => {}
+    }
+}
+
+/// Hex colors offered after the palette in the color picker.
+const HEX_PRESETS: [&str; 8] = [
+    "#ff8800", "#ffd700", "#7fff00", "#40e0d0", "#87ceeb", "#9370db", "#ff69b4", "#202040",
+];
+
+/// Cells per row of the color picker grid.
+const PICKER_COLUMNS: usize = 16;
+
+/// Colors of the picker grid, as a color entry writes them: the basic names,
+/// the rest of the terminal's palette, then the hex presets.
+fn picker_colors() -> Vec<String> {
+    let palette = COLORS().clamp(8, 256);
+    palette::BASIC_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .chain((8..palette).map(|i| i.to_string()))
+        .chain(HEX_PRESETS.iter().map(|hex| hex.to_string()))
+        .collect()
+}
+
+/// Part of the sample dial of the color picker colored by an entry.
+#[derive(PartialEq)]
+enum PreviewElement {
+    Ring,
+    Digits,
+    Hours,
+    Minutes,
+    Seconds,
+    Background,
+    /// Entries of other elements: the whole sample
+    Everything,
+}
+
+impl PreviewElement {
+    fn of(key: &str) -> Self {
+        match key {
+            "circle color" => PreviewElement::Ring,
+            "digits color" => PreviewElement::Digits,
+            "hours color" => PreviewElement::Hours,
+            "minutes color" => PreviewElement::Minutes,
+            "seconds color" => PreviewElement::Seconds,
+            "background color" => PreviewElement::Background,
+            _ => PreviewElement::Everything,
+        }
+    }
+}
+
+/// Sample dial centred at (cx,cy), the element colored by `key` drawn with `pair`.
+fn draw_color_preview(key: &str, pair: Option<i16>, cx: i32, cy: i32) {
+    let element = PreviewElement::of(key);
+    let (a, b) = (8, 4);
+    let draw = |part: PreviewElement, points: Vec<(i32, i32)>, text: &str| {
+        let colored = part == element || element == PreviewElement::Everything;
+        if let Some(pair) = pair.filter(|_| colored) {
+            attron(COLOR_PAIR(pair));
+        }
+        for (i, (x, y)) in points.into_iter().enumerate() {
+            let ch = text.chars().nth(i % text.chars().count()).unwrap_or(' ');
+            mvaddstr(y, x, ch.encode_utf8(&mut [0; 4]));
+        }
+        if let Some(pair) = pair.filter(|_| colored) {
+            attroff(COLOR_PAIR(pair));
+        }
+    };
+    if element == PreviewElement::Background {
+        if let Some(pair) = pair {
+            attron(COLOR_PAIR(pair) | A_REVERSE());
+            for y in cy - b..=cy + b {
+                mvaddstr(y, cx - a - 1, &" ".repeat(2 * a as usize + 3));
+            }
+            attroff(COLOR_PAIR(pair) | A_REVERSE());
+        }
+    }
+    draw(PreviewElement::Ring, ellipse_points(cx, cy, a, b), "*");
+    draw(
+        PreviewElement::Digits,
+        vec![(cx - 1, cy - b + 1), (cx, cy - b + 1)],
+        "12",
+    );
+    draw(
+        PreviewElement::Seconds,
+        line_points(cx, cy, cx - 5, cy + 2),
+        ".",
+    );
+    draw(
+        PreviewElement::Minutes,
+        line_points(cx, cy, cx, cy - 2),
+        "m",
+    );
+    draw(
+        PreviewElement::Hours,
+        line_points(cx, cy, cx + 4, cy - 1),
+        "h",
+    );
+}
+
+/// Popup grid of the colors the terminal can show, 8, 16 or 256 of them: the
+/// arrows move, scrolling the grid on short terminals, Enter picks the color,
+/// Escape keeps the one the entry had. A sample dial shows the hovered color
+/// on the element the entry colors. Pairs from `first_pair` on are used for
+/// the grid.
+fn pick_color(key: &str, value: &mut Value, first_pair: i16) {
+    let Value::Color {
+        options,
+        selected,
+        custom,
+    } = value
+    else {
+        return;
+    };
+    let colors = picker_colors();
+    let current = custom
+        .clone()
+        .or_else(|| options.get(*selected).cloned())
+        .unwrap_or_default();
+    let mut index = colors
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case(&current))
+        .unwrap_or(0);
+    let pairs: Vec<Option<i16>> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            let pair = first_pair.checked_add(i as i16)?;
+            if !has_colors() || pair as i32 >= COLOR_PAIRS() {
+                return None;
+            }
+            palette::set_pair(pair, ColorSpec::parse(color)?.resolve(), -1);
+            Some(pair)
+        })
+        .collect();
+
+    let rows = colors.len().div_ceil(PICKER_COLUMNS) as i32;
+    // First grid row shown, when the terminal is too short for all of them
+    let mut scroll = 0;
+    loop {
+        let mut max_y = 0;
+        let mut max_x = 0;
+        getmaxyx(stdscr(), &mut max_y, &mut max_x);
+        let shown = rows.min(max_y - 4).max(1);
+        let row = (index / PICKER_COLUMNS) as i32;
+        scroll = scroll.max(row - shown + 1).min(row);
+        // Grid of 3-column cells, the sample dial on its right
+        let width = 3 * PICKER_COLUMNS as i32 + 22;
+        let height = shown.max(9) + 4;
+        let (top, left) = ((max_y - height).max(0) / 2, (max_x - width).max(0) / 2);
+        for y in top..top + height {
+            mvaddstr(y, left, &" ".repeat(width as usize));
+        }
+        attron(A_REVERSE());
+        mvaddstr(top, left, &format!(" {key}: {} ", colors[index]));
+        attroff(A_REVERSE());
+        for (i, color) in colors.iter().enumerate() {
+            let (row, column) = ((i / PICKER_COLUMNS) as i32, (i % PICKER_COLUMNS) as i32);
+            if row < scroll || row >= scroll + shown {
+                continue;
+            }
+            let (y, x) = (top + 2 + row - scroll, left + 1 + 3 * column);
+            let cell = if i == index { "[ ]" } else { "   " };
+            match pairs[i] {
+                Some(pair) => {
+                    attron(COLOR_PAIR(pair) | A_REVERSE());
+                    mvaddstr(y, x, cell);
+                    attroff(COLOR_PAIR(pair) | A_REVERSE());
+                }
+                // No pair left: the name stands for the color
+                None => {
+                    let name: String = color.chars().take(3).collect();
+                    mvaddstr(y, x, &name);
+                }
+            }
+        }
+        // Rows scrolled out of sight
+        let edge = left + 1 + 3 * PICKER_COLUMNS as i32;
+        if scroll > 0 {
+            mvaddstr(top + 2, edge, &charset::text("▲"));
+        }
+        if scroll + shown < rows {
+            mvaddstr(top + 1 + shown, edge, &charset::text("▼"));
+        }
+        draw_color_preview(key, pairs[index], left + width - 11, top + 2 + 4);
+        mvaddstr(
+            top + height - 1,
+            left,
+            " arrows/PgUp/PgDn: move   Enter: pick   Esc: cancel",
+        );
+        refresh();
+
+        let last = colors.len() - 1;
+        match getch() {
+            KEY_LEFT => index = index.saturating_sub(1),
+            KEY_RIGHT => index = (index + 1).min(last),
+            KEY_UP if index >= PICKER_COLUMNS => index -= PICKER_COLUMNS,
+            KEY_DOWN => index = (index + PICKER_COLUMNS).min(last),
+            KEY_PPAGE => index = index.saturating_sub(shown as usize * PICKER_COLUMNS),
+            KEY_NPAGE => index = (index + shown as usize * PICKER_COLUMNS).min(last),
+            10 | 13 => {
+                match options.iter().position(|o| o == &colors[index]) {
+                    Some(option) => {
+                        *selected = option;
+                        *custom = None;
+                    }
+                    None => *custom = Some(colors[index].clone()),
+                }
+                return;
+            }
+            27 => return,
+            // No input yet (the clock runs the screen in nodelay mode)
+            ERR => {
+                napms(10);
+            }
+            _ => {}
+        }
+    }
+}
+
 fn edit_entry(entry: &mut Entry) {
     let key = entry.key.clone(); // avoid borrow issues
 
@@ -1104,16 +3532,41 @@ fn edit_entry(entry: &mut Entry) {
         Value::Integer { ref mut value } => {
             edit_integer_value(&key, value);
         }
-        // Choice and Color are edited directly with ←/→ / Enter
+        Value::Float { ref mut value } => {
+            edit_float_value(&key, value);
+        }
+        Value::Shortcut { ref mut value } => {
+            capture_shortcut(&key, value);
+        }
+        // Choices are edited directly with ←/→ / Enter
         Value::Choice { .. } => {
             show_status("Use ←/→ or Enter to change this choice.");
         }
-        Value::Color { .. } => {
-            show_status("Use ←/→ or Enter to change this color.");
+        // Basic colors are picked with ←/→ / Enter, other ones typed in
+        Value::Color {
+            ref options,
+            ref mut selected,
+            ref mut custom,
+        } => {
+            let mut text = custom.clone().unwrap_or_default();
+            edit_text_value(&key, &mut text, Some(7));
+            if let Some(index) = options.iter().position(|o| o.eq_ignore_ascii_case(&text)) {
+                *selected = index;
+                *custom = None;
+            } else if text.is_empty() {
+                *custom = None;
+            } else if ColorSpec::parse(&text).is_some() {
+                *custom = Some(text);
+            } else {
+                show_status("Colors are a name, a palette index (0-255) or #rrggbb.");
+            }
         }
         Value::Boolean { .. } => {
             show_status("Use ←/→ or Enter to toggle this boolean.");
         }
+        Value::List { .. } => {
+            show_status("Lists are edited in the JSON file.");
+        }
         Value::Category => {
             show_status("Category header (not editable).");
         }
@@ -0,0 +1,67 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of the config file under `$XDG_CONFIG_HOME` (`~/.config` when unset).
+const APP_DIRECTORY: &str = "terminal_analog_clock";
+
+/// Where the config file lives: the `--config` path, else `$TAC_CONFIG`, else
+/// `$XDG_CONFIG_HOME/terminal_analog_clock/config.json` (`config.toml` there
+/// when the user wrote one). A config left at the legacy `~/.tac.json` is
+/// moved there the first time; when it can't be, the legacy file keeps being
+/// used.
+pub fn resolve(explicit: Option<&Path>) -> PathBuf {
+    if let Some(path) = explicit {
+        return path.to_path_buf();
+    }
+    if let Some(path) = env::var_os("TAC_CONFIG").filter(|p| !p.is_empty()) {
+        return PathBuf::from(path);
+    }
+    let home = PathBuf::from(env::var("HOME").expect("Could not find HOME environment variable"));
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map_or_else(|| home.join(".config"), PathBuf::from);
+    let directory = config_home.join(APP_DIRECTORY);
+    let toml = directory.join("config.toml");
+    if toml.exists() {
+        return toml;
+    }
+    let path = directory.join("config.json");
+    let legacy = home.join(".tac.json");
+
+    if fs::create_dir_all(&directory).is_err() {
+        return legacy;
+    }
+    if !path.exists() && legacy.exists() && !migrate(&legacy, &path) {
+        return legacy;
+    }
+    path
+}
+
+/// Directory of the files the clock keeps for itself, such as the alarm
+/// history: `$XDG_STATE_HOME/terminal_analog_clock` (`~/.local/state` when
+/// unset). It may not exist yet.
+pub fn state_directory() -> PathBuf {
+    let state_home = env::var_os("XDG_STATE_HOME")
+        .filter(|p| !p.is_empty())
+        .map_or_else(
+            || {
+                let home = env::var("HOME").expect("Could not find HOME environment variable");
+                Path::new(&home).join(".local").join("state")
+            },
+            PathBuf::from,
+        );
+    state_home.join(APP_DIRECTORY)
+}
+
+/// Move `from` to `to`, copying across file systems. Returns whether it worked.
+fn migrate(from: &Path, to: &Path) -> bool {
+    if fs::rename(from, to).is_ok() {
+        return true;
+    }
+    if fs::copy(from, to).is_err() {
+        return false;
+    }
+    let _ = fs::remove_file(from);
+    true
+}
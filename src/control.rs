@@ -0,0 +1,200 @@
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::{text_width, Attributes, FrameBuffer, Style};
+
+use crate::config_edit::Config;
+use crate::minute_track;
+
+/// How long a connected script has to send its commands.
+const READ_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// What a script asked for through the control socket.
+pub enum Request {
+    /// Show `message` followed by the time left, for `duration`
+    Countdown { duration: Duration, message: String },
+    /// Remove the countdown banner
+    ClearCountdown,
+    /// Values of the minute track, or `None` to go back to the "minute track file"
+    MinuteTrack(Option<Vec<Option<f64>>>),
+}
+
+/// Unix socket at the "control socket" path, through which scripts drive the
+/// running clock with one command per line, e.g.
+/// `echo 'countdown 5:00 Deploy window closes in' | nc -U ~/.tac.sock`.
+/// Each line gets "ok" or "error: …" back. The socket file is removed when
+/// the clock quits.
+pub struct ControlSocket {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl ControlSocket {
+    /// Listen at the "control socket" path, read at startup; `None` when it is
+    /// empty. A socket file left behind by a clock that crashed is replaced,
+    /// one another clock still listens on is not.
+    pub fn open(cfg: &Config) -> Option<Result<Self, String>> {
+        let path = cfg.get_string("control socket").unwrap_or_default();
+        if path.trim().is_empty() {
+            return None;
+        }
+        let path = PathBuf::from(shellexpand::tilde(path.trim()).into_owned());
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Some(Err(format!("{} is in use", path.display())));
+            }
+            let _ = fs::remove_file(&path);
+        }
+        let listener = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => return Some(Err(format!("{}: {e}", path.display()))),
+        };
+        if let Err(e) = listener.set_nonblocking(true) {
+            return Some(Err(e.to_string()));
+        }
+        Some(Ok(Self { listener, path }))
+    }
+
+    /// Requests of the scripts that connected since the last call; never waits
+    /// for a connection.
+    pub fn poll(&self) -> Vec<Request> {
+        let mut requests = Vec::new();
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => requests.extend(serve(stream)),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+        requests
+    }
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Read the commands of one connection, answering each line.
+fn serve(stream: UnixStream) -> Vec<Request> {
+    let mut requests = Vec::new();
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(READ_TIMEOUT)).is_err()
+    {
+        return requests;
+    }
+    let Ok(mut writer) = stream.try_clone() else {
+        return requests;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match parse(&line) {
+            Ok(request) => {
+                requests.push(request);
+                "ok".to_string()
+            }
+            Err(e) => format!("error: {e}"),
+        };
+        if writeln!(writer, "{reply}").is_err() {
+            break;
+        }
+    }
+    requests
+}
+
+/// Parse one command line.
+fn parse(line: &str) -> Result<Request, String> {
+    let mut words = line.split_whitespace();
+    match words.next() {
+        Some("countdown") => match words.next() {
+            Some("clear") => Ok(Request::ClearCountdown),
+            Some(time) => {
+                let duration = parse_duration(time)
+                    .ok_or_else(|| format!("\"{time}\" is not a duration like 5:00"))?;
+                let message = words.collect::<Vec<_>>().join(" ");
+                Ok(Request::Countdown { duration, message })
+            }
+            None => Err("usage: countdown [[H:]M:]S message | countdown clear".into()),
+        },
+        Some("minutes") => match words.collect::<Vec<_>>().join(" ").as_str() {
+            "" => Err("usage: minutes value… (up to 60) | minutes clear".into()),
+            "clear" => Ok(Request::MinuteTrack(None)),
+            values => Ok(Request::MinuteTrack(Some(minute_track::parse(values)))),
+        },
+        Some(command) => Err(format!("unknown command \"{command}\"")),
+        None => Err("empty command".into()),
+    }
+}
+
+/// Seconds, "M:SS" or "H:MM:SS".
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut seconds: u64 = 0;
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    for part in parts {
+        seconds = seconds.checked_mul(60)? + part.parse::<u64>().ok()?;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Banner pushed with the "countdown" command: its message and the time
+/// left, across the top of the dial, gone at zero.
+pub struct Banner {
+    message: String,
+    until: Instant,
+}
+
+impl Banner {
+    pub fn new(message: String, duration: Duration) -> Self {
+        Self {
+            message,
+            until: Instant::now() + duration,
+        }
+    }
+
+    /// Whether the countdown reached zero.
+    pub fn is_over(&self) -> bool {
+        Instant::now() >= self.until
+    }
+
+    /// Text of the banner, e.g. "Deploy window closes in 4:59".
+    fn text(&self) -> String {
+        // Rounded up, so that the banner goes as it would show 0:00
+        let left = self.until.saturating_duration_since(Instant::now());
+        let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+        let time = match seconds {
+            3600.. => format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ),
+            _ => format!("{}:{:02}", seconds / 60, seconds % 60),
+        };
+        match self.message.is_empty() {
+            true => time,
+            false => format!("{} {time}", self.message),
+        }
+    }
+
+    /// Draw the banner as a reversed band the width of the frame on row `y`,
+    /// over whatever is there.
+    pub fn draw(&self, frame: &mut FrameBuffer, y: i32) {
+        let style = Style::default().with(Attributes::REVERSE | Attributes::BOLD);
+        let text = self.text();
+        let width = frame.width();
+        frame.put_str(0, y, &" ".repeat(width.max(0) as usize), style);
+        frame.put_str(((width - text_width(&text)) / 2).max(0), y, &text, style);
+    }
+}
@@ -0,0 +1,90 @@
+use std::mem;
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::Style;
+
+use crate::config_edit::Config;
+use crate::layout::{Layout, Slot};
+use crate::schedule::Pace;
+
+/// Wall time between two measures of the usage.
+const SAMPLE_PERIOD: Duration = Duration::from_secs(2);
+
+/// CPU time used by this process so far.
+fn process_time() -> Duration {
+    // SAFETY: timespec is plain data, and clock_gettime only writes into it
+    let mut time: libc::timespec = unsafe { mem::zeroed() };
+    if unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut time) } != 0 {
+        return Duration::ZERO;
+    }
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
+
+/// Share of a core the clock uses, and how much it slows down to stay within
+/// the "cpu budget percent" (0 for no budget).
+pub struct CpuMeter {
+    /// Wall and CPU time of the last measure
+    sample: (Instant, Duration),
+    /// Percent of a core used between the last two measures
+    usage: Option<f64>,
+    /// 0 runs at full pace, 1 turns smooth hands into animations, 2 redraws
+    /// once a second only
+    throttle: u8,
+}
+
+impl Default for CpuMeter {
+    fn default() -> Self {
+        CpuMeter {
+            sample: (Instant::now(), process_time()),
+            usage: None,
+            throttle: 0,
+        }
+    }
+}
+
+impl CpuMeter {
+    /// Measure the usage every [`SAMPLE_PERIOD`]: slow down one step when it
+    /// is over budget, speed up one step when it is under half the budget.
+    pub fn tick(&mut self, cfg: &Config) {
+        let (then, then_cpu) = self.sample;
+        let elapsed = then.elapsed();
+        if elapsed < SAMPLE_PERIOD {
+            return;
+        }
+        let cpu = process_time();
+        let usage = 100.0 * cpu.saturating_sub(then_cpu).as_secs_f64() / elapsed.as_secs_f64();
+        self.sample = (Instant::now(), cpu);
+        self.usage = Some(usage);
+
+        let budget = cfg.get_float("cpu budget percent");
+        if budget <= 0.0 {
+            self.throttle = 0;
+        } else if usage > budget {
+            self.throttle = (self.throttle + 1).min(2);
+        } else if usage < budget / 2.0 {
+            self.throttle = self.throttle.saturating_sub(1);
+        }
+    }
+
+    /// `pace`, slowed down as far as the budget requires.
+    pub fn limit(&self, pace: Pace) -> Pace {
+        match (self.throttle, pace) {
+            (0, pace) => pace,
+            (1, Pace::Smooth) => Pace::Animation,
+            (1, pace) => pace,
+            _ => Pace::EachSecond,
+        }
+    }
+
+    /// Show the usage, and the slow down if any, in the top right corner.
+    pub fn add_readout(&self, layout: &mut Layout) {
+        let Some(usage) = self.usage else {
+            return;
+        };
+        let text = match self.throttle {
+            0 => format!("cpu {usage:.1}%"),
+            throttle => format!("cpu {usage:.1}% throttled {throttle}"),
+        };
+        layout.add(Slot::NorthEast, 100, text, Style::default());
+    }
+}
@@ -0,0 +1,59 @@
+//! Calendar arithmetic for the date complications, on proleptic Gregorian
+//! dates: leap years, month lengths, moving by whole months, and the weeks
+//! of a month as a calendar view lays them out.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Whether `year` has a February 29th: every fourth year, except the
+/// centuries not divisible by 400 (1900 and 2100 have none, 2000 has one).
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Number of days of `month` (1 to 12) in `year`, 0 for any other month.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// `date` moved by `months` (negative ones go back), the day kept when the
+/// month has it and else the last day of the month: January 31st plus one
+/// month is February 28th or 29th. `None` beyond the dates chrono knows.
+pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let index = (date.year() as i64) * 12 + date.month0() as i64 + months as i64;
+    let year = i32::try_from(index.div_euclid(12)).ok()?;
+    let month = index.rem_euclid(12) as u32 + 1;
+    let day = date.day().min(days_in_month(year, month));
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Start of the day after `now`, across month and year ends.
+pub fn next_midnight(now: NaiveDateTime) -> Option<NaiveDateTime> {
+    Some(now.date().succ_opt()?.and_time(NaiveTime::MIN))
+}
+
+/// Days of `month` (1 to 12) of `year` in rows of a week from Monday to
+/// Sunday, the days of the neighbouring months left empty: the grid of a
+/// calendar view. Empty for a month that does not exist.
+pub fn month_weeks(year: i32, month: u32) -> Vec<[Option<u32>; 7]> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return Vec::new();
+    };
+    let offset = first.weekday().num_days_from_monday();
+    let days = days_in_month(year, month);
+    let mut weeks = Vec::new();
+    for week in 0..(offset + days).div_ceil(7) {
+        let mut row = [None; 7];
+        for (column, cell) in row.iter_mut().enumerate() {
+            let day = (week * 7 + column as u32 + 1).checked_sub(offset);
+            *cell = day.filter(|&day| (1..=days).contains(&day));
+        }
+        weeks.push(row);
+    }
+    weeks
+}
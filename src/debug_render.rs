@@ -0,0 +1,85 @@
+use tac::face::Hand;
+use tac::framebuffer::{FrameBuffer, Style};
+use tac::geometry::line_points;
+
+use crate::dial;
+use crate::layout::{Layout, Slot};
+
+/// Color pairs of the overlay: cells the exact hands go through but that
+/// were not plotted, and the plotted cells.
+pub const ANALYTIC_PAIR: i16 = 12;
+pub const PLOTTED_PAIR: i16 = 13;
+
+/// Cells the segment from `from` to `to` goes through, sampled every quarter cell.
+fn analytic_cells((from, to): ((f64, f64), (f64, f64))) -> Vec<(i32, i32)> {
+    let steps = ((to.0 - from.0).abs().max((to.1 - from.1).abs()) * 4.0).ceil() as usize;
+    let mut cells: Vec<(i32, i32)> = Vec::new();
+    for i in 0..=steps.max(1) {
+        let t = i as f64 / steps.max(1) as f64;
+        let cell = (
+            (from.0 + (to.0 - from.0) * t).round() as i32,
+            (from.1 + (to.1 - from.1) * t).round() as i32,
+        );
+        if !cells.contains(&cell) {
+            cells.push(cell);
+        }
+    }
+    cells
+}
+
+/// Largest distance, in cells, between a plotted cell of `hand` and its exact line.
+fn drift(hand: &Hand) -> f64 {
+    let ((x0, y0), (x1, y1)) = hand.exact;
+    let length = (x1 - x0).hypot(y1 - y0);
+    line_points(hand.from.0, hand.from.1, hand.to.0, hand.to.1)
+        .into_iter()
+        .map(|(x, y)| {
+            let (x, y) = (x as f64, y as f64);
+            if length == 0.0 {
+                (x - x0).hypot(y - y0)
+            } else {
+                ((x1 - x0) * (y0 - y) - (x0 - x) * (y1 - y0)).abs() / length
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Recolor the plotted cells of `hands` and mark in another color the cells
+/// their exact lines go through that the plotting missed.
+pub fn draw(frame: &mut FrameBuffer, hands: &[Hand]) {
+    let plotted = Style::pair(PLOTTED_PAIR);
+    for hand in hands {
+        let cells = line_points(hand.from.0, hand.from.1, hand.to.0, hand.to.1);
+        for &(x, y) in &cells {
+            if let Some(cell) = frame.get(x, y).copied() {
+                frame.put(x, y, cell.ch, plotted);
+            }
+        }
+        for (x, y) in analytic_cells(hand.exact) {
+            if !cells.contains(&(x, y)) {
+                frame.put(x, y, '·', Style::pair(ANALYTIC_PAIR));
+            }
+        }
+    }
+}
+
+/// Show the drift of each hand in the top left corner.
+pub fn add_readout(layout: &mut Layout, hands: &[Hand]) {
+    let drifts: Vec<String> = hands
+        .iter()
+        .map(|hand| {
+            let name = match hand.pair {
+                dial::HOURS_PAIR => 'h',
+                dial::MINUTES_PAIR => 'm',
+                _ => 's',
+            };
+            format!("{name} {:.2}", drift(hand))
+        })
+        .collect();
+    layout.add(
+        Slot::NorthWest,
+        100,
+        format!("drift {}", drifts.join(" ")),
+        Style::default(),
+    );
+}
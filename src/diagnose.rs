@@ -0,0 +1,14 @@
+use crate::config_edit::Config;
+
+/// Print what may be wrong with the configuration, for `tac --diagnose`.
+pub fn report(cfg: &Config, path: &str) {
+    println!("config file: {path}");
+    println!("profile: {}", cfg.profile());
+    let problems = cfg.problems();
+    if problems.is_empty() {
+        println!("no problem found");
+    }
+    for problem in problems {
+        println!("  {problem}");
+    }
+}
@@ -0,0 +1,296 @@
+use chrono::{DateTime, TimeZone, Timelike};
+use std::f64::consts::PI;
+
+use tac::face::{self, Hand};
+use tac::framebuffer::{FrameBuffer, Style};
+use tac::geometry::{ellipse_spans, polar_to_cartesian_ellipse};
+
+use crate::border::BorderStyle;
+use crate::choices::{ClockBorder, DisplaySeconds, FaceFill, Numbers, NumbersStyle, ProgressRing};
+use crate::config_edit::Config;
+use crate::minute_track;
+use crate::subdials;
+use crate::uptime;
+
+pub use tac::face::{
+    BORDER_PAIR, DIGITS_PAIR, FACE_PAIR, HOURS_PAIR, MINUTES_PAIR, PROGRESS_PAIR, SECONDS_PAIR,
+};
+
+/// Draw a whole clock (border, hour markers, sub-dials and hands) showing `now`, centred at
+/// (cx,cy) with horizontal radius `a` and vertical radius `b`.
+pub fn draw_clock<Z: TimeZone>(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    now: &DateTime<Z>,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+) {
+    draw_face(frame, cfg, cx, cy, a, b);
+
+    let border = Style::pair(BORDER_PAIR);
+    // ----- draw the ellipse (the “clock”) -----
+    let clock_border = cfg.get::<ClockBorder>();
+    if clock_border == ClockBorder::Full {
+        BorderStyle::from_config(cfg, border).draw(frame, cx, cy, a, b);
+    } else if clock_border == ClockBorder::DotAndHours {
+        for i in 0..60 {
+            let (dx, dy) = polar_to_cartesian_ellipse(
+                cx,
+                cy,
+                2.0 * PI * (i as f64) / 60.0,
+                a as f64,
+                b as f64,
+            );
+            if i % 5 == 0 {
+                let (ddx, ddy) = polar_to_cartesian_ellipse(
+                    cx,
+                    cy,
+                    2.0 * PI * (i as f64) / 60.0,
+                    (a as f64) * 0.95,
+                    (b as f64) * 0.95,
+                );
+                frame.draw_line(dx, dy, ddx, ddy, "*", border);
+            } else {
+                frame.put(dx, dy, '.', border);
+            }
+        }
+    } else if clock_border == ClockBorder::Hours {
+        for i in 0..12 {
+            let (dx, dy) = polar_to_cartesian_ellipse(
+                cx,
+                cy,
+                2.0 * PI * (i as f64) / 12.0,
+                a as f64,
+                b as f64,
+            );
+            frame.put(dx, dy, '*', border);
+        }
+    }
+
+    minute_track::draw(frame, cfg, cx, cy, a, b);
+    draw_progress_ring(frame, cfg, now, cx, cy, a, b);
+
+    let digits = Style::pair(DIGITS_PAIR);
+    match cfg.get::<Numbers>() {
+        Numbers::Digits => {
+            let style = cfg.get::<NumbersStyle>();
+            face::draw_hour_markers(frame, (cx, cy), (a, b), digits, |i| {
+                hour_label(style, i, now.hour())
+            });
+        }
+        Numbers::Stars => {
+            face::draw_hour_markers(frame, (cx, cy), (a, b), digits, |_| Some("*".into()));
+        }
+        _ => {}
+    }
+
+    subdials::draw(frame, cfg, now, cx, cy, a, b);
+    subdials::draw_date_window(frame, cfg, now, cx, cy, a, b);
+    uptime::draw(frame, cfg, cx, cy, a, b);
+
+    // ----- hands, second hand first so that the others are drawn over it -----
+    face::draw_hands(frame, &hands(cfg, now, cx, cy, a, b));
+
+    // ----- hub over the roots of the hands -----
+    let hub = cfg.get_int("center hub size").clamp(0, (b / 3) as i64) as i32;
+    if hub > 0 {
+        // Cells are about twice as high as wide, like the dial
+        for dy in -hub..=hub {
+            for dx in -2 * hub..=2 * hub {
+                if dx * dx + 4 * dy * dy <= 4 * hub * hub {
+                    frame.put(cx + dx, cy + dy, '@', border);
+                }
+            }
+        }
+    }
+}
+
+/// Fill the inside of the dial as the "face fill" entry says, under
+/// everything else.
+fn draw_face(frame: &mut FrameBuffer, cfg: &Config, cx: i32, cy: i32, a: i32, b: i32) {
+    let ch = cfg
+        .get_string("face character")
+        .and_then(|text| text.chars().next())
+        .filter(|ch| !ch.is_whitespace())
+        .unwrap_or('·');
+    let style = Style::pair(FACE_PAIR);
+    match cfg.get::<FaceFill>() {
+        FaceFill::Character => frame.fill_ellipse(cx, cy, a, b, ch, style),
+        FaceFill::Shading => {
+            for (y, first, last) in ellipse_spans(cx, cy, a, b) {
+                for x in first..=last {
+                    // Distance from the centre, 1 on the border
+                    let (dx, dy) = ((x - cx) as f64 / a as f64, (y - cy) as f64 / b as f64);
+                    let r = dx.hypot(dy);
+                    let drawn = if r < 0.5 {
+                        (x + 2 * y).rem_euclid(4) == 0
+                    } else if r < 0.8 {
+                        (x + y).rem_euclid(2) == 0
+                    } else {
+                        true
+                    };
+                    if drawn {
+                        frame.put(x, y, ch, style);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Arc just inside the border going clockwise from 12 o'clock as far as the
+/// part of the day (or of the hour) gone by, as the "progress ring" entry says.
+fn draw_progress_ring<T: Timelike>(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    now: &T,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+) {
+    let fraction = match cfg.get::<ProgressRing>() {
+        ProgressRing::Off => return,
+        ProgressRing::Day => now.num_seconds_from_midnight() as f64 / 86400.0,
+        ProgressRing::Hour => (now.minute() * 60 + now.second()) as f64 / 3600.0,
+    };
+    if b <= 2 || fraction <= 0.0 {
+        return;
+    }
+    frame.draw_arc(
+        cx,
+        cy,
+        (a - 2) as f64,
+        (b - 1) as f64,
+        0.0,
+        2.0 * PI * fraction,
+        '+',
+        Style::pair(PROGRESS_PAIR),
+    );
+}
+
+/// How long the minute hand takes to glide to a new minute.
+const GLIDE_MS: u32 = 500;
+
+/// Progress (0 to 1) of the minute hand gliding to the minute that just
+/// started, or `None` when it isn't moving.
+fn glide_progress<T: Timelike>(cfg: &Config, now: &T) -> Option<f64> {
+    let millis = now.nanosecond() / 1_000_000;
+    let gliding = cfg.get_bool("minute hand glide")
+        && !cfg.get_bool("continuous minutes")
+        && now.second() == 0
+        && millis < GLIDE_MS;
+    gliding.then(|| millis as f64 / GLIDE_MS as f64)
+}
+
+/// Whether a hand is moving between two ticks, so frames are needed more than once a second.
+pub fn is_gliding<T: Timelike>(cfg: &Config, now: &T) -> bool {
+    glide_progress(cfg, now).is_some()
+}
+
+/// A fraction entry, kept between `min` and `max`.
+fn fraction(cfg: &Config, key: &str, min: f64, max: f64) -> f64 {
+    cfg.get_float(key).clamp(min, max)
+}
+
+/// Length of a hand as a fraction of the dial radius.
+fn hand_length(cfg: &Config, key: &str) -> f64 {
+    fraction(cfg, key, 0.1, 1.0)
+}
+
+/// Roman numerals of the hours, 1 to 12.
+const ROMAN: [&str; 12] = [
+    "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII",
+];
+
+/// Text of the marker of hour `i` (1–12) in the numbers style `style`, or
+/// `None` when that hour has no number. The 24 hours style numbers the half
+/// of the day `hour` is in: 0 to 11 in the morning, 12 to 23 afterwards.
+fn hour_label(style: NumbersStyle, i: u32, hour: u32) -> Option<String> {
+    match style {
+        NumbersStyle::Roman => Some(ROMAN[i as usize - 1].to_string()),
+        NumbersStyle::TwentyFourHours => Some((i % 12 + 12 * (hour / 12)).to_string()),
+        NumbersStyle::QuartersOnly if !i.is_multiple_of(3) => None,
+        _ => Some(i.to_string()),
+    }
+}
+
+/// Text drawn along a hand: its label entry, or `default` when the label is blank.
+fn hand_label(cfg: &Config, key: &str, default: &str) -> String {
+    match cfg.get_string(key) {
+        Some(label) if !label.trim().is_empty() => label,
+        _ => default.to_string(),
+    }
+}
+
+/// The visible hands of a clock showing `now`, in drawing order.
+pub fn hands<T: Timelike>(cfg: &Config, now: &T, cx: i32, cy: i32, a: i32, b: i32) -> Vec<Hand> {
+    let hour = now.hour() % 12;
+    let minute = now.minute();
+    let display_seconds = cfg.get::<DisplaySeconds>();
+    let second = if display_seconds.is_continuous() {
+        now.second() * 1000 + (now.nanosecond() / 1_000_000)
+    } else {
+        now.second()
+    } as f64;
+
+    // Angles: 0 rad = 12 o'clock, increase clockwise.
+    let hour_angle = 2.0 * PI * ((hour as f64) + (minute as f64) / 60.0) / 12.0;
+    let minute_angle = if cfg.get_bool("continuous minutes") {
+        2.0 * PI * ((minute as f64) + second / 60.0) / 60.0
+    } else if let Some(progress) = glide_progress(cfg, now) {
+        // Smoothstep from the previous minute
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        2.0 * PI * ((minute as f64) - 1.0 + eased) / 60.0
+    } else {
+        2.0 * PI * (minute as f64) / 60.0
+    };
+
+    let mut hands = Vec::with_capacity(3);
+    let hand = |angle: f64, start: f64, end: f64, pattern: String, pair: i16| {
+        Hand::new((cx, cy), (a, b), angle, (start, end), &pattern, pair)
+    };
+
+    // ----- second hand, unless the seconds sub-dial carries it -----
+    if display_seconds != DisplaySeconds::NoDisplay && !subdials::carries_seconds(cfg, b) {
+        let second_angle = if display_seconds.is_continuous() {
+            2.0 * PI * second / 60000.0
+        } else {
+            2.0 * PI * second / 60.0
+        };
+        let length = hand_length(cfg, "second hand length");
+        let start = if display_seconds.is_end_only() {
+            fraction(cfg, "second hand tip start", 0.0, 0.9) * length
+        } else {
+            0.0
+        };
+        let pattern = hand_label(cfg, "second hand label", ".");
+        hands.push(hand(second_angle, start, length, pattern, SECONDS_PAIR));
+    }
+    // Negative start: the tail behind the centre
+    let tail = fraction(cfg, "hand tail length", 0.0, 0.5);
+    // ----- minute hand -----
+    let length = hand_length(cfg, "minute hand length");
+    let pattern = hand_label(cfg, "minute hand label", "minutes");
+    hands.push(hand(
+        minute_angle,
+        -tail * length,
+        length,
+        pattern,
+        MINUTES_PAIR,
+    ));
+    // ----- hour hand -----
+    let length = hand_length(cfg, "hour hand length");
+    let pattern = hand_label(cfg, "hour hand label", "HOURS");
+    hands.push(hand(
+        hour_angle,
+        -tail * length,
+        length,
+        pattern,
+        HOURS_PAIR,
+    ));
+    hands
+}
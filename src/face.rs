@@ -0,0 +1,134 @@
+//! Parts of the analog dial shared by the `tac` binary and the ratatui
+//! widget: the color pairs and their default colors, the hour markers and the
+//! hands, drawn into a [`FrameBuffer`].
+//!
+//! ```
+//! use tac::face::{self, Hand, HOURS_PAIR};
+//! use tac::framebuffer::FrameBuffer;
+//!
+//! let mut frame = FrameBuffer::new(40, 20);
+//! // Three o'clock: the hour hand points right
+//! let hand = Hand::new((20, 10), (16, 8), std::f64::consts::FRAC_PI_2, (0.0, 0.7), "H", HOURS_PAIR);
+//! face::draw_hands(&mut frame, &[hand]);
+//! assert_eq!(frame.get(31, 10).map(|c| c.ch), Some('H'));
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::framebuffer::{text_width, FrameBuffer, Style};
+use crate::geometry::{line_points, polar_to_cartesian_ellipse};
+
+/// Color pairs of the dial.
+pub const BORDER_PAIR: i16 = 1;
+pub const HOURS_PAIR: i16 = 2;
+pub const MINUTES_PAIR: i16 = 3;
+pub const SECONDS_PAIR: i16 = 4;
+pub const DIGITS_PAIR: i16 = 5;
+pub const FACE_PAIR: i16 = 14;
+pub const PROGRESS_PAIR: i16 = 15;
+
+/// Foreground color of each pair of the dial in the default settings.
+pub const DEFAULT_COLORS: [(i16, &str); 7] = [
+    (BORDER_PAIR, "green"),
+    (HOURS_PAIR, "red"),
+    (MINUTES_PAIR, "yellow"),
+    (SECONDS_PAIR, "cyan"),
+    (DIGITS_PAIR, "white"),
+    (FACE_PAIR, "blue"),
+    (PROGRESS_PAIR, "cyan"),
+];
+
+/// Default lengths of the hands as fractions of the dial radius, and of the
+/// tail of the hour and minute hands as a fraction of their length.
+pub const HOUR_HAND_LENGTH: f64 = 0.7;
+pub const MINUTE_HAND_LENGTH: f64 = 0.9;
+pub const SECOND_HAND_LENGTH: f64 = 1.0;
+pub const HAND_TAIL_LENGTH: f64 = 0.1;
+
+/// Where the hour markers sit, as a fraction of the dial radius.
+const MARKER_RADIUS: f64 = 0.9;
+
+/// Unrounded position of the point at `angle` on the ellipse centred at
+/// (cx,cy), as `polar_to_cartesian_ellipse` computes it before picking a cell.
+fn exact_point(cx: i32, cy: i32, angle: f64, a: f64, b: f64) -> (f64, f64) {
+    (cx as f64 + a * angle.sin(), cy as f64 - b * angle.cos())
+}
+
+/// A hand as drawn on the dial: a textured line in a color pair.
+pub struct Hand {
+    pub from: (i32, i32),
+    pub to: (i32, i32),
+    /// The hand as computed, before its ends are rounded to cells.
+    pub exact: ((f64, f64), (f64, f64)),
+    pub pattern: String,
+    pub pair: i16,
+}
+
+impl Hand {
+    /// The hand at `angle` (0 at 12 o'clock, clockwise) of the dial centred at
+    /// `(cx, cy)` with radii `(a, b)`, from `start` to `end` as fractions of
+    /// the radius; a negative start is a tail behind the centre.
+    pub fn new(
+        (cx, cy): (i32, i32),
+        (a, b): (i32, i32),
+        angle: f64,
+        (start, end): (f64, f64),
+        pattern: &str,
+        pair: i16,
+    ) -> Self {
+        let (a, b) = (a as f64, b as f64);
+        Hand {
+            from: polar_to_cartesian_ellipse(cx, cy, angle, a * start, b * start),
+            to: polar_to_cartesian_ellipse(cx, cy, angle, a * end, b * end),
+            exact: (
+                exact_point(cx, cy, angle, a * start, b * start),
+                exact_point(cx, cy, angle, a * end, b * end),
+            ),
+            pattern: pattern.into(),
+            pair,
+        }
+    }
+
+    /// Cells of the hand in the order its pattern is drawn on them: left to
+    /// right (top to bottom when vertical), so that the label reads left to
+    /// right whichever way the hand points.
+    pub fn points(&self) -> Vec<(i32, i32)> {
+        line_points(self.from.0, self.from.1, self.to.0, self.to.1)
+    }
+
+    /// Whether the hand goes through the cell (x,y).
+    pub fn covers(&self, x: i32, y: i32) -> bool {
+        self.points().contains(&(x, y))
+    }
+}
+
+/// Draw the hands in order, each over the previous ones.
+pub fn draw_hands(frame: &mut FrameBuffer, hands: &[Hand]) {
+    for hand in hands {
+        frame.draw_path(&hand.points(), &hand.pattern, Style::pair(hand.pair));
+    }
+}
+
+/// Draw the marker of each hour 1 to 12 given by `label`, centred on its
+/// place inside the border (an even length leaning left).
+pub fn draw_hour_markers(
+    frame: &mut FrameBuffer,
+    (cx, cy): (i32, i32),
+    (a, b): (i32, i32),
+    style: Style,
+    label: impl Fn(u32) -> Option<String>,
+) {
+    for i in 1..13 {
+        let Some(label) = label(i) else {
+            continue;
+        };
+        let (x, y) = polar_to_cartesian_ellipse(
+            cx,
+            cy,
+            2.0 * PI * (i as f64) / 12.0,
+            a as f64 * MARKER_RADIUS,
+            b as f64 * MARKER_RADIUS,
+        );
+        frame.put_str(x - text_width(&label) / 2, y, &label, style);
+    }
+}
@@ -0,0 +1,333 @@
+//! Off-screen frame: a grid of characters, each with a color pair and text
+//! attributes. Faces and complications draw into a [`FrameBuffer`]; a
+//! backend (ncurses in the `tac` binary) then puts it on screen.
+//!
+//! ```
+//! use tac::framebuffer::{Attributes, FrameBuffer, Style};
+//!
+//! let mut frame = FrameBuffer::new(20, 5);
+//! frame.draw_ellipse(10, 2, 8, 2, '*', Style::pair(1));
+//! frame.put_str(7, 2, "12:00", Style::pair(6).with(Attributes::BOLD));
+//! assert_eq!(frame.get(7, 2).map(|c| c.ch), Some('1'));
+//! ```
+
+use std::ops::{BitOr, BitOrAssign};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::geometry::{arc_points, ellipse_points, ellipse_spans, line_points};
+
+/// Text attributes of a cell, combined with `|`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Attributes(u8);
+
+impl Attributes {
+    pub const NONE: Attributes = Attributes(0);
+    pub const BOLD: Attributes = Attributes(1);
+    pub const DIM: Attributes = Attributes(1 << 1);
+    pub const REVERSE: Attributes = Attributes(1 << 2);
+    pub const BLINK: Attributes = Attributes(1 << 3);
+
+    /// Whether every attribute of `other` is set.
+    pub fn contains(self, other: Attributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Attributes {
+    type Output = Attributes;
+
+    fn bitor(self, other: Attributes) -> Attributes {
+        Attributes(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Attributes {
+    fn bitor_assign(&mut self, other: Attributes) {
+        self.0 |= other.0;
+    }
+}
+
+/// Color pair (0 = terminal default colors) and attributes of a cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub pair: i16,
+    pub attributes: Attributes,
+}
+
+impl Style {
+    /// Plain text in color pair `pair`.
+    pub fn pair(pair: i16) -> Self {
+        Self {
+            pair,
+            attributes: Attributes::NONE,
+        }
+    }
+
+    /// The same style with `attributes` added.
+    pub fn with(mut self, attributes: Attributes) -> Self {
+        self.attributes |= attributes;
+        self
+    }
+}
+
+/// Columns taken by `ch` on a terminal: 2 for wide characters (CJK, most
+/// emoji), 0 for combining marks and control characters, which are not drawn.
+fn char_width(ch: char) -> i32 {
+    ch.width().unwrap_or(0) as i32
+}
+
+/// Columns taken by `text` on a terminal.
+pub fn text_width(text: &str) -> i32 {
+    text.chars().map(char_width).sum()
+}
+
+/// One character cell. A wide character fills its cell and the next one,
+/// which holds [`Cell::CONTINUATION`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell {
+        ch: ' ',
+        style: Style {
+            pair: 0,
+            attributes: Attributes::NONE,
+        },
+    };
+
+    /// Character of the right half of a wide character.
+    pub const CONTINUATION: char = '\0';
+
+    /// Whether the cell is the right half of the wide character on its left,
+    /// and so must not be drawn on its own.
+    pub fn is_continuation(&self) -> bool {
+        self.ch == Cell::CONTINUATION
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::BLANK
+    }
+}
+
+/// A `width` × `height` grid of cells. Drawing outside the grid is ignored,
+/// so dials may be partly off screen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameBuffer {
+    width: i32,
+    height: i32,
+    cells: Vec<Cell>,
+}
+
+impl FrameBuffer {
+    /// A blank frame. Negative sizes give an empty frame.
+    pub fn new(width: i32, height: i32) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Self {
+            width,
+            height,
+            cells: vec![Cell::BLANK; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            Some((y * self.width + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: i32) -> &[Cell] {
+        match self.index(0, y) {
+            Some(start) => &self.cells[start..start + self.width as usize],
+            None => &[],
+        }
+    }
+
+    /// Write one character. A wide character also takes the cell on its
+    /// right, and is left out at the right edge where it has no room.
+    /// Characters without width (combining marks, controls) are ignored.
+    pub fn put(&mut self, x: i32, y: i32, ch: char, style: Style) {
+        let width = char_width(ch);
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        if width == 0 || (width == 2 && self.index(x + 1, y).is_none()) {
+            return;
+        }
+        self.split(x, y);
+        self.cells[i] = Cell { ch, style };
+        if width == 2 {
+            self.split(x + 1, y);
+            self.cells[i + 1] = Cell {
+                ch: Cell::CONTINUATION,
+                style,
+            };
+        }
+    }
+
+    /// Blank the other half of the wide character at (x,y), if there is
+    /// one, before the cell is overwritten.
+    fn split(&mut self, x: i32, y: i32) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        let other = if self.cells[i].is_continuation() {
+            self.index(x - 1, y)
+        } else if char_width(self.cells[i].ch) == 2 {
+            self.index(x + 1, y)
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            self.cells[other].ch = ' ';
+        }
+    }
+
+    /// Write `text` from (x,y) to the right; wide characters take two cells.
+    pub fn put_str(&mut self, x: i32, y: i32, text: &str, style: Style) {
+        let mut x = x;
+        for ch in text.chars() {
+            self.put(x, y, ch, style);
+            x += char_width(ch);
+        }
+    }
+
+    /// Draw a straight line from (x0,y0) to (x1,y1) textured with a repeating `pattern`.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, pattern: &str, style: Style) {
+        self.draw_path(&line_points(x0, y0, x1, y1), pattern, style);
+    }
+
+    /// Draw the cells of `points` in order, textured with a repeating `pattern`.
+    /// Points of a horizontal run hidden by the wide character drawn before
+    /// them are skipped.
+    pub fn draw_path(&mut self, points: &[(i32, i32)], pattern: &str, style: Style) {
+        let pattern: Vec<char> = pattern.chars().filter(|&c| char_width(c) > 0).collect();
+        if pattern.is_empty() {
+            return;
+        }
+        let mut drawn = 0;
+        let mut last: Option<(i32, i32, i32)> = None;
+        for &(x, y) in points {
+            let ch = pattern[drawn % pattern.len()];
+            let width = char_width(ch);
+            if let Some((lx, ly, lw)) = last {
+                if y == ly && x < lx + lw && lx < x + width {
+                    continue;
+                }
+            }
+            self.put(x, y, ch, style);
+            drawn += 1;
+            last = Some((x, y, width));
+        }
+    }
+
+    /// Draw an ellipse centred at (cx,cy) with horizontal radius `a` and vertical radius `b`.
+    pub fn draw_ellipse(&mut self, cx: i32, cy: i32, a: i32, b: i32, ch: char, style: Style) {
+        for (x, y) in ellipse_points(cx, cy, a, b) {
+            self.put(x, y, ch, style);
+        }
+    }
+
+    /// Draw the arc of an ellipse going clockwise from angle `start` to angle
+    /// `end` (radians, 0 = 12 o'clock).
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_arc(
+        &mut self,
+        cx: i32,
+        cy: i32,
+        a: f64,
+        b: f64,
+        start: f64,
+        end: f64,
+        ch: char,
+        style: Style,
+    ) {
+        for (x, y) in arc_points(cx, cy, a, b, start, end) {
+            self.put(x, y, ch, style);
+        }
+    }
+
+    /// Fill the inside of the ellipse drawn by [`FrameBuffer::draw_ellipse`]
+    /// with `ch`, row by row.
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, a: i32, b: i32, ch: char, style: Style) {
+        let width = char_width(ch).max(1);
+        for (y, first, last) in ellipse_spans(cx, cy, a, b) {
+            for x in (first..=last + 1 - width).step_by(width as usize) {
+                self.put(x, y, ch, style);
+            }
+        }
+    }
+
+    /// Blank the whole frame.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::BLANK);
+    }
+
+    /// Blank a `width` × `height` rectangle whose top left corner is (x,y).
+    pub fn clear_region(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        for row in y..y + height {
+            for col in x..x + width {
+                self.put(col, row, ' ', Style::default());
+            }
+        }
+    }
+
+    /// Copy `source` with its top left corner at (x,y); blank cells of the
+    /// source are transparent.
+    pub fn blit(&mut self, source: &FrameBuffer, x: i32, y: i32) {
+        for sy in 0..source.height {
+            for (sx, cell) in source.row(sy).iter().enumerate() {
+                if *cell != Cell::BLANK && !cell.is_continuation() {
+                    self.put(x + sx as i32, y + sy, cell.ch, cell.style);
+                }
+            }
+        }
+    }
+
+    /// Add `attributes` to every cell drawn so far, e.g. to flash the whole dial.
+    pub fn add_attributes(&mut self, attributes: Attributes) {
+        for cell in self.cells.iter_mut().filter(|c| **c != Cell::BLANK) {
+            cell.style.attributes |= attributes;
+        }
+    }
+
+    /// The characters of the frame, one line per row, without trailing blanks.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for y in 0..self.height {
+            let line: String = self
+                .row(y)
+                .iter()
+                .filter(|c| !c.is_continuation())
+                .map(|c| c.ch)
+                .collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+}
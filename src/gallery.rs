@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::choices::{
+    BorderLine, Choice, ClockBorder, DisplayMode, DisplaySeconds, FaceFill, Numbers,
+};
+use crate::config_edit::Config;
+
+/// The dial, for the settings only it shows.
+const ANALOG: (&str, &str) = (DisplayMode::KEY, "analog");
+
+/// Settings whose options are shown one after the other, with the overrides
+/// their options need to be seen: the line of the border only shows on a full
+/// border.
+const GALLERY_KEYS: &[(&str, &[(&str, &str)])] = &[
+    (DisplayMode::KEY, &[]),
+    (FaceFill::KEY, &[ANALOG]),
+    (ClockBorder::KEY, &[ANALOG]),
+    (BorderLine::KEY, &[ANALOG, (ClockBorder::KEY, "full")]),
+    (Numbers::KEY, &[ANALOG]),
+    (DisplaySeconds::KEY, &[ANALOG]),
+];
+
+/// How long each option stays on screen.
+const STEP_DURATION: Duration = Duration::from_secs(2);
+
+/// Self-test gallery: every option of the display settings in turn, as session
+/// overrides so the config file is left untouched.
+pub struct Gallery {
+    /// Index in [`GALLERY_KEYS`] and option shown
+    steps: Vec<(usize, String)>,
+    started: Instant,
+}
+
+impl Gallery {
+    pub fn new(cfg: &Config) -> Self {
+        let steps = GALLERY_KEYS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(key, _))| {
+                cfg.get_choices(key)
+                    .into_iter()
+                    .map(move |option| (i, option))
+            })
+            .collect();
+        Self {
+            steps,
+            started: Instant::now(),
+        }
+    }
+
+    /// Index of the step on screen, looping forever.
+    fn step(&self) -> Option<usize> {
+        if self.steps.is_empty() {
+            return None;
+        }
+        let elapsed = self.started.elapsed().as_millis() / STEP_DURATION.as_millis();
+        Some(elapsed as usize % self.steps.len())
+    }
+
+    /// Override the setting of the current step; the other gallery settings go
+    /// back to their configured value.
+    pub fn apply(&self, cfg: &mut Config) {
+        let Some(step) = self.step() else {
+            return;
+        };
+        let (i, option) = &self.steps[step];
+        for (other, _) in GALLERY_KEYS {
+            cfg.drop_override(other);
+        }
+        let (key, needs) = GALLERY_KEYS[*i];
+        for (needed, value) in needs {
+            cfg.override_value(needed, value);
+        }
+        cfg.override_value(key, option);
+    }
+
+    /// Name the current step on the top row, e.g. "clock border: hours (3/12)".
+    pub fn draw(&self, frame: &mut FrameBuffer) {
+        let Some(step) = self.step() else {
+            return;
+        };
+        let (i, option) = &self.steps[step];
+        let key = GALLERY_KEYS[*i].0;
+        let caption = format!("{key}: {option} ({}/{})", step + 1, self.steps.len());
+        let width = text_width(&caption);
+        let x = ((frame.width() - width) / 2).max(0);
+        frame.put_str(x, 0, &caption, Style::default());
+    }
+}
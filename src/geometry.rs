@@ -0,0 +1,173 @@
+//! Screen geometry of the clock, independent of any terminal library:
+//! every function returns cell coordinates, the caller decides how to draw them.
+
+/// The four symmetric points of an ellipse.
+fn ellipse_quadrant_points(cx: i32, cy: i32, x: i32, y: i32, points: &mut Vec<(i32, i32)>) {
+    // Quadrant symmetry
+    points.extend_from_slice(&[
+        (cx + x, cy + y),
+        (cx - x, cy + y),
+        (cx + x, cy - y),
+        (cx - x, cy - y),
+    ]);
+}
+
+/// Cells of an ellipse centred at (cx,cy) with horizontal radius `a` and vertical radius `b`.
+/// Uses the classic midpoint ellipse algorithm, in integer arithmetic only.
+pub fn ellipse_points(cx: i32, cy: i32, a: i32, b: i32) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+
+    // Squares of radii – keep them as i64 to avoid overflow in the integer part.
+    let a2 = (a as i64) * (a as i64);
+    let b2 = (b as i64) * (b as i64);
+
+    // ---------- Region 1 (slope > –1) ----------
+    let mut x: i32 = 0;
+    let mut y: i32 = b;
+    let mut d1: i64 = b2 - a2 * b as i64 + (a2 / 4);
+
+    while (2 * b2 * (x as i64)) < (2 * a2 * (y as i64)) {
+        ellipse_quadrant_points(cx, cy, x, y, &mut points);
+        if d1 < 0 {
+            d1 += 2 * b2 * (x as i64) + 3 * b2;
+        } else {
+            d1 += 2 * b2 * (x as i64) - 2 * a2 * (y as i64) + 3 * b2 + 2 * a2;
+            y -= 1;
+        }
+        x += 1;
+    }
+
+    // ---------- Region 2 (slope ≤ –1) ----------
+    // The classic formula uses a half‑pixel offset (x+0.5) and (y‑1).
+    // Everything is multiplied by 4 to keep the 0.5 in integers:
+    // d2 = b²·(2x+1)² + 4a²·(y−1)² − 4a²b².
+    let (x2, y2) = (x as i64, y as i64);
+    let mut d2: i64 = b2 * (2 * x2 + 1) * (2 * x2 + 1) + 4 * a2 * (y2 - 1) * (y2 - 1) - 4 * a2 * b2;
+
+    let mut axis_x = x;
+    while y >= 0 {
+        ellipse_quadrant_points(cx, cy, x, y, &mut points);
+        axis_x = x;
+        if d2 > 0 {
+            d2 += 4 * (3 * a2 - 2 * a2 * (y as i64));
+        } else {
+            d2 += 4 * (2 * b2 * (x as i64) - 2 * a2 * (y as i64) + 2 * b2 + 3 * a2);
+            x += 1;
+        }
+        y -= 1;
+    }
+
+    // Very flat ellipses reach the horizontal axis before x = a:
+    // finish the axis up to the extremity.
+    for x in axis_x + 1..=a {
+        ellipse_quadrant_points(cx, cy, x, 0, &mut points);
+    }
+
+    points
+}
+
+/// Rows of the inside of the ellipse drawn by [`ellipse_points`], for a
+/// scanline fill: `(y, first, last)` for each row with cells strictly between
+/// the leftmost and rightmost cells of the outline.
+pub fn ellipse_spans(cx: i32, cy: i32, a: i32, b: i32) -> Vec<(i32, i32, i32)> {
+    let mut extents: Vec<(i32, i32, i32)> = Vec::new();
+    for (x, y) in ellipse_points(cx, cy, a, b) {
+        match extents.iter_mut().find(|(row, _, _)| *row == y) {
+            Some((_, first, last)) => {
+                *first = (*first).min(x);
+                *last = (*last).max(x);
+            }
+            None => extents.push((y, x, x)),
+        }
+    }
+    extents.sort_unstable();
+    extents
+        .into_iter()
+        .filter(|&(_, first, last)| first + 1 < last)
+        .map(|(y, first, last)| (y, first + 1, last - 1))
+        .collect()
+}
+
+/// Bresenham line – cells of a straight line between (x0,y0) and (x1,y1).
+///
+/// The cells are returned in reading order (left to right, or top to bottom
+/// for vertical lines) so that a text pattern drawn along them stays readable.
+pub fn line_points(x_ori0: i32, y_ori0: i32, x_ori1: i32, y_ori1: i32) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+
+    let mut start_at_0 = x_ori0 < x_ori1;
+    if x_ori0 == x_ori1 {
+        // the writing is vertical, write from top to bottom
+        start_at_0 = y_ori0 < y_ori1
+    }
+    let mut x0 = if start_at_0 { x_ori0 } else { x_ori1 };
+    let mut y0 = if start_at_0 { y_ori0 } else { y_ori1 };
+    let x1 = if start_at_0 { x_ori1 } else { x_ori0 };
+    let y1 = if start_at_0 { y_ori1 } else { y_ori0 };
+    let dx = (x1 - x0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let dy = -(y1 - y0).abs();
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy; // error value
+
+    loop {
+        points.push((x0, y0));
+
+        // Check for the end of the line
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+
+        // Bresenham's algorithm logic
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+
+    points
+}
+
+/// Cells of a line from (x0,y0) to (x1,y1), each with the character of a
+/// repeating `pattern` drawn on it. An empty pattern gives no cells.
+pub fn textured_line(x0: i32, y0: i32, x1: i32, y1: i32, pattern: &str) -> Vec<((i32, i32), char)> {
+    line_points(x0, y0, x1, y1)
+        .into_iter()
+        .zip(pattern.chars().cycle())
+        .collect()
+}
+
+/// Convert an angle (radians) into screen coordinates for an ellipse with
+/// horizontal radius `a` and vertical radius `b`.
+pub fn polar_to_cartesian_ellipse(cx: i32, cy: i32, angle: f64, a: f64, b: f64) -> (i32, i32) {
+    // Y grows downwards on the terminal → we invert the Y component.
+    let x = cx as f64 + a * angle.sin();
+    let y = cy as f64 - b * angle.cos(); // minus = “up”
+    (x.round() as i32, y.round() as i32)
+}
+
+/// Cells of the arc of an ellipse going clockwise from angle `start` to angle
+/// `end` (radians, 0 = 12 o'clock), touching one another, a cell repeated only
+/// when the arc comes back over it.
+pub fn arc_points(cx: i32, cy: i32, a: f64, b: f64, start: f64, end: f64) -> Vec<(i32, i32)> {
+    let mut points: Vec<(i32, i32)> = Vec::new();
+    if end <= start {
+        return points;
+    }
+    // Small enough steps for consecutive cells to touch on any dial size
+    let steps = ((end - start) * a.max(b) * 2.0).ceil().max(1.0) as usize;
+    for i in 0..=steps {
+        let angle = start + (end - start) * (i as f64) / (steps as f64);
+        let p = polar_to_cartesian_ellipse(cx, cy, angle, a, b);
+        // Only repeats in a row: past a full turn the arc goes over its start again
+        if points.last() != Some(&p) {
+            points.push(p);
+        }
+    }
+    points
+}
@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+
+use tac::framebuffer::{FrameBuffer, Style};
+
+/// Color pairs of the overlay, for cells that changed in a few, a quarter
+/// and most of the last frames.
+pub const COOL_PAIR: i16 = 16;
+pub const WARM_PAIR: i16 = 17;
+pub const HOT_PAIR: i16 = 18;
+
+/// Frames the changes are counted over.
+const FRAMES: usize = 60;
+
+/// How often each cell changed over the last [`FRAMES`] frames, to find what
+/// the renderer redraws and which regions animate for nothing.
+#[derive(Default)]
+pub struct HeatMap {
+    previous: Option<FrameBuffer>,
+    /// Changed cells of each of the last frames, oldest first, row by row
+    history: VecDeque<Vec<bool>>,
+    /// Frames each cell changed in, over the history
+    counts: Vec<u32>,
+}
+
+impl HeatMap {
+    /// Compare `frame` with the previous one. A new terminal size starts over.
+    pub fn record(&mut self, frame: &FrameBuffer) {
+        let cells = (frame.width() * frame.height()) as usize;
+        let previous = match self.previous.take() {
+            Some(previous)
+                if previous.width() == frame.width() && previous.height() == frame.height() =>
+            {
+                previous
+            }
+            _ => {
+                self.history.clear();
+                self.counts = vec![0; cells];
+                self.previous = Some(frame.clone());
+                return;
+            }
+        };
+        let changed: Vec<bool> = (0..frame.height())
+            .flat_map(|y| {
+                frame
+                    .row(y)
+                    .iter()
+                    .zip(previous.row(y))
+                    .map(|(a, b)| a != b)
+            })
+            .collect();
+        for (count, &changed) in self.counts.iter_mut().zip(&changed) {
+            *count += changed as u32;
+        }
+        self.history.push_back(changed);
+        if self.history.len() > FRAMES {
+            if let Some(oldest) = self.history.pop_front() {
+                for (count, changed) in self.counts.iter_mut().zip(oldest) {
+                    *count -= changed as u32;
+                }
+            }
+        }
+        self.previous = Some(frame.clone());
+    }
+
+    /// Color the cells of `frame` that changed lately, hotter the more often.
+    pub fn draw(&self, frame: &mut FrameBuffer) {
+        let frames = self.history.len().max(1) as u32;
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                let count = self.counts[(y * frame.width() + x) as usize];
+                let pair = match count {
+                    0 => continue,
+                    c if 4 * c < frames => COOL_PAIR,
+                    c if 4 * c < 3 * frames => WARM_PAIR,
+                    _ => HOT_PAIR,
+                };
+                if let Some(cell) = frame.get(x, y).copied() {
+                    if !cell.is_continuation() {
+                        frame.put(x, y, cell.ch, Style::pair(pair));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use tac::framebuffer::{Attributes, FrameBuffer, Style};
+
+use crate::config_edit::Config;
+use crate::keymap::{Action, SHORTCUTS};
+
+const TITLE: &str = " Keyboard shortcuts ";
+const FOOTER: &str = " press any key to close ";
+
+/// Space between two columns of shortcuts.
+const COLUMN_GAP: usize = 3;
+
+/// One line per bound shortcut: key, action and the current value of the
+/// setting it changes, e.g. "c  change clock border  [dot and hours]".
+fn help_lines(cfg: &Config) -> Vec<String> {
+    // Escape and '?' are always bound, see `Keymap::from_config`
+    let mut rows = vec![("Esc".to_string(), "settings editor".to_string(), None)];
+    for &(name, action) in SHORTCUTS {
+        let key = cfg.get_string(name).filter(|k| !k.is_empty() && k != "?");
+        let key = match (key, action) {
+            (Some(key), Action::ShowHelp) => format!("{key}/?"),
+            (None, Action::ShowHelp) => "?".to_string(),
+            (Some(key), _) => key,
+            (None, _) => continue,
+        };
+        let value = action.setting().and_then(|setting| cfg.get_string(setting));
+        rows.push((key, name.to_string(), value));
+    }
+
+    let key_width = rows
+        .iter()
+        .map(|(k, _, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let name_width = rows
+        .iter()
+        .map(|(_, n, _)| n.chars().count())
+        .max()
+        .unwrap_or(0);
+    rows.into_iter()
+        .map(|(key, name, value)| match value {
+            Some(value) => format!("{key:>key_width$}  {name:<name_width$}  [{value}]"),
+            None => format!("{key:>key_width$}  {name}"),
+        })
+        .collect()
+}
+
+/// Spread `lines` over as many columns as needed to fit in `height` rows.
+/// Returns the columns, each with its width.
+fn layout(lines: &[String], height: usize) -> Vec<(usize, &[String])> {
+    if height == 0 {
+        return Vec::new();
+    }
+    lines
+        .chunks(height)
+        .map(|column| {
+            let width = column.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+            (width, column)
+        })
+        .collect()
+}
+
+/// Cut `text` to at most `width` characters.
+fn clip(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+/// Draw the shortcut list in a box over the middle of the frame; the dial
+/// stays visible around it.
+pub fn draw(frame: &mut FrameBuffer, cfg: &Config) {
+    let (rows, cols) = (frame.height(), frame.width());
+    let lines = help_lines(cfg);
+    // The box and its title/footer rows take 2 rows and 4 columns
+    let height = (rows - 2).max(0) as usize;
+    let columns = layout(&lines, height.min(lines.len()));
+    let content_width = columns.iter().map(|(w, _)| w).sum::<usize>()
+        + COLUMN_GAP * columns.len().saturating_sub(1);
+    let inner_width = content_width
+        .max(TITLE.len())
+        .max(FOOTER.len())
+        .min((cols - 4).max(0) as usize);
+    let inner_height = columns.first().map_or(0, |(_, c)| c.len());
+
+    let width = inner_width as i32 + 4;
+    let top = (rows - inner_height as i32 - 2) / 2;
+    let left = (cols - width) / 2;
+    let bottom = top + inner_height as i32 + 1;
+
+    // Box, with the title and footer written over its top and bottom edges
+    let edge = format!("+{}+", "-".repeat(inner_width + 2));
+    frame.clear_region(left, top, width, bottom - top + 1);
+    frame.put_str(left, top, &edge, Style::default());
+    frame.put_str(left, bottom, &edge, Style::default());
+    for y in top + 1..bottom {
+        frame.put(left, y, '|', Style::default());
+        frame.put(left + width - 1, y, '|', Style::default());
+    }
+    let bold = Style::default().with(Attributes::BOLD);
+    for (y, text) in [(top, TITLE), (bottom, FOOTER)] {
+        let text = clip(text, inner_width);
+        frame.put_str(left + (width - text.len() as i32) / 2, y, &text, bold);
+    }
+
+    // Columns left to right, clipped to the box
+    let mut x = 0;
+    for (column_width, column) in columns {
+        if x >= inner_width {
+            break;
+        }
+        for (i, line) in column.iter().enumerate() {
+            let text = clip(line, inner_width - x);
+            frame.put_str(
+                left + 2 + x as i32,
+                top + 1 + i as i32,
+                &text,
+                Style::default(),
+            );
+        }
+        x += column_width + COLUMN_GAP;
+    }
+}
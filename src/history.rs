@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config_edit::Config;
+use crate::config_path;
+use crate::time::{self, SessionClock};
+
+/// How many firings the history keeps, the oldest going first.
+const CAPACITY: usize = 100;
+
+/// One alarm that rang or pomodoro phase that ended.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Firing {
+    /// When it happened in the displayed time zone, "YYYY-MM-DD HH:MM:SS".
+    pub at: String,
+    /// "alarm" or "pomodoro".
+    pub event: String,
+    /// Label or time of the alarm, phase of the pomodoro.
+    pub label: String,
+}
+
+/// File holding the history between runs.
+fn path() -> PathBuf {
+    config_path::state_directory().join("history.json")
+}
+
+/// The firings kept, oldest first. A missing or unreadable file gives none.
+pub fn recent() -> Vec<Firing> {
+    fs::read_to_string(path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Add a firing happening now to the history file, dropping the oldest
+/// beyond [`CAPACITY`]. Failures are ignored: the history is only a record.
+pub fn record(cfg: &Config, event: &str, label: &str) {
+    let mut firings = recent();
+    firings.push(Firing {
+        at: time::effective_now(cfg, &SessionClock)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        event: event.into(),
+        label: label.into(),
+    });
+    let excess = firings.len().saturating_sub(CAPACITY);
+    firings.drain(..excess);
+    let path = path();
+    if let Some(directory) = path.parent() {
+        let _ = fs::create_dir_all(directory);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&firings) {
+        let _ = fs::write(path, json);
+    }
+}
@@ -0,0 +1,49 @@
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::config_edit::Config;
+
+/// Run a user-configured shell command without blocking the clock.
+///
+/// The command line is handed to `sh -c` untouched; details about the event are
+/// passed as environment variables (e.g. `TAC_EVENT`) rather than spliced into
+/// the command, so they never need quoting. Output is discarded to keep the
+/// ncurses screen intact, and the child is reaped on a background thread.
+pub fn run_command(command: &str, event: &str, vars: &[(&str, String)]) {
+    if command.trim().is_empty() {
+        return;
+    }
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env("TAC_EVENT", event)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    for (name, value) in vars {
+        cmd.env(name, value);
+    }
+    if let Ok(mut child) = cmd.spawn() {
+        thread::spawn(move || {
+            let _ = child.wait();
+        });
+    }
+}
+
+/// POST `payload` as JSON to the "webhook url" without blocking the clock,
+/// e.g. to a chat or home-automation webhook. Like commands, failures are
+/// ignored. Only builds with the `network` feature send anything.
+pub fn post_webhook(cfg: &Config, payload: serde_json::Value) {
+    let url = cfg.get_string("webhook url").unwrap_or_default();
+    if url.trim().is_empty() {
+        return;
+    }
+    #[cfg(feature = "network")]
+    thread::spawn(move || {
+        let _ = ureq::post(url.trim())
+            .header("Content-Type", "application/json")
+            .send(payload.to_string());
+    });
+    #[cfg(not(feature = "network"))]
+    let _ = payload;
+}
@@ -0,0 +1,78 @@
+use ncurses::*;
+use std::io::{self, Write};
+
+const KEY_ESCAPE: i32 = 27;
+
+/// How long the rest of a paste may take to arrive, in milliseconds.
+const PASTE_TIMEOUT_MS: i32 = 50;
+
+/// Ask the terminal to wrap pasted text in `ESC [200~` … `ESC [201~`, or stop.
+/// Off while the settings editor and the alarm screen run, whose text fields
+/// take pasted text as typed.
+pub fn bracketed_paste(enable: bool) {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(if enable {
+        b"\x1b[?2004h"
+    } else {
+        b"\x1b[?2004l"
+    });
+    let _ = stdout.flush();
+}
+
+/// Next key like `getch()`, `ERR` when none came before the timeout. Pasted
+/// text and escape sequences ncurses does not know (Alt+key, focus reports…)
+/// are swallowed and give `ERR`, instead of each character acting as a
+/// shortcut; Escape alone stays a key.
+pub fn read_key() -> i32 {
+    let ch = getch();
+    if ch != KEY_ESCAPE {
+        return ch;
+    }
+    // ncurses waited ESCDELAY after the Escape: whatever follows is already there
+    timeout(0);
+    let sequence = read_sequence();
+    if sequence.is_empty() {
+        return KEY_ESCAPE;
+    }
+    if sequence == "[200~" {
+        skip_paste();
+    }
+    ERR
+}
+
+/// The bytes after an Escape: a CSI sequence up to its final byte, or the
+/// single character of any other sequence.
+fn read_sequence() -> String {
+    let mut sequence = String::new();
+    let first = getch();
+    if !(0..0x80).contains(&first) {
+        return sequence;
+    }
+    sequence.push(first as u8 as char);
+    if first != '[' as i32 {
+        return sequence;
+    }
+    loop {
+        let ch = getch();
+        if !(0..0x80).contains(&ch) {
+            return sequence;
+        }
+        sequence.push(ch as u8 as char);
+        if (0x40..0x7f).contains(&ch) {
+            return sequence;
+        }
+    }
+}
+
+/// Drop the pasted text up to the closing `ESC [201~`, or until the terminal
+/// has been quiet for [`PASTE_TIMEOUT_MS`].
+fn skip_paste() {
+    timeout(PASTE_TIMEOUT_MS);
+    loop {
+        match getch() {
+            ERR => return,
+            KEY_ESCAPE if read_sequence() == "[201~" => return,
+            _ => {}
+        }
+    }
+}
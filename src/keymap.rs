@@ -0,0 +1,296 @@
+use ncurses::{
+    ERR, KEY_BACKSPACE, KEY_BTAB, KEY_DC, KEY_DOWN, KEY_END, KEY_F0, KEY_HOME, KEY_IC, KEY_LEFT,
+    KEY_NPAGE, KEY_PPAGE, KEY_RIGHT, KEY_UP,
+};
+use std::time::{Duration, Instant};
+
+use crate::config_edit::Config;
+
+/// Escape always opens the settings editor, whatever the config says.
+const KEY_ESCAPE: i32 = 27;
+
+/// '?' always shows the help, whatever the config says.
+const KEY_QUESTION_MARK: i32 = '?' as i32;
+
+/// Everything the clock can do in response to a key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    OpenSettings,
+    ChangeClockBorder,
+    ChangeNumberDisplay,
+    ChangeSecondsDisplay,
+    ToggleContinuousMinutes,
+    IncreaseClockWidth,
+    DecreaseClockWidth,
+    IncreaseClockHeight,
+    DecreaseClockHeight,
+    /// Move the dial by so many columns and rows
+    MoveClock(i32, i32),
+    ChangeCountdownDisplay,
+    ToggleWorldClocks,
+    ChangeDisplayMode,
+    ManageAlarms,
+    SnoozeAlarm,
+    StartPausePomodoro,
+    ResetPomodoro,
+    CaptureFrame,
+    ShowHelp,
+    NextProfile,
+    CompareNetworkTime,
+}
+
+impl Action {
+    /// Config entry changed by the action, whose value is shown in the help.
+    pub fn setting(self) -> Option<&'static str> {
+        match self {
+            Action::ChangeClockBorder => Some("clock border"),
+            Action::ChangeNumberDisplay => Some("numbers"),
+            Action::ChangeSecondsDisplay => Some("display seconds"),
+            Action::ToggleContinuousMinutes => Some("continuous minutes"),
+            Action::IncreaseClockWidth | Action::DecreaseClockWidth => Some("clock width"),
+            Action::IncreaseClockHeight | Action::DecreaseClockHeight => Some("clock height"),
+            Action::MoveClock(..) => Some("clock position"),
+            Action::ChangeCountdownDisplay => Some("countdown display"),
+            Action::ToggleWorldClocks | Action::ChangeDisplayMode => Some("display mode"),
+            _ => None,
+        }
+    }
+}
+
+/// Config entries (in the "Keyboard shortcuts" category) holding the key of each action.
+pub const SHORTCUTS: &[(&str, Action)] = &[
+    ("change clock border", Action::ChangeClockBorder),
+    ("change number display", Action::ChangeNumberDisplay),
+    ("change seconds display", Action::ChangeSecondsDisplay),
+    ("toggle continuous minutes", Action::ToggleContinuousMinutes),
+    ("increase clock width", Action::IncreaseClockWidth),
+    ("decrease clock width", Action::DecreaseClockWidth),
+    ("increase clock height", Action::IncreaseClockHeight),
+    ("decrease clock height", Action::DecreaseClockHeight),
+    ("change countdown display", Action::ChangeCountdownDisplay),
+    ("toggle world clocks", Action::ToggleWorldClocks),
+    ("change display mode", Action::ChangeDisplayMode),
+    ("manage alarms", Action::ManageAlarms),
+    ("snooze alarm", Action::SnoozeAlarm),
+    ("start/pause pomodoro", Action::StartPausePomodoro),
+    ("reset pomodoro", Action::ResetPomodoro),
+    ("capture frame", Action::CaptureFrame),
+    ("show help", Action::ShowHelp),
+    ("next profile", Action::NextProfile),
+    ("compare network time", Action::CompareNetworkTime),
+    ("quit", Action::Quit),
+];
+
+/// Name of the arrow keys moving the dial in "session-only shortcuts", as
+/// they have no entry of their own.
+const MOVE_CLOCK: &str = "move clock";
+
+/// Name of `action` in "session-only shortcuts": its shortcut entry.
+fn action_name(action: Action) -> Option<&'static str> {
+    match action {
+        Action::MoveClock(..) => Some(MOVE_CLOCK),
+        _ => SHORTCUTS
+            .iter()
+            .find(|&&(_, a)| a == action)
+            .map(|&(name, _)| name),
+    }
+}
+
+/// The names listed in "session-only shortcuts", separated by commas.
+fn session_only_names(cfg: &Config) -> Vec<String> {
+    cfg.get_string("session-only shortcuts")
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Whether the settings changed by `action` last for this session only, the
+/// action being listed in "session-only shortcuts", instead of being saved.
+pub fn is_session_only(cfg: &Config, action: Action) -> bool {
+    action_name(action).is_some_and(|name| session_only_names(cfg).iter().any(|n| n == name))
+}
+
+/// Names of "session-only shortcuts" that are no shortcut, for the report of
+/// `--diagnose`.
+pub fn unknown_session_only(cfg: &Config) -> Vec<String> {
+    session_only_names(cfg)
+        .into_iter()
+        .filter(|name| name != MOVE_CLOCK && !SHORTCUTS.iter().any(|&(n, _)| n == name))
+        .collect()
+}
+
+/// Named keys besides the letters, F-keys and Ctrl combinations, as written
+/// in the shortcut entries.
+const NAMED_KEYS: &[(&str, i32)] = &[
+    ("Space", 32),
+    ("Tab", 9),
+    ("S-Tab", KEY_BTAB),
+    ("Backspace", KEY_BACKSPACE),
+    ("Insert", KEY_IC),
+    ("Delete", KEY_DC),
+    ("Home", KEY_HOME),
+    ("End", KEY_END),
+    ("PageUp", KEY_PPAGE),
+    ("PageDown", KEY_NPAGE),
+];
+
+/// Canonical name of a key code returned by `getch()`, as stored in a
+/// shortcut entry: the character for printable ones ("c", "+"), "F1" to
+/// "F12", "C-b" for Ctrl and a letter, or a name such as "PageUp". `None`
+/// for the keys that can't be shortcuts: Enter, Escape, the arrows.
+pub fn key_name(code: i32) -> Option<String> {
+    if let Some(&(name, _)) = NAMED_KEYS.iter().find(|&&(_, c)| c == code) {
+        return Some(name.into());
+    }
+    match code {
+        33..=126 => char::from_u32(code as u32).map(String::from),
+        // Tab, Enter (^J, ^M) and Backspace (^H) are keys of their own
+        1..=26 if ![8, 9, 10, 13].contains(&code) => {
+            Some(format!("C-{}", (b'a' + code as u8 - 1) as char))
+        }
+        _ if (KEY_F0 + 1..=KEY_F0 + 12).contains(&code) => Some(format!("F{}", code - KEY_F0)),
+        _ => None,
+    }
+}
+
+/// Key code of a key name written by [`key_name`], names in any case; a
+/// single character stands for itself.
+pub fn parse_key(name: &str) -> Option<i32> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(ch as i32);
+    }
+    if let Some(&(_, code)) = NAMED_KEYS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+    {
+        return Some(code);
+    }
+    let lower = name.to_ascii_lowercase();
+    if let Some(letter) = lower.strip_prefix("c-") {
+        return match letter.as_bytes() {
+            [ch @ b'a'..=b'z'] => Some((ch - b'a' + 1) as i32),
+            _ => None,
+        };
+    }
+    match lower.strip_prefix('f')?.parse::<i32>().ok()? {
+        n @ 1..=12 => Some(KEY_F0 + n),
+        _ => None,
+    }
+}
+
+/// How long the first key of a chord waits for the second one.
+const CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Key → action table built from the shortcut entries of a `Config`.
+///
+/// A shortcut is one key ("c", "F5", "C-b", see [`key_name`]) or a chord of
+/// two keys separated by a space ("t n"). The first key of a chord is held
+/// until the second one comes, so a key starting a chord can't also be a
+/// shortcut on its own.
+pub struct Keymap {
+    bindings: Vec<(Vec<i32>, Action)>,
+    /// First key of a chord being typed, and when it was pressed
+    pending: Option<(i32, Instant)>,
+}
+
+impl Keymap {
+    /// Read every shortcut from the config. Empty or missing entries, and
+    /// those naming an unknown key, leave the action unbound.
+    pub fn from_config(cfg: &Config) -> Self {
+        let mut bindings = vec![
+            (vec![KEY_ESCAPE], Action::OpenSettings),
+            (vec![KEY_QUESTION_MARK], Action::ShowHelp),
+            // The arrow keys always move the dial
+            (vec![KEY_LEFT], Action::MoveClock(-1, 0)),
+            (vec![KEY_RIGHT], Action::MoveClock(1, 0)),
+            (vec![KEY_UP], Action::MoveClock(0, -1)),
+            (vec![KEY_DOWN], Action::MoveClock(0, 1)),
+        ];
+        for &(key, action) in SHORTCUTS {
+            let keys: Vec<i32> = cfg
+                .get_string(key)
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(parse_key)
+                .take(2)
+                .collect::<Option<_>>()
+                .unwrap_or_default();
+            if !keys.is_empty() {
+                bindings.push((keys, action));
+            }
+        }
+        Self {
+            bindings,
+            pending: None,
+        }
+    }
+
+    /// Find the action bound to a single key code returned by `getch()`,
+    /// ignoring chords.
+    ///
+    /// An exact match wins; otherwise letters are matched case-insensitively
+    /// so that 'Q' still quits when the shortcut is 'q'.
+    pub fn action(&self, ch: i32) -> Option<Action> {
+        self.find(&[ch])
+    }
+
+    /// Feed a key code returned by `getch()`, and get the action it completes.
+    /// The first key of a chord gives `None` and is remembered; a second key
+    /// not completing any chord cancels it.
+    pub fn press(&mut self, ch: i32) -> Option<Action> {
+        if ch == ERR {
+            return None;
+        }
+        if let Some(first) = self.pending() {
+            self.pending = None;
+            return self.find(&[first, ch]);
+        }
+        let starts_chord = self
+            .bindings
+            .iter()
+            .any(|(keys, _)| keys.len() == 2 && same_key(keys[0], ch));
+        if starts_chord {
+            self.pending = Some((ch, Instant::now()));
+            return None;
+        }
+        self.action(ch)
+    }
+
+    /// First key of the chord being typed, until it times out.
+    pub fn pending(&self) -> Option<i32> {
+        self.pending
+            .filter(|(_, since)| since.elapsed() < CHORD_TIMEOUT)
+            .map(|(ch, _)| ch)
+    }
+
+    /// The binding whose keys are exactly `keys`, or failing that matches
+    /// them ignoring the case of letters.
+    fn find(&self, keys: &[i32]) -> Option<Action> {
+        if let Some((_, action)) = self.bindings.iter().find(|(k, _)| k == keys) {
+            return Some(*action);
+        }
+        self.bindings
+            .iter()
+            .find(|(k, _)| {
+                k.len() == keys.len() && k.iter().zip(keys).all(|(a, b)| same_key(*a, *b))
+            })
+            .map(|&(_, action)| action)
+    }
+}
+
+/// Whether two key codes are the same key, ignoring the case of letters.
+fn same_key(a: i32, b: i32) -> bool {
+    a == b || fold_case(a).is_some_and(|a| fold_case(b) == Some(a))
+}
+
+fn fold_case(ch: i32) -> Option<char> {
+    u32::try_from(ch)
+        .ok()
+        .and_then(char::from_u32)
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+}
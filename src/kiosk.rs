@@ -0,0 +1,81 @@
+use ncurses::{KEY_BACKSPACE, KEY_ENTER};
+
+use tac::framebuffer::{Attributes, Style};
+
+use crate::config_edit::Config;
+use crate::keymap::Action;
+use crate::layout::{Layout, Slot};
+
+/// Longest PIN the prompt takes.
+const MAX_PIN_LENGTH: usize = 16;
+
+/// Whether `action` has to wait for the "settings PIN": in kiosk mode, with a
+/// PIN set, everything that changes the saved settings does.
+pub fn needs_pin(kiosk: bool, cfg: &Config, action: Action) -> bool {
+    kiosk
+        && matches!(
+            action,
+            Action::OpenSettings | Action::ManageAlarms | Action::NextProfile
+        )
+        && !cfg
+            .get_string("settings PIN")
+            .unwrap_or_default()
+            .is_empty()
+}
+
+/// What a key did to the PIN prompt.
+pub enum PinInput {
+    Typing,
+    /// The right PIN was entered: go on with the action
+    Accepted(Action),
+    Refused,
+    Cancelled,
+}
+
+/// The PIN being typed before `action` runs, shown as stars over the dial.
+pub struct PinPrompt {
+    typed: String,
+    action: Action,
+}
+
+impl PinPrompt {
+    pub fn new(action: Action) -> Self {
+        Self {
+            typed: String::new(),
+            action,
+        }
+    }
+
+    /// Feed a key: Enter checks the PIN, Escape gives up, Backspace erases.
+    pub fn key(&mut self, ch: i32, cfg: &Config) -> PinInput {
+        match ch {
+            10 | 13 | KEY_ENTER => {
+                if self.typed == cfg.get_string("settings PIN").unwrap_or_default() {
+                    PinInput::Accepted(self.action)
+                } else {
+                    PinInput::Refused
+                }
+            }
+            27 => PinInput::Cancelled,
+            8 | 127 | KEY_BACKSPACE => {
+                self.typed.pop();
+                PinInput::Typing
+            }
+            _ => {
+                let typed = u32::try_from(ch).ok().and_then(char::from_u32);
+                if let Some(c) = typed.filter(|c| !c.is_control()) {
+                    if self.typed.chars().count() < MAX_PIN_LENGTH {
+                        self.typed.push(c);
+                    }
+                }
+                PinInput::Typing
+            }
+        }
+    }
+
+    pub fn add_to(&self, layout: &mut Layout) {
+        let stars = "*".repeat(self.typed.chars().count());
+        let style = Style::default().with(Attributes::REVERSE);
+        layout.add(Slot::Center, 100, format!("PIN: {stars}_"), style);
+    }
+}
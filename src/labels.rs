@@ -0,0 +1,247 @@
+use ncurses::*;
+use serde::{Deserialize, Serialize};
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::config_edit::{edit_text_value, Config};
+use crate::dial::DIGITS_PAIR;
+use crate::layout::{Layout, Slot};
+use crate::palette::{self, ColorSpec};
+use crate::signals;
+
+/// Color pairs of the labels with a color of their own, in the order of the
+/// list; the labels after them take the color of the digits.
+const LABEL_PAIRS: [i16; 8] = [24, 25, 26, 27, 28, 29, 30, 31];
+
+/// One item of the "labels" config list: a line of text drawn on every
+/// frame, such as a name plate under the dial.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Label {
+    /// Name of the label in the list, e.g. "city"
+    #[serde(default)]
+    pub name: String,
+    pub text: String,
+    /// See [`place`]; empty for below the dial
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub position: String,
+    /// Color name, palette index or #rrggbb; empty for the color of the digits
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub color: String,
+}
+
+/// Where a label goes.
+enum Place {
+    /// With the readouts around the dial
+    Slot(Slot),
+    /// At a row and column of the screen
+    Cell(i32, i32),
+}
+
+/// Place of a "position": "below" or "above" the dial, "inside" it, a
+/// corner of the screen ("top left", "bottom right"…), or "ROW,COL" with
+/// negative numbers counting from the bottom and right edges.
+fn place(position: &str) -> Option<Place> {
+    let slot = match position.trim().to_lowercase().as_str() {
+        "" | "below" => Slot::South,
+        "above" => Slot::North,
+        "inside" => Slot::Center,
+        "top left" => Slot::NorthWest,
+        "top right" => Slot::NorthEast,
+        "bottom left" => Slot::SouthWest,
+        "bottom right" => Slot::SouthEast,
+        cell => {
+            let (row, column) = cell.split_once(',')?;
+            return Some(Place::Cell(
+                row.trim().parse().ok()?,
+                column.trim().parse().ok()?,
+            ));
+        }
+    };
+    Some(Place::Slot(slot))
+}
+
+/// Read the "labels" list; items that do not parse are skipped.
+pub fn labels(cfg: &Config) -> Vec<Label> {
+    cfg.get_list("labels")
+        .into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()
+}
+
+fn save_labels(cfg: &mut Config, labels: &[Label]) {
+    let items = labels
+        .iter()
+        .filter_map(|label| serde_json::to_value(label).ok())
+        .collect();
+    cfg.set_list("labels", items);
+}
+
+/// Give the labels with a color their pairs, over `background`.
+pub fn set_pairs(cfg: &Config, background: i32) {
+    for (pair, label) in LABEL_PAIRS.into_iter().zip(labels(cfg)) {
+        if let Some(color) = ColorSpec::parse(&label.color) {
+            palette::set_pair(pair, color.resolve(), background);
+        }
+    }
+}
+
+/// Draw the labels: those around the dial are queued in `layout` after the
+/// readouts, the ones at a row and column written into `frame` right away.
+/// Labels with a position that does not parse are left out.
+pub fn draw(frame: &mut FrameBuffer, layout: &mut Layout, cfg: &Config) {
+    for (i, label) in labels(cfg).into_iter().enumerate() {
+        let pair = match LABEL_PAIRS.get(i) {
+            Some(&pair) if ColorSpec::parse(&label.color).is_some() => pair,
+            _ => DIGITS_PAIR,
+        };
+        match place(&label.position) {
+            Some(Place::Slot(slot)) => layout.add(slot, 5, label.text, Style::pair(pair)),
+            Some(Place::Cell(row, column)) => {
+                let y = if row < 0 { frame.height() + row } else { row };
+                let x = match column {
+                    0.. => column,
+                    _ => frame.width() + column + 1 - text_width(&label.text),
+                };
+                frame.put_str(x, y, &label.text, Style::pair(pair));
+            }
+            None => {}
+        }
+    }
+}
+
+/// Draw the label management screen.
+fn draw_manage_screen(labels: &[Label], selected: usize) {
+    erase();
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+
+    mvaddstr(0, 0, "Labels");
+    mvaddstr(
+        1,
+        0,
+        "n: new  Enter: text  l: name  p: position  c: color  d: delete  Esc: back",
+    );
+
+    if labels.is_empty() {
+        mvaddstr(3, 2, "No label yet, press n to add one.");
+    }
+    for (i, label) in labels.iter().enumerate() {
+        let row = 3 + i as i32;
+        if row >= max_y - 3 {
+            break;
+        }
+        let position = match label.position.is_empty() {
+            true => "below",
+            false => &label.position,
+        };
+        let color = match label.color.is_empty() {
+            true => "digits color",
+            false => &label.color,
+        };
+        let line = format!("{:<12} {:<30}  {position}, {color}", label.name, label.text);
+        let line: String = line.chars().take((max_x - 3).max(0) as usize).collect();
+        if i == selected {
+            attron(A_REVERSE());
+        }
+        mvaddstr(row, 2, &line);
+        if i == selected {
+            attroff(A_REVERSE());
+        }
+    }
+    mvaddstr(
+        max_y - 2,
+        2,
+        "Positions: below, above, inside, top left, top right, bottom left, bottom right, or ROW,COL",
+    );
+    refresh();
+}
+
+/// Label management screen, opened from the "labels" entry of the settings
+/// editor: add, edit and delete labels. Every change is written to the
+/// config right away; a position or color that does not parse is refused.
+pub fn manage(cfg: &mut Config) {
+    nodelay(stdscr(), false);
+    let mut list = labels(cfg);
+    let mut selected = 0;
+
+    loop {
+        selected = selected.min(list.len().saturating_sub(1));
+        draw_manage_screen(&list, selected);
+        let ch = getch();
+        if signals::quit_requested() {
+            break;
+        }
+        signals::suspend_if_asked();
+        let mut changed = true;
+        match ch {
+            KEY_UP => {
+                selected = selected.saturating_sub(1);
+                changed = false;
+            }
+            KEY_DOWN => {
+                if selected + 1 < list.len() {
+                    selected += 1;
+                }
+                changed = false;
+            }
+            // 'n' -> new label
+            110 => {
+                let mut text = String::new();
+                edit_text_value("label text", &mut text, Some(128));
+                if text.is_empty() {
+                    changed = false;
+                } else {
+                    list.push(Label {
+                        text,
+                        ..Label::default()
+                    });
+                    selected = list.len() - 1;
+                }
+            }
+            // 'q', Esc -> back to the settings
+            113 | 27 => break,
+            _ => {
+                let Some(label) = list.get_mut(selected) else {
+                    continue;
+                };
+                match ch {
+                    // Enter, 'e' -> edit text
+                    10 | 13 | 101 => edit_text_value("label text", &mut label.text, Some(128)),
+                    // 'l' -> name
+                    108 => edit_text_value("label name", &mut label.name, Some(32)),
+                    // 'p' -> position
+                    112 => {
+                        let mut position = label.position.clone();
+                        edit_text_value("label position", &mut position, Some(32));
+                        match place(&position) {
+                            Some(_) => label.position = position.trim().to_string(),
+                            None => changed = false,
+                        }
+                    }
+                    // 'c' -> color
+                    99 => {
+                        let mut color = label.color.clone();
+                        edit_text_value("label color (empty: digits color)", &mut color, Some(7));
+                        match color.trim() {
+                            "" => label.color.clear(),
+                            color if ColorSpec::parse(color).is_some() => {
+                                label.color = color.to_string();
+                            }
+                            _ => changed = false,
+                        }
+                    }
+                    // 'd' -> delete
+                    100 | KEY_DC => {
+                        list.remove(selected);
+                    }
+                    _ => changed = false,
+                }
+            }
+        }
+        if changed {
+            save_labels(cfg, &list);
+        }
+    }
+    nodelay(stdscr(), true);
+}
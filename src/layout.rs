@@ -0,0 +1,265 @@
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::aspect;
+use crate::choices::ClockPosition;
+use crate::config_edit::Config;
+
+/// Where a readout goes around (or inside) the dial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)] // not every slot has a widget yet
+pub enum Slot {
+    /// Above the dial, stacked upwards.
+    North,
+    /// Below the dial, stacked downwards.
+    South,
+    /// Inside the dial, between the centre and the 3 o'clock marker.
+    East,
+    /// Inside the dial, between the 9 o'clock marker and the centre.
+    West,
+    /// Inside the dial, halfway between the centre and 12 o'clock.
+    Center,
+    /// Corners of the screen, stacked towards the middle.
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+/// One line of text waiting for a place.
+struct Widget {
+    slot: Slot,
+    priority: i32,
+    text: String,
+    style: Style,
+}
+
+/// Collects the readouts of a frame and places them in named slots around the
+/// dial centred at (cx,cy) with radii `a` and `b`.
+///
+/// Widgets are placed by decreasing priority, so the most important ones get
+/// the line closest to the dial. A widget that does not fit on its line, or
+/// that would overlap one already placed, moves to the next line of its slot;
+/// when the slot is out of lines it is left out.
+pub struct Layout {
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+    widgets: Vec<Widget>,
+}
+
+impl Layout {
+    pub fn new(cx: i32, cy: i32, a: i32, b: i32) -> Self {
+        Self {
+            cx,
+            cy,
+            a,
+            b,
+            widgets: Vec::new(),
+        }
+    }
+
+    /// Queue `text` for `slot`. Higher priorities are placed first.
+    pub fn add(&mut self, slot: Slot, priority: i32, text: String, style: Style) {
+        if !text.is_empty() {
+            self.widgets.push(Widget {
+                slot,
+                priority,
+                text,
+                style,
+            });
+        }
+    }
+
+    /// Place the queued widgets and write them into `frame`.
+    pub fn draw(mut self, frame: &mut FrameBuffer) {
+        // Stable sort: equal priorities keep the order they were added in
+        self.widgets.sort_by_key(|w| -w.priority);
+        let mut placed: Vec<(i32, i32, i32)> = Vec::new();
+        for widget in &self.widgets {
+            let width = text_width(&widget.text);
+            let spot = (0..)
+                .map(|line| self.position(widget.slot, line, width, frame))
+                .take_while(Option::is_some)
+                .flatten()
+                .find(|&(x, y)| {
+                    placed
+                        .iter()
+                        .all(|&(row, start, end)| row != y || x + width <= start || end <= x)
+                });
+            if let Some((x, y)) = spot {
+                frame.put_str(x, y, &widget.text, widget.style);
+                placed.push((y, x, x + width));
+            }
+        }
+    }
+
+    /// Top left corner of a `width`-column text on line `line` of `slot`, or
+    /// `None` when the slot has no such line.
+    fn position(
+        &self,
+        slot: Slot,
+        line: i32,
+        width: i32,
+        frame: &FrameBuffer,
+    ) -> Option<(i32, i32)> {
+        let (cx, cy, a, b) = (self.cx, self.cy, self.a, self.b);
+        let (rows, cols) = (frame.height(), frame.width());
+        if width > cols {
+            return None;
+        }
+        // Stop short of the hour markers drawn at 0.9·a
+        let inner = ((a as f64) * 0.8) as i32 - 1;
+        let (x, y) = match slot {
+            Slot::North => (
+                cx - width / 2,
+                row_clear_of_ellipse(cy, a, b, rows, width, -1, line)?,
+            ),
+            Slot::South => (
+                cx - width / 2,
+                row_clear_of_ellipse(cy, a, b, rows, width, 1, line)?,
+            ),
+            Slot::East => {
+                let start = cx + inner - width;
+                if start <= cx + 1 || line >= (b / 2).max(1) {
+                    return None;
+                }
+                (start, cy + line)
+            }
+            Slot::West => {
+                let start = cx - inner;
+                if start + width >= cx - 1 || line >= (b / 2).max(1) {
+                    return None;
+                }
+                (start, cy + line)
+            }
+            Slot::Center => {
+                if line >= (b / 2).max(1) {
+                    return None;
+                }
+                (cx - width / 2, cy - b / 2 + line)
+            }
+            Slot::NorthWest => (0, line),
+            Slot::NorthEast => (cols - width, line),
+            Slot::SouthWest => (0, rows - 1 - line),
+            Slot::SouthEast => (cols - width, rows - 1 - line),
+        };
+        let corner = matches!(
+            slot,
+            Slot::NorthWest | Slot::NorthEast | Slot::SouthWest | Slot::SouthEast
+        );
+        if corner && line >= rows / 2 {
+            return None;
+        }
+        (0..rows).contains(&y).then_some((x.max(0), y))
+    }
+}
+
+/// Row of line `line` of a `width`-column text centred horizontally on the
+/// ellipse centred at row `cy`, above it (`sign` = -1) or below it (`sign` = 1),
+/// without touching it: right outside the dial when there is room, otherwise
+/// inside its upper or lower half. Returns `None` when the text fits nowhere.
+fn row_clear_of_ellipse(
+    cy: i32,
+    a: i32,
+    b: i32,
+    rows: i32,
+    width: i32,
+    sign: i32,
+    line: i32,
+) -> Option<i32> {
+    // Outside the ellipse
+    let outside = cy + sign * (b + 1 + line);
+    if (0..rows).contains(&outside) {
+        return Some(outside);
+    }
+    // Inside, halfway between the centre and the edge of the dial
+    let dy = b / 2 + line;
+    if b / 2 < 2 || dy >= b {
+        return None;
+    }
+    let half_width = (a as f64) * (1.0 - ((dy * dy) as f64) / ((b * b) as f64)).sqrt();
+    if (width as f64) / 2.0 + 1.0 < half_width {
+        Some(cy + sign * dy)
+    } else {
+        None
+    }
+}
+
+/// Size and place of the dial on a `cols`×`rows` screen.
+pub struct Placement {
+    pub cx: i32,
+    pub cy: i32,
+    pub a: i32,
+    pub b: i32,
+    /// Vertical radius without the "clock height" offset
+    pub max_b: i32,
+    /// Horizontal radius without the "clock width" offset
+    pub round_a: i32,
+    /// Centre without the offsets, see [`anchor`]
+    pub anchor: (i32, i32),
+}
+
+impl Placement {
+    pub fn new(cfg: &Config, cols: i32, rows: i32) -> Self {
+        // ----- choose radii so that the dial looks round and everything fits -----
+        // a = horizontal radius, b = vertical radius, and a = ratio·b where
+        // ratio is the height of a cell over its width (about 2).
+        // Must satisfy: a <= cols/2‑1  and  b <= rows/2‑1.
+        // Hence: b <= min(rows/2‑1, (cols/2‑1)/ratio)
+        let ratio = aspect::cell_aspect();
+        let max_b = (rows / 2 - 1).min(((cols / 2 - 1) as f64 / ratio) as i32);
+        // vertical radius (the “height” of the clock) + custom offset,
+        // never taller than the screen
+        let b = (max_b + cfg.get_int("clock height") as i32)
+            .min(rows / 2 - 1)
+            .max(1);
+
+        // horizontal radius = ratio × the height + custom offset, never
+        // wider than the screen
+        let round_a = (ratio * b as f64).round() as i32;
+        let a = (round_a + cfg.get_int("clock width") as i32)
+            .min(cols / 2 - 1)
+            .max(1);
+
+        // ----- centre, where the "clock position" and the offsets put it -----
+        let anchor = anchor(cfg, cols, rows, a, b);
+        let (cx, cy) = keep_on_screen(
+            anchor.0 + cfg.get_int("clock column offset") as i32,
+            anchor.1 + cfg.get_int("clock row offset") as i32,
+            cols,
+            rows,
+            a,
+            b,
+        );
+        Self {
+            cx,
+            cy,
+            a,
+            b,
+            max_b,
+            round_a,
+            anchor,
+        }
+    }
+}
+
+/// Centre of a dial with radii `a` and `b` on a `cols`×`rows` screen, where
+/// the "clock position" puts it before the offsets.
+pub fn anchor(cfg: &Config, cols: i32, rows: i32, a: i32, b: i32) -> (i32, i32) {
+    let (left, right) = (a, cols - 1 - a);
+    let (top, bottom) = (b, rows - 1 - b);
+    match cfg.get::<ClockPosition>() {
+        ClockPosition::Center => (cols / 2, rows / 2),
+        ClockPosition::TopLeft => (left, top),
+        ClockPosition::TopRight => (right, top),
+        ClockPosition::BottomLeft => (left, bottom),
+        ClockPosition::BottomRight => (right, bottom),
+    }
+}
+
+/// The centre closest to (x,y) that keeps the whole dial on screen, or the
+/// top left one when the dial is larger than the screen.
+pub fn keep_on_screen(x: i32, y: i32, cols: i32, rows: i32, a: i32, b: i32) -> (i32, i32) {
+    (x.min(cols - 1 - a).max(a), y.min(rows - 1 - b).max(b))
+}
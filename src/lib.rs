@@ -0,0 +1,18 @@
+//! Terminal analog clock.
+//!
+//! The `tac` binary draws the clock with ncurses. The library exposes the
+//! terminal-independent geometry, the [`Clock`](clock::Clock) time source, the
+//! [`date`] arithmetic of the date complications, the
+//! [`FrameBuffer`](framebuffer::FrameBuffer) faces are drawn into, the
+//! [`face`] parts of the dial, and with the `ratatui` feature an
+//! [`AnalogClock`](widget::AnalogClock) widget to embed the clock face in
+//! ratatui applications.
+
+pub mod clock;
+pub mod date;
+pub mod face;
+pub mod framebuffer;
+pub mod geometry;
+
+#[cfg(feature = "ratatui")]
+pub mod widget;
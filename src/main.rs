@@ -1,377 +1,704 @@
-use chrono::{Local, Timelike};
+use chrono::{DateTime, FixedOffset};
 use ncurses::*;
-use std::cmp::min;
-use std::env;
-use std::f64::consts::PI;
-use std::path::PathBuf;
+use std::time::Duration;
 
+mod alarm;
+mod aspect;
+mod big_digital;
+mod border;
+mod calendar;
+mod capture;
+mod charset;
+mod chime;
+mod choices;
+mod cli;
+mod complications;
 mod config_edit;
+mod config_path;
+mod control;
+mod cpu;
+mod debug_render;
+mod diagnose;
+mod dial;
+mod gallery;
+mod heat_map;
+mod help;
+mod history;
+mod hooks;
+mod input;
+mod keymap;
+mod kiosk;
+mod labels;
+mod layout;
+mod minute_track;
+mod mouse;
+mod ntp;
+mod palette;
+mod pane;
+mod pomodoro;
+mod presets;
+mod radial_chart;
+mod render;
+mod schedule;
+mod signals;
+mod sizes;
+mod subdials;
+mod time;
+mod toast;
+mod uptime;
+mod word_clock;
+mod world;
 
+use alarm::Alarms;
+use chime::Chime;
+use choices::{
+    AlarmAction, Choice, ClockBorder, CountdownDisplay, DisplayMode, DisplaySeconds, Numbers,
+    StartupPage,
+};
+use clap::Parser;
+use cli::{Cli, Command};
 use config_edit::Config;
+use control::{Banner, ControlSocket, Request};
+use cpu::CpuMeter;
+use gallery::Gallery;
+use heat_map::HeatMap;
+use keymap::{Action, Keymap};
+use kiosk::{PinInput, PinPrompt};
+use layout::{Layout, Placement, Slot};
+use mouse::MouseEvent;
+use palette::set_pair;
+use pomodoro::Pomodoro;
+use render::Renderer;
+use schedule::Pace;
+use sizes::SizeLayout;
+use tac::clock::Clock;
+use tac::framebuffer::{Attributes, FrameBuffer, Style};
+use toast::Toasts;
 
-/// Plot the four symmetric points of an ellipse.
-fn plot_ellipse_points(cx: i32, cy: i32, x: i32, y: i32, ch: chtype) {
-    // Quadrant symmetry
-    let points = [
-        (cx + x, cy + y),
-        (cx - x, cy + y),
-        (cx + x, cy - y),
-        (cx - x, cy - y),
-    ];
-    for &(px, py) in &points {
-        if px >= 0 && py >= 0 {
-            mvaddch(py, px, ch);
-        }
-    }
-}
+/// How long problems found in the config file are shown at startup.
+const STARTUP_NOTICE: Duration = Duration::from_secs(8);
 
-/// Draw an ellipse centred at (cx,cy) with horizontal radius `a` and vertical radius `b`.
-/// Uses the classic integer‑based midpoint ellipse algorithm.
-fn draw_ellipse(cx: i32, cy: i32, a: i32, b: i32, ch: chtype) {
-    // Squares of radii – keep them as i64 to avoid overflow in the integer part.
-    let a2 = (a as i64) * (a as i64);
-    let b2 = (b as i64) * (b as i64);
+/// Color pair of the window background; every other pair shares its background color.
+const BACKGROUND_PAIR: i16 = 11;
 
-    // ---------- Region 1 (slope > –1) ----------
-    let mut x: i32 = 0;
-    let mut y: i32 = b;
-    let mut d1: i64 = b2 - a2 * b as i64 + (a2 / 4);
+/// Color pairs of the clock and the entries giving their foreground color;
+/// they all share the "background color".
+const CLOCK_COLORS: [(i16, &str); 12] = [
+    (dial::BORDER_PAIR, "circle color"),
+    (dial::HOURS_PAIR, "hours color"),
+    (dial::MINUTES_PAIR, "minutes color"),
+    (dial::SECONDS_PAIR, "seconds color"),
+    (dial::DIGITS_PAIR, "digits color"),
+    (dial::FACE_PAIR, "face color"),
+    (dial::PROGRESS_PAIR, "progress ring color"),
+    (complications::DIGITAL_PAIR, "digital color"),
+    (complications::DATE_PAIR, "date color"),
+    (complications::CAPTION_PAIR, "caption color"),
+    (pomodoro::WORK_PAIR, "pomodoro work color"),
+    (pomodoro::BREAK_PAIR, "pomodoro break color"),
+];
 
-    while (2 * b2 * (x as i64)) < (2 * a2 * (y as i64)) {
-        plot_ellipse_points(cx, cy, x, y, ch);
-        if d1 < 0 {
-            d1 += 2 * b2 * (x as i64) + 3 * b2;
-        } else {
-            d1 += 2 * b2 * (x as i64) - 2 * a2 * (y as i64) + 3 * b2;
-            y -= 1;
-        }
-        x += 1;
-    }
+/// `pane_mode` keeps the terminal background instead of the configured one.
+fn restore_ncurses_context(cfg: &Config, pane_mode: bool) {
+    charset::configure(cfg);
+    palette::configure(cfg);
+    use_default_colors();
+    cbreak();
+    noecho();
+    keypad(stdscr(), true);
+    nodelay(stdscr(), true);
+    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+    mouse::enable();
+    input::bracketed_paste(true);
 
-    // ---------- Region 2 (slope ≤ –1) ----------
-    // The classic formula uses a half‑pixel offset (x+0.5) and (y‑1).
-    // We compute it with `f64` to keep the 0.5 without casting problems.
-    let mut d2: f64 = b2 as f64 * ((x as f64) + 0.5).powi(2)
-        + a2 as f64 * ((y as f64) - 1.0).powi(2)
-        - (a2 * b2) as f64;
+    if has_colors() {
+        start_color();
+        let background = cfg.get_color("background color");
+        let color = |key| cfg.get_color(key).resolve();
+        let (background, text_color) = match pane_mode {
+            true => (-1, -1),
+            false if background.is_light() => (background.resolve(), COLOR_BLACK as i32),
+            false => (background.resolve(), COLOR_WHITE as i32),
+        };
 
-    while y >= 0 {
-        plot_ellipse_points(cx, cy, x, y, ch);
-        if d2 > 0.0 {
-            d2 -= 2.0 * a2 as f64 * (y as f64) + 3.0 * a2 as f64;
-        } else {
-            d2 += 2.0 * b2 as f64 * (x as f64) - 2.0 * a2 as f64 * (y as f64) + 3.0 * a2 as f64;
-            x += 1;
+        for (pair, key) in CLOCK_COLORS {
+            set_pair(pair, color(key), background);
+        }
+        set_pair(BACKGROUND_PAIR, text_color, background); // plain text
+        set_pair(debug_render::ANALYTIC_PAIR, COLOR_YELLOW as i32, background); // --debug-render
+        set_pair(debug_render::PLOTTED_PAIR, COLOR_RED as i32, background);
+        set_pair(heat_map::COOL_PAIR, COLOR_BLACK as i32, COLOR_BLUE as i32); // --heat-map
+        set_pair(heat_map::WARM_PAIR, COLOR_BLACK as i32, COLOR_YELLOW as i32);
+        set_pair(heat_map::HOT_PAIR, COLOR_BLACK as i32, COLOR_RED as i32);
+        let levels = [COLOR_BLUE, COLOR_CYAN, COLOR_GREEN, COLOR_YELLOW, COLOR_RED];
+        for (pair, color) in minute_track::LEVEL_PAIRS.into_iter().zip(levels) {
+            set_pair(pair, color as i32, background); // minute track
         }
-        y -= 1;
+        labels::set_pairs(cfg, background);
+        bkgd(' ' as chtype | COLOR_PAIR(BACKGROUND_PAIR) as chtype);
     }
 }
 
-/// Bresenham line drawing – draws a straight line from (x0,y0) to (x1,y1)
-/// using a repeating string pattern for the line's texture.
-fn draw_line(x_ori0: i32, y_ori0: i32, x_ori1: i32, y_ori1: i32, pattern: &str) {
-    // If the pattern is empty, there's nothing to draw.
-    if pattern.is_empty() {
-        return;
-    }
-    let mut start_at_0 = x_ori0 < x_ori1;
-    if x_ori0 == x_ori1 {
-        // the writing is vertical, write from top to bottom
-        start_at_0 = y_ori0 < y_ori1
+/// Draw what the "display mode" shows into `frame`, and queue its readouts in
+/// `layout`. Returns the mode drawn: the dial when there are no world clocks,
+/// none when the frame is too small for any face and shows the time in digits.
+fn draw_display(
+    frame: &mut FrameBuffer,
+    layout: &mut Layout,
+    cfg: &Config,
+    now: &DateTime<FixedOffset>,
+    placement: &Placement,
+    pomodoro: &Pomodoro,
+    debug_render: bool,
+) -> Option<DisplayMode> {
+    if pane::too_small(frame.width(), frame.height()) {
+        pane::draw_compact(frame, cfg, now);
+        return None;
     }
-    let mut x0 = if start_at_0 { x_ori0 } else { x_ori1 };
-    let mut y0 = if start_at_0 { y_ori0 } else { y_ori1 };
-    let x1 = if start_at_0 { x_ori1 } else { x_ori0 };
-    let y1 = if start_at_0 { y_ori1 } else { y_ori0 };
-    let dx = (x1 - x0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let dy = -(y1 - y0).abs();
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx + dy; // error value
-
-    // Create an iterator that cycles through the characters of the pattern indefinitely.
-    let mut pattern_chars = pattern.chars().cycle();
-
-    loop {
-        // Get the next character from our cycling iterator and draw it.
-        // .unwrap() is safe here because we checked that the pattern is not empty.
-        let ch = pattern_chars.next().unwrap();
-        mvaddch(y0, x0, ch as chtype);
-
-        // Check for the end of the line
-        if x0 == x1 && y0 == y1 {
-            break;
+    let Placement { cx, cy, a, b, .. } = *placement;
+    let world_clocks = world::world_clocks(cfg);
+    // Without world clocks, the world clock mode shows the dial
+    let mode = match cfg.get::<DisplayMode>() {
+        DisplayMode::WorldClocks if world_clocks.is_empty() => DisplayMode::Analog,
+        mode => mode,
+    };
+    match mode {
+        DisplayMode::WorldClocks => {
+            world::draw_world_clocks(frame, cfg, &world_clocks, now.to_utc());
         }
+        DisplayMode::BigDigital => big_digital::draw(frame, cfg, now),
+        DisplayMode::WordClock => word_clock::draw(frame, cfg, now),
+        DisplayMode::RadialChart => radial_chart::draw(frame, cfg, now, cx, cy, a, b),
+        DisplayMode::Analog => {
+            dial::draw_clock(frame, cfg, now, cx, cy, a, b);
+            if debug_render {
+                let hands = dial::hands(cfg, now, cx, cy, a, b);
+                debug_render::draw(frame, &hands);
+                debug_render::add_readout(layout, &hands);
+            }
+            pomodoro.draw(frame, cfg, now, cx, cy, a, b);
 
-        // Bresenham's algorithm logic
-        let e2 = 2 * err;
-        if e2 >= dy {
-            err += dy;
-            x0 += sx;
-        }
-        if e2 <= dx {
-            err += dx;
-            y0 += sy;
+            // ----- complications -----
+            complications::add_digital(layout, cfg, now);
+            complications::add_date(layout, cfg, now);
+            complications::add_countdown(layout, cfg, now);
+            complications::add_caption(layout, cfg, now);
+            pomodoro.add_readout(layout, cfg);
         }
     }
+    labels::draw(frame, layout, cfg);
+    Some(mode)
 }
 
-/// Convert an angle (radians) into screen coordinates for an ellipse with
-/// horizontal radius `a` and vertical radius `b`.
-fn polar_to_cartesian_ellipse(cx: i32, cy: i32, angle: f64, a: f64, b: f64) -> (i32, i32) {
-    // Y grows downwards on the terminal → we invert the Y component.
-    let x = cx as f64 + a * angle.sin();
-    let y = cy as f64 - b * angle.cos(); // minus = “up”
-    (x.round() as i32, y.round() as i32)
+/// Set the choice `value` from a shortcut or the mouse, saved unless the
+/// shortcut is listed in "session-only shortcuts".
+fn change<C: Choice>(cfg: &mut Config, value: C, session_only: bool) {
+    if session_only {
+        cfg.override_value(C::KEY, value.name());
+    } else {
+        cfg.set(value);
+    }
 }
 
-fn restore_ncurses_context(cfg: &Config) {
-    use_default_colors();
-    cbreak();
-    noecho();
-    keypad(stdscr(), true);
-    nodelay(stdscr(), true);
-    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
-
-    if has_colors() {
-        start_color();
-        let circle_color = cfg.get_option("circle color") as i16;
-        let hours_color = cfg.get_option("hours color") as i16;
-        let minutes_color = cfg.get_option("minutes color") as i16;
-        let seconds_color = cfg.get_option("seconds color") as i16;
-        let digits_color = cfg.get_option("digits color") as i16;
+/// The clock as `cols`×`rows` cells, laid out as on a terminal of that size:
+/// for `tac --print` and the preview of the settings editor.
+fn draw_frame(cfg: &Config, now: &DateTime<FixedOffset>, cols: i32, rows: i32) -> FrameBuffer {
+    let placement = Placement::new(cfg, cols, rows);
+    let mut frame = FrameBuffer::new(cols, rows);
+    let mut layout = Layout::new(placement.cx, placement.cy, placement.a, placement.b);
+    let pomodoro = Pomodoro::default();
+    draw_display(
+        &mut frame,
+        &mut layout,
+        cfg,
+        now,
+        &placement,
+        &pomodoro,
+        false,
+    );
+    layout.draw(&mut frame);
+    frame
+}
 
-        init_pair(1, circle_color, -1); // ellipse
-        init_pair(2, hours_color, -1); // hour hand
-        init_pair(3, minutes_color, -1); // minute hand
-        init_pair(4, seconds_color, -1); // second hand
-        init_pair(5, digits_color, -1); // digits
-    }
+/// Print the clock once as plain text, the size of the terminal (80×24 when
+/// standard output is not one), for `tac --print`.
+fn print_frame(cfg: &Config, clock: &dyn Clock) {
+    let (cols, rows) = aspect::terminal_size().unwrap_or((80, 24));
+    let now = time::effective_now(cfg, clock);
+    charset::configure(cfg);
+    print!(
+        "{}",
+        charset::text(&draw_frame(cfg, &now, cols, rows).to_text())
+    );
 }
 
 fn main() {
-    let home = env::var("HOME").expect("Could not find HOME environment variable");
-    let mut path = PathBuf::from(home);
-    path.push(".tac.json");
+    uptime::start();
+    let cli = Cli::parse();
+    let path = config_path::resolve(cli.config.as_deref());
+    if cli.diagnose {
+        let cfg = Config::inspect(path.to_str().unwrap());
+        diagnose::report(&cfg, path.to_str().unwrap());
+        return;
+    }
     let mut cfg = Config::load(path.to_str().unwrap());
+    if let Err(e) = cli.apply(&mut cfg) {
+        eprintln!("tac: {e}");
+        std::process::exit(2);
+    }
+    if let Some(date) = cli.date {
+        match time::moment(&cfg, date) {
+            Some(moment) => time::start_at(moment),
+            None => {
+                eprintln!("tac: {date} does not exist in the time zone shown");
+                std::process::exit(2);
+            }
+        }
+    }
+    let clock: Box<dyn Clock> = Box::new(time::SessionClock);
+    // Only a chart waits for the data to print
+    if !cli.print || cfg.get::<DisplayMode>() == DisplayMode::RadialChart {
+        radial_chart::read_stdin(cli.print);
+    }
+    if cli.print {
+        print_frame(&cfg, clock.as_ref());
+        return;
+    }
+    if cli.no_color {
+        palette::disable_colors();
+    }
 
     // Init ncurses
     setlocale(LcCategory::all, "");
     initscr();
+    signals::install();
     start_color();
-    restore_ncurses_context(&cfg);
+    restore_ncurses_context(&cfg, false);
+    let mut keys = Keymap::from_config(&cfg);
+    let mut alarms = Alarms::default();
+    let mut pomodoro = Pomodoro::default();
+    let mut toasts = Toasts::default();
+    // Typos in a hand-edited config file
+    let problems = cfg.problems();
+    match problems.as_slice() {
+        [] => {}
+        [problem] => toasts.show_for(format!("Config: {problem}"), STARTUP_NOTICE),
+        _ => toasts.show_for(
+            format!("Config: {} problems, see tac --diagnose", problems.len()),
+            STARTUP_NOTICE,
+        ),
+    }
+    // ----- startup page -----
+    // Its face is a session override set with the command line options
+    match cfg.get::<StartupPage>() {
+        StartupPage::Pomodoro => pomodoro.start_pause(&cfg),
+        StartupPage::Alarms => {
+            input::bracketed_paste(false);
+            alarm::manage(&mut cfg);
+            input::bracketed_paste(true);
+        }
+        StartupPage::History => {
+            nodelay(stdscr(), false);
+            alarm::show_history();
+            nodelay(stdscr(), true);
+        }
+        _ => {}
+    }
+    let control = match ControlSocket::open(&cfg) {
+        Some(Ok(control)) => Some(control),
+        Some(Err(e)) => {
+            toasts.show_for(format!("Control socket: {e}"), STARTUP_NOTICE);
+            None
+        }
+        None => None,
+    };
+    let mut banner: Option<Banner> = None;
+    let mut chime = Chime::default();
+    let mut show_help = false;
+    let mut confirm_quit = false;
+    let mut pin_prompt: Option<PinPrompt> = None;
+    let mut renderer = Renderer::default();
+    let gallery = (cli.command == Some(Command::Gallery)).then(|| Gallery::new(&cfg));
+    let debug_render = cli.debug_render;
+    let mut heat_map = cli.heat_map.then(HeatMap::default);
+    let mut cpu = CpuMeter::default();
+    let mut pane_mode = false;
+    let mut size_layout = SizeLayout::default();
 
     /* ---------- main loop ---------- */
     loop {
+        // ----- edits of the config file made elsewhere -----
+        if cfg.changed_on_disk() {
+            match cfg.reload() {
+                Ok(()) => {
+                    restore_ncurses_context(&cfg, pane_mode);
+                    keys = Keymap::from_config(&cfg);
+                    size_layout.invalidate();
+                    renderer.invalidate();
+                    toasts.show("Config reloaded");
+                }
+                Err(e) => toasts.show(format!("Config not reloaded: {e}")),
+            }
+        }
+
+        // ----- commands of scripts -----
+        for request in control.iter().flat_map(ControlSocket::poll) {
+            match request {
+                Request::Countdown { duration, message } => {
+                    banner = Some(Banner::new(message, duration));
+                }
+                Request::ClearCountdown => banner = None,
+                Request::MinuteTrack(series) => minute_track::push(series),
+            }
+        }
+        if banner.as_ref().is_some_and(Banner::is_over) {
+            banner = None;
+        }
+
         // ----- terminal size & centre -----
         let mut rows = 0;
         let mut cols = 0;
         getmaxyx(stdscr(), &mut rows, &mut cols);
-        let cx = cols / 2;
-        let cy = rows / 2;
-
-        // ----- choose radii so that width = 2 × height and everything fits -----
-        // a = horizontal radius, b = vertical radius, and a = 2·b.
-        // Must satisfy: a <= cols/2‑1  and  b <= rows/2‑1.
-        // Hence: b <= min(rows/2‑1, (cols/2‑1)/2)
-        let max_b = min(rows / 2 - 1, (cols / 2 - 1) / 2);
-        let b = max_b; // vertical radius (the “height” of the clock)
-                       //        let a = b;          // horizontal radius (twice the height)
-                       // horizontal radius = (twice the height) + custom offset
-        let a = 2 * b + (cfg.get_int("clock width") as i32);
+        if pane::wanted(cli.pane, rows, cols) != pane_mode {
+            pane_mode = !pane_mode;
+            pane::apply(&mut cfg, pane_mode);
+            // The settings given on the command line win over those of pane mode
+            let _ = cli.apply(&mut cfg);
+            restore_ncurses_context(&cfg, pane_mode);
+            renderer.invalidate();
+        }
+        if let Some(gallery) = &gallery {
+            gallery.apply(&mut cfg);
+        }
+        size_layout.apply(&mut cfg, cols, rows);
+        let placement = Placement::new(&cfg, cols, rows);
+        let Placement {
+            cx,
+            cy,
+            a,
+            b,
+            max_b,
+            round_a,
+            anchor: (anchor_x, anchor_y),
+        } = placement;
 
-        // ----- clear screen -----
-        erase();
+        // ----- current time in the displayed zone -----
+        let now = time::effective_now(&cfg, clock.as_ref());
 
-        // ----- draw the ellipse (the “clock”) -----
-        if cfg.get_option("clock border") == 1 {
-            if has_colors() {
-                attron(COLOR_PAIR(1));
-            }
-            draw_ellipse(cx, cy, a, b, '*' as chtype);
-            if has_colors() {
-                attroff(COLOR_PAIR(1));
-            }
-        } else if cfg.get_option("clock border") == 2 {
-            if has_colors() {
-                attron(COLOR_PAIR(1));
-            }
-            for i in 0..60 {
-                let (dx, dy) = polar_to_cartesian_ellipse(
-                    cx,
-                    cy,
-                    2.0 * PI * (i as f64) / 60.0,
-                    a as f64,
-                    b as f64,
-                );
-                if i % 5 == 0 {
-                    let (ddx, ddy) = polar_to_cartesian_ellipse(
-                        cx,
-                        cy,
-                        2.0 * PI * (i as f64) / 60.0,
-                        (a as f64) * 0.95,
-                        (b as f64) * 0.95,
-                    );
-                    draw_line(dx, dy, ddx, ddy, "*");
-                } else {
-                    draw_line(dx, dy, dx, dy, ".");
-                }
-            }
-            if has_colors() {
-                attroff(COLOR_PAIR(1));
-            }
-        } else if cfg.get_option("clock border") == 3 {
-            if has_colors() {
-                attron(COLOR_PAIR(1));
-            }
-            for i in 0..12 {
-                let (dx, dy) = polar_to_cartesian_ellipse(
-                    cx,
-                    cy,
-                    2.0 * PI * (i as f64) / 12.0,
-                    a as f64,
-                    b as f64,
-                );
-                draw_line(dx, dy, dx, dy, "*");
-            }
-            if has_colors() {
-                attroff(COLOR_PAIR(1));
-            }
+        // ----- border color, shifting as the next alarm nears -----
+        if has_colors() {
+            let border = alarms.border_color(&cfg, now.naive_local());
+            let (_, background) = palette::pair_colors(dial::BORDER_PAIR);
+            set_pair(dial::BORDER_PAIR, border.resolve(), background);
         }
 
-        // ----- current local time -----
-        let now = Local::now();
-        let hour = (cfg.get_int("local time offset") + (now.hour() as i64)) % 12;
-        let minute = now.minute();
-        let second = match cfg.get_option("display seconds") {
-            2 | 4 => now.second() * 1000 + (now.nanosecond() / 1_000_000),
-            _ => now.second(),
-        } as f64;
+        // ----- blank frame -----
+        let mut frame = FrameBuffer::new(cols, rows);
+        let mut layout = Layout::new(cx, cy, a, b);
 
-        // Angles: 0 rad = 12 o'clock, increase clockwise.
-        let hour_angle = 2.0 * PI * ((hour as f64) + (minute as f64) / 60.0) / 12.0;
-        let minute_angle = if cfg.get_bool("continuous minutes") {
-            2.0 * PI * ((minute as f64) + (second as f64) / 60.0) / 60.0
-        } else {
-            2.0 * PI * (minute as f64) / 60.0
-        };
+        let mode = draw_display(
+            &mut frame,
+            &mut layout,
+            &cfg,
+            &now,
+            &placement,
+            &pomodoro,
+            debug_render,
+        );
 
-        for i in 1..13 {
-            if has_colors() {
-                attron(COLOR_PAIR(5));
-            }
-            let (dx, dy) = polar_to_cartesian_ellipse(
-                cx,
-                cy,
-                2.0 * PI * (i as f64) / 12.0,
-                (a as f64) * 0.9,
-                (b as f64) * 0.9,
-            );
-            if cfg.get_int("numbers") == 2 {
-                if i > 9 {
-                    draw_line(dx - 1, dy, dx, dy, "1");
-                }
-                let s = (i % 10).to_string();
-                draw_line(dx, dy, dx, dy, &s);
-            } else if cfg.get_int("numbers") == 1 {
-                draw_line(dx, dy, dx, dy, "*");
-            }
+        if debug_render {
+            cpu.add_readout(&mut layout);
         }
 
-        // ----- second hand -----
-        if cfg.get_option("display seconds") > 0 {
-            let second_angle = match cfg.get_option("display seconds") {
-                2 | 4 => 2.0 * PI * second / 60000.0,
-                _ => 2.0 * PI * second / 60.0,
-            };
-            let (sx, sy) = polar_to_cartesian_ellipse(cx, cy, second_angle, a as f64, b as f64);
-            if has_colors() {
-                attron(COLOR_PAIR(4));
-            }
-            if cfg.get_option("display seconds") < 3 {
-                draw_line(cx, cy, sx, sy, ".");
-            } else {
-                let (bx, by) = polar_to_cartesian_ellipse(
-                    cx,
-                    cy,
-                    second_angle,
-                    (a as f64) * 0.8,
-                    (b as f64) * 0.8,
-                );
-                draw_line(bx, by, sx, sy, ".");
-            }
-            if has_colors() {
-                attroff(COLOR_PAIR(4));
-            }
+        // ----- alarm message -----
+        if let Some(message) = alarms.message(&cfg, now.naive_local()) {
+            let style = Style::default().with(Attributes::REVERSE);
+            layout.add(Slot::Center, 100, message, style);
         }
-        // ----- minute hand -----
-        let (mx, my) =
-            polar_to_cartesian_ellipse(cx, cy, minute_angle, (a as f64) * 0.9, (b as f64) * 0.9);
-        if has_colors() {
-            attron(COLOR_PAIR(3));
+        // ----- quit question -----
+        if confirm_quit {
+            let style = Style::default().with(Attributes::REVERSE);
+            let question = "Timer running — quit anyway? y/n".to_string();
+            layout.add(Slot::Center, 100, question, style);
         }
-        draw_line(cx + (cx - mx) / 10, cy + (cy - my) / 10, mx, my, "minutes");
-        if has_colors() {
-            attroff(COLOR_PAIR(3));
+        if let Some(prompt) = &pin_prompt {
+            prompt.add_to(&mut layout);
         }
-        // ----- hour hand -----
-        let (hx, hy) =
-            polar_to_cartesian_ellipse(cx, cy, hour_angle, (a as f64) * 0.7, (b as f64) * 0.7);
-        if has_colors() {
-            attron(COLOR_PAIR(2));
+        // ----- first key of a chord -----
+        if let Some(first) = keys.pending().and_then(|k| char::from_u32(k as u32)) {
+            let style = Style::default().with(Attributes::REVERSE);
+            layout.add(Slot::SouthWest, 100, format!("{first} …"), style);
         }
-        draw_line(cx + (cx - hx) / 10, cy + (cy - hy) / 10, hx, hy, "HOURS");
-        if has_colors() {
-            attroff(COLOR_PAIR(2));
+        toasts.add_to(&mut layout);
+        layout.draw(&mut frame);
+        if let Some(banner) = &banner {
+            banner.draw(&mut frame, (cy - b).max(0));
         }
 
-        // ----- refresh & input -----
-        refresh();
+        // Alarms brighten the whole dial while they ramp up, then make it flash.
+        frame.add_attributes(alarms.attributes(&cfg, now.naive_local()) | pomodoro.attributes());
 
-        let ch = getch();
-        if ch == 27_i32 {
-            cfg.terminal_edit_json();
-            restore_ncurses_context(&cfg);
+        if let Some(gallery) = &gallery {
+            gallery.draw(&mut frame);
         }
-        if ch == 'q' as i32 || ch == 'Q' as i32 {
+        if show_help {
+            help::draw(&mut frame, &cfg);
+        }
+
+        if let Some(heat_map) = &mut heat_map {
+            heat_map.record(&frame);
+            heat_map.draw(&mut frame);
+        }
+
+        // ----- refresh & input -----
+        renderer.present(&frame);
+        for alarm in alarms.tick(&cfg, now.naive_local(), &mut toasts) {
+            match alarm.action {
+                AlarmAction::Preset => presets::apply_named(&mut cfg, &alarm.argument, &mut toasts),
+                AlarmAction::Pomodoro => pomodoro.start(&cfg),
+                _ => {}
+            }
+        }
+        pomodoro.tick(&cfg, &mut toasts);
+        chime.tick(&cfg, now.naive_local());
+        cpu.tick(&cfg);
+
+        // Wait for a key until the next frame is due
+        let smooth_seconds = cfg.get::<DisplaySeconds>().is_continuous();
+        let pace = if smooth_seconds || dial::is_gliding(&cfg, &now) {
+            Pace::Smooth
+        } else if pomodoro.in_transition()
+            || chime.is_striking()
+            || keys.pending().is_some()
+            || !toasts.is_empty()
+        {
+            Pace::Animation
+        } else {
+            Pace::EachSecond
+        };
+        timeout(schedule::delay_until_next_frame(
+            clock.now(),
+            cpu.limit(pace),
+            cli.fps,
+        ));
+        let ch = input::read_key();
+        // ----- signals -----
+        if signals::quit_requested() {
             break;
         }
-        if ch == 's' as i32 || ch == 'S' as i32 {
-            cfg.set_option(
-                "display seconds",
-                ((cfg.get_option("display seconds") as i64) + 1) % 5,
-            );
+        if signals::suspend_if_asked() {
+            restore_ncurses_context(&cfg, pane_mode);
         }
-        if ch == 'c' as i32 || ch == 'C' as i32 {
-            cfg.set_option(
-                "clock border",
-                ((cfg.get_option("clock border") as i64) + 1) % 4,
-            );
+        if signals::take_resumed() {
+            // Whatever ran meanwhile may have drawn over the clock
+            clear();
+            renderer.invalidate();
+            continue;
         }
-        if ch == 'n' as i32 || ch == 'N' as i32 {
-            cfg.set_option("numbers", ((cfg.get_option("numbers") as i64) + 1) % 3);
+        // A resize is no key press: draw the new size right away, from scratch
+        if ch == KEY_RESIZE {
+            renderer.invalidate();
+            continue;
         }
-        if ch == 'm' as i32 || ch == 'M' as i32 {
-            cfg.set_bool("continuous minutes", !cfg.get_bool("continuous minutes"));
+        if ch != ERR && alarms.is_active(&cfg, now.naive_local()) {
+            // The snooze key snoozes a ringing alarm, any other key silences it
+            if keys.action(ch) == Some(Action::SnoozeAlarm)
+                && alarms.is_ringing(&cfg, now.naive_local())
+            {
+                alarms.snooze(&cfg, now.naive_local());
+                toasts.show(format!(
+                    "Snoozed for {} min",
+                    cfg.get_int("snooze minutes").max(1)
+                ));
+            } else {
+                alarms.dismiss(&cfg, now.naive_local());
+            }
+            continue;
         }
-        if ch == '+' as i32 &&  cfg.get_int("clock width") < (b as i64) {
-            cfg.set_int("clock width", cfg.get_int("clock width") - 1);
+        // The PIN prompt takes every key until it is answered
+        let mut unlocked = None;
+        if let Some(prompt) = pin_prompt.as_mut().filter(|_| ch != ERR) {
+            match prompt.key(ch, &cfg) {
+                PinInput::Typing => continue,
+                PinInput::Accepted(action) => unlocked = Some(action),
+                PinInput::Refused => toasts.show("Wrong PIN"),
+                PinInput::Cancelled => {}
+            }
+            pin_prompt = None;
+            if unlocked.is_none() {
+                continue;
+            }
         }
-        if ch == '-' as i32 && cfg.get_int("clock width") > (-b as i64) {
-            cfg.set_int("clock width", cfg.get_int("clock width") - 1);
+        if ch != ERR && confirm_quit {
+            // 'y' quits, any other key keeps the clock running
+            confirm_quit = false;
+            if ch == 'y' as i32 || ch == 'Y' as i32 {
+                break;
+            }
+            continue;
         }
-
-        if cfg.get_option("display seconds") == 2 || cfg.get_option("display seconds") == 4 {
-            // Sleep a little (≈30ms → ~33fps)
-            napms(30);
-        } else {
-            // Sleep 1 second
-            //napms(333);
-            // Sleep .2 second
-            napms(66);
+        if ch != ERR && show_help {
+            // Any key closes the help
+            show_help = false;
+            continue;
+        }
+        if ch == KEY_MOUSE {
+            match mouse::event() {
+                // Clicking a hand shows or hides the second hand, clicking
+                // elsewhere on the face changes its border
+                Some(MouseEvent::Click(x, y)) if mode == Some(DisplayMode::Analog) => {
+                    if dial::hands(&cfg, &now, cx, cy, a, b)
+                        .iter()
+                        .any(|hand| hand.covers(x, y))
+                    {
+                        let seconds = match cfg.get::<DisplaySeconds>() {
+                            DisplaySeconds::NoDisplay => DisplaySeconds::FullEachSecond,
+                            _ => DisplaySeconds::NoDisplay,
+                        };
+                        let session_only =
+                            keymap::is_session_only(&cfg, Action::ChangeSecondsDisplay);
+                        change(&mut cfg, seconds, session_only);
+                    } else if mouse::inside_ellipse(x, y, cx, cy, a, b) {
+                        let border = cfg.get::<ClockBorder>().next();
+                        let session_only = keymap::is_session_only(&cfg, Action::ChangeClockBorder);
+                        change(&mut cfg, border, session_only);
+                    }
+                }
+                Some(MouseEvent::ScrollUp) if a < cols / 2 - 1 => {
+                    let session_only = keymap::is_session_only(&cfg, Action::IncreaseClockWidth);
+                    let width = (a + 1 - round_a) as i64;
+                    sizes::set(&mut cfg, (cols, rows), "clock width", width, session_only);
+                }
+                Some(MouseEvent::ScrollDown) if a > 1 => {
+                    let session_only = keymap::is_session_only(&cfg, Action::DecreaseClockWidth);
+                    let width = (a - 1 - round_a) as i64;
+                    sizes::set(&mut cfg, (cols, rows), "clock width", width, session_only);
+                }
+                _ => {}
+            }
+        }
+        // The key ending a chord is taken by it, even when it completes none
+        let chording = keys.pending().is_some();
+        let action = unlocked.or_else(|| keys.press(ch));
+        let session_only = action.is_some_and(|action| keymap::is_session_only(&cfg, action));
+        match action {
+            Some(action) if unlocked.is_none() && kiosk::needs_pin(cli.kiosk, &cfg, action) => {
+                pin_prompt = Some(PinPrompt::new(action));
+            }
+            Some(Action::Quit)
+                if pomodoro.is_active() && cfg.get_bool("confirm quit during pomodoro") =>
+            {
+                confirm_quit = true;
+            }
+            Some(Action::Quit) => break,
+            Some(Action::OpenSettings) => {
+                input::bracketed_paste(false);
+                cfg.terminal_edit_json();
+                restore_ncurses_context(&cfg, pane_mode);
+                keys = Keymap::from_config(&cfg);
+                size_layout.invalidate();
+                renderer.invalidate();
+            }
+            Some(Action::ChangeSecondsDisplay) => {
+                let seconds = cfg.get::<DisplaySeconds>().next();
+                change(&mut cfg, seconds, session_only);
+            }
+            Some(Action::ChangeClockBorder) => {
+                let border = cfg.get::<ClockBorder>().next();
+                change(&mut cfg, border, session_only);
+            }
+            Some(Action::ChangeNumberDisplay) => {
+                let numbers = cfg.get::<Numbers>().next();
+                change(&mut cfg, numbers, session_only);
+            }
+            Some(Action::ToggleContinuousMinutes) => {
+                let continuous = !cfg.get_bool("continuous minutes");
+                if session_only {
+                    cfg.override_value("continuous minutes", &continuous.to_string());
+                } else {
+                    cfg.set_bool("continuous minutes", continuous);
+                }
+            }
+            Some(Action::ChangeCountdownDisplay) => {
+                let countdown = cfg.get::<CountdownDisplay>().next();
+                change(&mut cfg, countdown, session_only);
+            }
+            Some(Action::ToggleWorldClocks) => {
+                let mode = match cfg.get::<DisplayMode>() {
+                    DisplayMode::WorldClocks => DisplayMode::Analog,
+                    _ => DisplayMode::WorldClocks,
+                };
+                change(&mut cfg, mode, session_only);
+            }
+            Some(Action::ChangeDisplayMode) => {
+                let mode = cfg.get::<DisplayMode>().next();
+                change(&mut cfg, mode, session_only);
+            }
+            Some(Action::ManageAlarms) => {
+                input::bracketed_paste(false);
+                alarm::manage(&mut cfg);
+                input::bracketed_paste(true);
+                renderer.invalidate();
+            }
+            Some(Action::StartPausePomodoro) => pomodoro.start_pause(&cfg),
+            Some(Action::ResetPomodoro) => pomodoro.reset(&cfg),
+            Some(Action::ShowHelp) => show_help = true,
+            Some(Action::NextProfile) => match cfg.next_profile() {
+                Some(name) => {
+                    restore_ncurses_context(&cfg, pane_mode);
+                    keys = Keymap::from_config(&cfg);
+                    size_layout.invalidate();
+                    renderer.invalidate();
+                    toasts.show(format!("Profile \"{name}\""));
+                }
+                None => toasts.show("No other profile, make one in the settings"),
+            },
+            Some(Action::CompareNetworkTime) => {
+                ntp::show(&cfg);
+                renderer.invalidate();
+            }
+            Some(Action::CaptureFrame) => match capture::capture_frame(&cfg, &frame) {
+                Ok(path) => toasts.show(format!("Frame saved to {}", path.display())),
+                Err(e) => toasts.show(format!("Frame capture failed: {e}")),
+            },
+            // The dial stays on screen and at least one cell wide and high,
+            // counting from the size shown, which a too large offset doesn't change
+            Some(Action::IncreaseClockWidth) if a < cols / 2 - 1 => {
+                let width = (a + 1 - round_a) as i64;
+                sizes::set(&mut cfg, (cols, rows), "clock width", width, session_only);
+            }
+            Some(Action::DecreaseClockWidth) if a > 1 => {
+                let width = (a - 1 - round_a) as i64;
+                sizes::set(&mut cfg, (cols, rows), "clock width", width, session_only);
+            }
+            Some(Action::IncreaseClockHeight) if b < rows / 2 - 1 => {
+                let height = (b + 1 - max_b) as i64;
+                sizes::set(&mut cfg, (cols, rows), "clock height", height, session_only);
+            }
+            Some(Action::DecreaseClockHeight) if b > 1 => {
+                let height = (b - 1 - max_b) as i64;
+                sizes::set(&mut cfg, (cols, rows), "clock height", height, session_only);
+            }
+            Some(Action::MoveClock(dx, dy)) => {
+                let (x, y) = layout::keep_on_screen(cx + dx, cy + dy, cols, rows, a, b);
+                if (x, y) != (cx, cy) {
+                    let size = (cols, rows);
+                    let (column, row) = ((x - anchor_x) as i64, (y - anchor_y) as i64);
+                    sizes::set(&mut cfg, size, "clock column offset", column, session_only);
+                    sizes::set(&mut cfg, size, "clock row offset", row, session_only);
+                }
+            }
+            // Keys bound to no action may apply a preset
+            None if ch != ERR && !chording && keys.pending().is_none() => {
+                presets::apply_key(&mut cfg, ch, &mut toasts)
+            }
+            _ => {}
         }
     }
 
     /* ---------- clean up ---------- */
+    // Quitting ends a focus session, so its end command still runs
+    pomodoro.reset(&cfg);
+    input::bracketed_paste(false);
     endwin();
 }
@@ -0,0 +1,86 @@
+use std::f64::consts::PI;
+use std::fs;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use tac::framebuffer::{FrameBuffer, Style};
+use tac::geometry::polar_to_cartesian_ellipse;
+
+use crate::config_edit::Config;
+
+/// Color pairs of the minute track, lowest values first.
+pub const LEVEL_PAIRS: [i16; 5] = [19, 20, 21, 22, 23];
+
+/// One value per minute of the hour; `None` leaves that minute as the border draws it.
+type Series = Vec<Option<f64>>;
+
+/// Series pushed through the control socket or standard input, used instead of the file.
+static PUSHED: Mutex<Option<Series>> = Mutex::new(None);
+
+/// Series pushed through the control socket or standard input; `None`
+/// goes back to the "minute track file".
+pub fn push(series: Option<Series>) {
+    *PUSHED.lock().unwrap_or_else(|e| e.into_inner()) = series;
+}
+
+/// Up to 60 values separated by spaces, commas or new lines, such as a
+/// JSON array; "-" or anything else that is not a number leaves a minute out.
+pub fn parse(text: &str) -> Series {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .map(|word| word.trim_matches(|c| c == '[' || c == ']'))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.parse::<f64>().ok().filter(|v| v.is_finite()))
+        .take(60)
+        .collect()
+}
+
+/// The chart data, for the minute track and the radial chart: the pushed
+/// series, else the "minute track file", read again whenever it changes.
+pub fn series(cfg: &Config) -> Option<Series> {
+    static CACHE: Mutex<Option<(String, Option<SystemTime>, Series)>> = Mutex::new(None);
+    if let Some(series) = &*PUSHED.lock().unwrap_or_else(|e| e.into_inner()) {
+        return Some(series.clone());
+    }
+    let path = cfg.get_string("minute track file").unwrap_or_default();
+    if path.trim().is_empty() {
+        return None;
+    }
+    let expanded = shellexpand::tilde(path.trim()).into_owned();
+    let modified = fs::metadata(&expanded).and_then(|m| m.modified()).ok();
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let fresh = matches!(&*cache, Some((read, at, _)) if *read == path && *at == modified);
+    if !fresh {
+        let series = fs::read_to_string(&expanded)
+            .map(|text| parse(&text))
+            .unwrap_or_default();
+        *cache = Some((path, modified, series));
+    }
+    cache.as_ref().map(|(_, _, series)| series.clone())
+}
+
+/// Draw the minute track as a tiny radial chart: a mark on the rim at each
+/// minute with a value, colored from blue for the lowest value of the series
+/// to red for the highest. Hours get a star, like the "dot and hours" border.
+pub fn draw(frame: &mut FrameBuffer, cfg: &Config, cx: i32, cy: i32, a: i32, b: i32) {
+    let Some(series) = series(cfg) else {
+        return;
+    };
+    let values = series.iter().flatten();
+    let low = values.clone().copied().fold(f64::INFINITY, f64::min);
+    let high = values.copied().fold(f64::NEG_INFINITY, f64::max);
+    for (minute, value) in series.iter().enumerate() {
+        let Some(value) = value else {
+            continue;
+        };
+        // A flat series is all at the lowest level
+        let share = match high > low {
+            true => (value - low) / (high - low),
+            false => 0.0,
+        };
+        let level = ((share * LEVEL_PAIRS.len() as f64) as usize).min(LEVEL_PAIRS.len() - 1);
+        let (x, y) =
+            polar_to_cartesian_ellipse(cx, cy, 2.0 * PI * minute as f64 / 60.0, a as f64, b as f64);
+        let ch = if minute % 5 == 0 { '*' } else { '•' };
+        frame.put(x, y, ch, Style::pair(LEVEL_PAIRS[level]));
+    }
+}
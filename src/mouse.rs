@@ -0,0 +1,49 @@
+use ncurses::*;
+
+/// Mouse input the clock and the settings editor react to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseEvent {
+    /// Left button click on the cell (x,y).
+    Click(i32, i32),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Ask ncurses to report button presses and wheel events as `KEY_MOUSE`.
+pub fn enable() {
+    mousemask(ALL_MOUSE_EVENTS as mmask_t, None);
+}
+
+/// Decode the pending mouse event after `getch()` returned `KEY_MOUSE`.
+pub fn event() -> Option<MouseEvent> {
+    let mut event = MEVENT {
+        id: 0,
+        x: 0,
+        y: 0,
+        z: 0,
+        bstate: 0,
+    };
+    if getmouse(&mut event) != OK {
+        return None;
+    }
+    let state = event.bstate as i32;
+    if state & BUTTON4_PRESSED != 0 {
+        Some(MouseEvent::ScrollUp)
+    } else if state & BUTTON5_PRESSED != 0 {
+        Some(MouseEvent::ScrollDown)
+    } else if state & (BUTTON1_CLICKED | BUTTON1_PRESSED) != 0 {
+        Some(MouseEvent::Click(event.x, event.y))
+    } else {
+        None
+    }
+}
+
+/// Whether the cell (x,y) lies inside the ellipse centred at (cx,cy) with radii `a` and `b`.
+pub fn inside_ellipse(x: i32, y: i32, cx: i32, cy: i32, a: i32, b: i32) -> bool {
+    if a <= 0 || b <= 0 {
+        return false;
+    }
+    let dx = (x - cx) as f64 / a as f64;
+    let dy = (y - cy) as f64 / b as f64;
+    dx * dx + dy * dy <= 1.0
+}
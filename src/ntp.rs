@@ -0,0 +1,243 @@
+use chrono::{DateTime, Local, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use ncurses::*;
+use std::env;
+use std::fs;
+use std::net::UdpSocket;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::charset;
+use crate::config_edit::Config;
+use crate::time;
+
+/// Seconds from 1900, where NTP timestamps start, to 1970.
+const NTP_TO_UNIX: f64 = 2_208_988_800.0;
+
+/// How long a server has to answer.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// File systemd-timesyncd touches each time it sets the system clock.
+const TIMESYNCD_STAMP: &str = "/run/systemd/timesync/synchronized";
+
+/// What an NTP server said about its time.
+pub struct Sample {
+    /// Server time minus system time, in seconds
+    pub offset: f64,
+    /// Round trip to the server, in seconds
+    pub delay: f64,
+    /// Distance from the reference clock: 1 for a server with its own atomic or GPS clock
+    pub stratum: u8,
+    /// When the server last set its clock from its source
+    pub last_sync: Option<DateTime<Utc>>,
+}
+
+/// Seconds since 1970 of the 64-bit NTP timestamp at the start of `bytes`.
+/// Timestamps with the top bit clear are in the era starting in 2036.
+fn read_timestamp(bytes: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let era = if seconds & 0x8000_0000 == 0 {
+        1u64 << 32
+    } else {
+        0
+    };
+    (era + seconds as u64) as f64 + fraction as f64 / 4_294_967_296.0 - NTP_TO_UNIX
+}
+
+fn write_timestamp(bytes: &mut [u8], unix: f64) {
+    let ntp = unix + NTP_TO_UNIX;
+    // Wraps into the next era in 2036, as the protocol expects
+    let seconds = ntp.trunc() as u64 as u32;
+    let fraction = (ntp.fract() * 4_294_967_296.0) as u32;
+    bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+    bytes[4..8].copy_from_slice(&fraction.to_be_bytes());
+}
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64())
+}
+
+/// Ask `server` ("host" or "host:port") for its time with a single SNTP
+/// request, as described in RFC 4330.
+pub fn query(server: &str) -> Result<Sample, String> {
+    let address = match server.contains(':') {
+        true => server.to_string(),
+        false => format!("{server}:123"),
+    };
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    socket
+        .set_read_timeout(Some(TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    socket.connect(&address).map_err(|e| e.to_string())?;
+
+    // Version 4, client mode; the server copies our transmit time into its origin time
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    let sent = unix_now();
+    write_timestamp(&mut request[40..48], sent);
+    socket.send(&request).map_err(|e| e.to_string())?;
+
+    let mut reply = [0u8; 48];
+    let size = socket.recv(&mut reply).map_err(|e| match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => "no answer".to_string(),
+        _ => e.to_string(),
+    })?;
+    let received = unix_now();
+    if size < 48 || reply[0] & 0x07 != 4 || reply[24..32] != request[40..48] {
+        return Err("not an NTP answer".into());
+    }
+    let stratum = reply[1];
+    if stratum == 0 {
+        return Err("server refused the request".into());
+    }
+    let server_received = read_timestamp(&reply[32..40]);
+    let server_sent = read_timestamp(&reply[40..48]);
+    let reference = read_timestamp(&reply[16..24]);
+    Ok(Sample {
+        offset: ((server_received - sent) + (server_sent - received)) / 2.0,
+        delay: ((received - sent) - (server_sent - server_received)).max(0.0),
+        stratum,
+        last_sync: DateTime::from_timestamp(reference.trunc() as i64, 0)
+            .filter(|_| reference > 0.0),
+    })
+}
+
+/// When the system clock was last set by systemd-timesyncd, when it is the
+/// one keeping it in time.
+fn system_last_sync() -> Option<DateTime<Utc>> {
+    fs::metadata(TIMESYNCD_STAMP)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::from)
+}
+
+/// Zone of the system local time: $TZ when it names one, else the zone the
+/// system is set to.
+fn system_zone() -> Option<Tz> {
+    env::var("TZ")
+        .ok()
+        .and_then(|name| name.trim_start_matches(':').parse().ok())
+        .or_else(|| iana_time_zone::get_timezone().ok()?.parse().ok())
+}
+
+fn format_sync(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| {
+        t.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+    .unwrap_or_else(|| "unknown".into())
+}
+
+fn format_offset<Z: TimeZone>(now: &DateTime<Z>) -> String {
+    let seconds = now.offset().fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn table_row(source: &str, offset: &str, delay: &str, stratum: &str, sync: &str) -> String {
+    format!("{source:<24} {offset:>10} {delay:>11} {stratum:>8}  {sync}")
+}
+
+/// Page comparing the system clock with each of the "ntp servers", and the
+/// offset of the time zone according to the tz database built into the clock
+/// with the one the system gives. Servers are asked one after the other, each
+/// row filled in as its answer comes. Any key closes the page.
+pub fn show(cfg: &Config) {
+    nodelay(stdscr(), false);
+    erase();
+    let mut max_y = 0;
+    let mut max_x = 0;
+    getmaxyx(stdscr(), &mut max_y, &mut max_x);
+    mvaddstr(0, 0, "Network time");
+    mvaddstr(
+        1,
+        0,
+        "The system clock against the \"ntp servers\" and the time zone database.  Any key: back",
+    );
+    let header = table_row("source", "offset", "delay", "stratum", "last sync");
+    mvaddstr(3, 2, &header);
+    let system = table_row(
+        "system clock",
+        "-",
+        "-",
+        "-",
+        &format_sync(system_last_sync()),
+    );
+    mvaddstr(4, 2, &system);
+
+    let servers: Vec<String> = cfg
+        .get_list("ntp servers")
+        .iter()
+        .filter_map(|s| s.as_str().map(str::trim).map(String::from))
+        .filter(|s| !s.is_empty())
+        .collect();
+    // Room for the time zone lines under the table
+    let last_row = max_y - 5;
+    let mut row = 5;
+    if servers.is_empty() {
+        mvaddstr(row, 2, "No server in the \"ntp servers\" setting.");
+        row += 1;
+    }
+    for server in servers {
+        if row > last_row {
+            break;
+        }
+        mvaddstr(row, 2, &charset::text(&format!("{server:<24} asking…")));
+        refresh();
+        let line = match query(&server) {
+            Ok(sample) => table_row(
+                &server,
+                &format!("{:+.3} s", sample.offset),
+                &format!("{:.3} s", sample.delay),
+                &sample.stratum.to_string(),
+                &format_sync(sample.last_sync),
+            ),
+            Err(err) => format!("{server:<24} {err}"),
+        };
+        mv(row, 2);
+        clrtoeol();
+        mvaddstr(row, 2, &line);
+        row += 1;
+    }
+
+    // The system zone by both sources, which differ when one of them is out
+    // of date, then the configured zone
+    let utc = Utc::now();
+    let mut lines = vec![format!(
+        "tz database of the clock: {}",
+        chrono_tz::IANA_TZDB_VERSION
+    )];
+    let system = format_offset(&utc.with_timezone(&Local));
+    match system_zone() {
+        Some(tz) => {
+            let database = format_offset(&utc.with_timezone(&tz));
+            let verdict = match database == system {
+                true => "",
+                false => "  (one of them is out of date)",
+            };
+            lines.push(format!(
+                "system zone {}: {database} by the tz database, {system} by the system{verdict}",
+                tz.name()
+            ));
+        }
+        None => lines.push(format!("system zone: {system} by the system")),
+    }
+    if let Some(tz) = time::configured_timezone(cfg) {
+        let database = format_offset(&utc.with_timezone(&tz));
+        lines.push(format!(
+            "timezone {}: {database} by the tz database",
+            tz.name()
+        ));
+    }
+    for (row, line) in (row + 1..max_y - 1).zip(&lines) {
+        let line: String = line.chars().take((max_x - 2).max(0) as usize).collect();
+        mvaddstr(row, 2, &line);
+    }
+    refresh();
+    getch();
+    nodelay(stdscr(), true);
+}
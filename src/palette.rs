@@ -0,0 +1,211 @@
+use ncurses::{extended_pair_content, init_extended_pair, init_pair, COLORS};
+use std::env;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config_edit::Config;
+
+/// Terminals reporting this many colors take 24-bit RGB values as color numbers.
+const DIRECT_COLORS: i32 = 1 << 24;
+
+/// Names of the 8 basic colors, in ncurses order.
+pub const BASIC_NAMES: [&str; 8] = [
+    "BLACK", "RED", "GREEN", "YELLOW", "BLUE", "MAGENTA", "CYAN", "WHITE",
+];
+
+/// RGB of the 16 system colors, as xterm shows them.
+const SYSTEM_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of each component in the 6×6×6 color cube (indexes 16–231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// A color from the config: one of the 8 basic colors, an entry of the
+/// 256-color palette, or an RGB value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpec {
+    Basic(i16),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl ColorSpec {
+    /// Read a basic color name ("cyan"), a palette index ("208") or a hex
+    /// RGB string ("#ff8800").
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some(hex) = text.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            return Some(ColorSpec::Rgb(component(0)?, component(2)?, component(4)?));
+        }
+        if let Ok(index) = text.parse::<u8>() {
+            return Some(ColorSpec::Indexed(index));
+        }
+        BASIC_NAMES
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+            .map(|i| ColorSpec::Basic(i as i16))
+    }
+
+    /// RGB components, using the xterm palette for indexed colors.
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            ColorSpec::Basic(i) => SYSTEM_RGB[i.clamp(0, 7) as usize],
+            ColorSpec::Indexed(i @ 0..=15) => SYSTEM_RGB[i as usize],
+            ColorSpec::Indexed(i @ 16..=231) => {
+                let i = i - 16;
+                (
+                    CUBE_LEVELS[(i / 36) as usize],
+                    CUBE_LEVELS[(i / 6 % 6) as usize],
+                    CUBE_LEVELS[(i % 6) as usize],
+                )
+            }
+            ColorSpec::Indexed(i) => {
+                let level = 8 + 10 * (i - 232);
+                (level, level, level)
+            }
+            ColorSpec::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// Whether dark text reads better than light text on this color.
+    pub fn is_light(self) -> bool {
+        let (r, g, b) = self.rgb();
+        299 * r as u32 + 587 * g as u32 + 114 * b as u32 > 128_000
+    }
+
+    /// Color number to give ncurses on this terminal: the exact color when the
+    /// terminal has it, otherwise the nearest one it can show.
+    pub fn resolve(self) -> i32 {
+        let colors = COLORS();
+        match self {
+            ColorSpec::Basic(i) => i as i32,
+            // The first 8 colors stay indexed on direct color terminals
+            ColorSpec::Indexed(i) if i < 8 || (colors < DIRECT_COLORS && (i as i32) < colors) => {
+                i as i32
+            }
+            _ if colors >= DIRECT_COLORS => {
+                let (r, g, b) = self.rgb();
+                ((r as i32) << 16 | (g as i32) << 8 | b as i32).max(8)
+            }
+            // The system colors vary between terminals, the cube and gray ramp don't
+            _ if colors >= 256 => nearest(self.rgb(), 16..=255),
+            _ if colors >= 16 => nearest(self.rgb(), 0..=15),
+            _ => nearest(self.rgb(), 0..=7),
+        }
+    }
+}
+
+/// Color `t` of the way from `from` to `to`: `from` itself at 0 or less,
+/// `to` itself at 1 or more, and a mix of their RGB values in between.
+pub fn blend(from: ColorSpec, to: ColorSpec, t: f64) -> ColorSpec {
+    if t <= 0.0 {
+        return from;
+    }
+    if t >= 1.0 {
+        return to;
+    }
+    let ((r1, g1, b1), (r2, g2, b2)) = (from.rgb(), to.rgb());
+    let mix = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    ColorSpec::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    let (dr, dg, db) = (
+        r1 as i32 - r2 as i32,
+        g1 as i32 - g2 as i32,
+        b1 as i32 - b2 as i32,
+    );
+    dr * dr + dg * dg + db * db
+}
+
+/// Palette index in `indexes` closest to `rgb`.
+fn nearest(rgb: (u8, u8, u8), indexes: RangeInclusive<u8>) -> i32 {
+    indexes
+        .min_by_key(|&i| distance(rgb, ColorSpec::Indexed(i).rgb()))
+        .unwrap_or(0) as i32
+}
+
+/// Whether every pair is drawn in the default colors of the terminal (`--no-color`).
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Draw every pair defined from now on in the default colors of the terminal.
+pub fn disable_colors() {
+    NO_COLOR.store(true, Ordering::SeqCst);
+}
+
+/// Whether attributes stand for the colors, every pair being drawn in the
+/// default colors of the terminal.
+static MONOCHROME: AtomicBool = AtomicBool::new(false);
+
+/// Follow the "monochrome" setting, on whatever it says when the `NO_COLOR`
+/// environment variable is set and not empty (see no-color.org).
+pub fn configure(cfg: &Config) {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    MONOCHROME.store(no_color || cfg.get_bool("monochrome"), Ordering::SeqCst);
+}
+
+/// Whether the screen is drawn with attributes instead of colors.
+pub fn is_monochrome() -> bool {
+    MONOCHROME.load(Ordering::SeqCst)
+}
+
+/// Define color pair `pair`; -1 is the terminal default color. Colors beyond
+/// the 16-bit range of `init_pair` (direct RGB) need the extended call.
+pub fn set_pair(pair: i16, foreground: i32, background: i32) {
+    let plain = NO_COLOR.load(Ordering::SeqCst) || is_monochrome();
+    let (foreground, background) = match plain {
+        true => (-1, -1),
+        false => (foreground, background),
+    };
+    if COLORS() > 256 {
+        init_extended_pair(pair as i32, foreground, background);
+    } else {
+        init_pair(pair, foreground as i16, background as i16);
+    }
+}
+
+/// Foreground and background color numbers of `pair`.
+pub fn pair_colors(pair: i16) -> (i32, i32) {
+    let (mut foreground, mut background) = (0, 0);
+    extended_pair_content(pair as i32, &mut foreground, &mut background);
+    (foreground, background)
+}
+
+/// SGR parameters selecting color number `color` as foreground (`base` 30)
+/// or background (`base` 40).
+pub fn sgr(color: i32, base: i32) -> String {
+    match color {
+        c if c < 0 => format!("{}", base + 9),
+        c if c < 8 => format!("{}", base + c),
+        c if COLORS() >= DIRECT_COLORS => {
+            format!(
+                "{};2;{};{};{}",
+                base + 8,
+                c >> 16,
+                (c >> 8) & 0xff,
+                c & 0xff
+            )
+        }
+        c => format!("{};5;{}", base + 8, c),
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, FixedOffset};
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::choices::DigitalDisplay;
+use crate::complications::DIGITAL_PAIR;
+use crate::config_edit::Config;
+
+/// Terminals smaller than this switch to pane mode on their own.
+const MIN_COLS: i32 = 30;
+const MIN_ROWS: i32 = 12;
+
+/// Screens smaller than this get the time in digits instead of a face: the
+/// dial would be a radius of one cell or less.
+const DIAL_COLS: i32 = 12;
+const DIAL_ROWS: i32 = 6;
+
+/// Session overrides of pane mode: a minimal face with no readouts.
+const PANE_SETTINGS: &[(&str, &str)] = &[
+    ("clock border", "hours"),
+    ("numbers", "no numbers"),
+    ("display seconds", "no display"),
+    ("digital display", "off"),
+    ("date display", "off"),
+    ("countdown display", "off"),
+    ("zone caption", "false"),
+];
+
+/// Pane mode is for tiny embedded panes (zellij, wezterm, tmux splits):
+/// requested with `--pane`, or automatic when the terminal is small.
+pub fn wanted(requested: bool, rows: i32, cols: i32) -> bool {
+    requested || cols < MIN_COLS || rows < MIN_ROWS
+}
+
+/// Switch the pane mode overrides on or off. They only live for the session,
+/// the config file keeps the user's settings; the other session overrides
+/// stay.
+pub fn apply(cfg: &mut Config, on: bool) {
+    for (key, value) in PANE_SETTINGS {
+        if on {
+            cfg.override_value(key, value);
+        } else {
+            cfg.drop_override(key);
+        }
+    }
+}
+
+/// Whether a `cols`×`rows` screen is too small for any face.
+pub fn too_small(cols: i32, rows: i32) -> bool {
+    cols < DIAL_COLS || rows < DIAL_ROWS
+}
+
+/// Fallback of the screens too small for a face: the time on the middle row,
+/// without the seconds when they don't fit, and under it the size a face
+/// needs when there is room for it.
+pub fn draw_compact(frame: &mut FrameBuffer, cfg: &Config, now: &DateTime<FixedOffset>) {
+    let (cols, rows) = (frame.width(), frame.height());
+    let formats = match cfg.get::<DigitalDisplay>() {
+        DigitalDisplay::TwelveHours => ["%I:%M:%S %p", "%I:%M %p", "%I:%M"],
+        _ => ["%H:%M:%S", "%H:%M", "%H:%M"],
+    };
+    let time = formats
+        .iter()
+        .map(|format| now.format(format).to_string())
+        .find(|time| text_width(time) <= cols)
+        .unwrap_or_else(|| now.format(formats[2]).to_string());
+    let y = (rows - 1) / 2;
+    let style = Style::pair(DIGITAL_PAIR);
+    frame.put_str((cols - text_width(&time)).max(0) / 2, y, &time, style);
+
+    let notice = [
+        format!("terminal too small: need {DIAL_COLS}x{DIAL_ROWS}"),
+        format!("need {DIAL_COLS}x{DIAL_ROWS}"),
+    ]
+    .into_iter()
+    .find(|notice| text_width(notice) <= cols);
+    if let Some(notice) = notice.filter(|_| y + 1 < rows) {
+        frame.put_str((cols - text_width(&notice)) / 2, y + 1, &notice, style);
+    }
+}
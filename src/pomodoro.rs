@@ -0,0 +1,262 @@
+use chrono::{DateTime, FixedOffset, Timelike};
+use ncurses::beep;
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::{Attributes, FrameBuffer, Style};
+
+use crate::config_edit::Config;
+use crate::history;
+use crate::hooks;
+use crate::layout::{Layout, Slot};
+use crate::toast::Toasts;
+
+/// Color pairs of the remaining-time arc.
+pub const WORK_PAIR: i16 = 9;
+pub const BREAK_PAIR: i16 = 10;
+
+/// How long the dial flashes after a phase change.
+const TRANSITION_FLASH: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    Break,
+}
+
+impl Phase {
+    fn length(self, cfg: &Config) -> Duration {
+        let minutes = match self {
+            Phase::Work => cfg.get_int("pomodoro work minutes"),
+            Phase::Break => cfg.get_int("pomodoro break minutes"),
+        };
+        Duration::from_secs(minutes.clamp(1, 24 * 60) as u64 * 60)
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Phase::Work => Phase::Break,
+            Phase::Break => Phase::Work,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Work => "work",
+            Phase::Break => "break",
+        }
+    }
+
+    fn pair(self) -> i16 {
+        match self {
+            Phase::Work => WORK_PAIR,
+            Phase::Break => BREAK_PAIR,
+        }
+    }
+}
+
+enum State {
+    Idle,
+    /// `done` is the time spent in the phase before `since`.
+    Running {
+        phase: Phase,
+        since: Instant,
+        done: Duration,
+    },
+    Paused {
+        phase: Phase,
+        done: Duration,
+    },
+}
+
+/// Pomodoro timer alternating work and break phases. Time is measured with a
+/// monotonic clock, so changing the time zone or the system clock doesn't
+/// affect a running phase.
+pub struct Pomodoro {
+    state: State,
+    transition: Option<Instant>,
+}
+
+impl Default for Pomodoro {
+    fn default() -> Self {
+        Self {
+            state: State::Idle,
+            transition: None,
+        }
+    }
+}
+
+impl Pomodoro {
+    /// Start a work phase, or pause / resume the current phase.
+    pub fn start_pause(&mut self, cfg: &Config) {
+        self.state = match self.state {
+            State::Idle => {
+                focus_started(cfg);
+                State::Running {
+                    phase: Phase::Work,
+                    since: Instant::now(),
+                    done: Duration::ZERO,
+                }
+            }
+            State::Running { phase, since, done } => State::Paused {
+                phase,
+                done: done + since.elapsed(),
+            },
+            State::Paused { phase, done } => State::Running {
+                phase,
+                since: Instant::now(),
+                done,
+            },
+        };
+    }
+
+    /// Start a work phase unless a session is under way.
+    pub fn start(&mut self, cfg: &Config) {
+        if !self.is_active() {
+            self.start_pause(cfg);
+        }
+    }
+
+    /// Stop the session, ending the focus if it was in a work phase.
+    pub fn reset(&mut self, cfg: &Config) {
+        if let Some((Phase::Work, _, _)) = self.progress() {
+            focus_ended(cfg);
+        }
+        self.state = State::Idle;
+        self.transition = None;
+    }
+
+    /// Whether a session is under way, running or paused.
+    pub fn is_active(&self) -> bool {
+        !matches!(self.state, State::Idle)
+    }
+
+    /// Current phase, time spent in it, and whether it is paused.
+    fn progress(&self) -> Option<(Phase, Duration, bool)> {
+        match self.state {
+            State::Idle => None,
+            State::Running { phase, since, done } => Some((phase, done + since.elapsed(), false)),
+            State::Paused { phase, done } => Some((phase, done, true)),
+        }
+    }
+
+    /// Switch phase when the current one is over, with a bell.
+    pub fn tick(&mut self, cfg: &Config, toasts: &mut Toasts) {
+        if let State::Running { phase, since, done } = self.state {
+            let length = phase.length(cfg);
+            let spent = done + since.elapsed();
+            if spent >= length {
+                self.state = State::Running {
+                    phase: phase.other(),
+                    since: Instant::now(),
+                    done: spent - length,
+                };
+                self.transition = Some(Instant::now());
+                toasts.show(format!("Pomodoro: {} time", phase.other().name()));
+                history::record(cfg, "pomodoro", &format!("{} ended", phase.name()));
+                hooks::post_webhook(
+                    cfg,
+                    serde_json::json!({
+                        "event": "pomodoro",
+                        "finished": phase.name(),
+                        "next": phase.other().name(),
+                    }),
+                );
+                match phase {
+                    Phase::Work => focus_ended(cfg),
+                    Phase::Break => focus_started(cfg),
+                }
+                beep();
+            }
+        }
+    }
+
+    /// Whether the dial is flashing after a phase change.
+    pub fn in_transition(&self) -> bool {
+        self.transition
+            .is_some_and(|t| t.elapsed() < TRANSITION_FLASH)
+    }
+
+    /// Attributes to apply to the dial: a short flash after a phase change.
+    pub fn attributes(&self) -> Attributes {
+        match self.transition {
+            // Blink twice a second
+            Some(t)
+                if t.elapsed() < TRANSITION_FLASH && (t.elapsed().as_millis() / 500) % 2 == 0 =>
+            {
+                Attributes::REVERSE
+            }
+            _ => Attributes::NONE,
+        }
+    }
+
+    /// Draw the remaining time of the phase as an arc over the rim, from the
+    /// minute hand to where it will be at the end of the phase.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &self,
+        frame: &mut FrameBuffer,
+        cfg: &Config,
+        now: &DateTime<FixedOffset>,
+        cx: i32,
+        cy: i32,
+        a: i32,
+        b: i32,
+    ) {
+        let (phase, spent, _) = match self.progress() {
+            Some(progress) => progress,
+            None => return,
+        };
+        let remaining = phase.length(cfg).saturating_sub(spent);
+
+        // Minutes on the dial, a full turn is one hour
+        let minutes_now = now.minute() as f64 + now.second() as f64 / 60.0;
+        let start = 2.0 * PI * minutes_now / 60.0;
+        let turns = (remaining.as_secs_f64() / 3600.0).min(1.0);
+        let end = start + 2.0 * PI * turns;
+
+        frame.draw_arc(
+            cx,
+            cy,
+            a as f64,
+            b as f64,
+            start,
+            end,
+            '#',
+            Style::pair(phase.pair()),
+        );
+    }
+
+    /// Place a "work 12:34" readout above the dial, under the countdown.
+    pub fn add_readout(&self, layout: &mut Layout, cfg: &Config) {
+        let Some((phase, spent, paused)) = self.progress() else {
+            return;
+        };
+        let seconds = phase.length(cfg).saturating_sub(spent).as_secs();
+        let mut text = format!("{} {}:{:02}", phase.name(), seconds / 60, seconds % 60);
+        if paused {
+            text.push_str(" (paused)");
+        }
+        layout.add(Slot::North, 10, text, Style::pair(phase.pair()));
+    }
+}
+
+/// Run the "on focus start" command as a work phase begins, e.g. to turn on
+/// do-not-disturb or set a chat status.
+fn focus_started(cfg: &Config) {
+    let minutes = Phase::Work.length(cfg).as_secs() / 60;
+    hooks::run_command(
+        &cfg.get_string("on focus start").unwrap_or_default(),
+        "on_focus_start",
+        &[("TAC_FOCUS_MINUTES", minutes.to_string())],
+    );
+}
+
+/// Run the "on focus end" command as a work phase ends or is abandoned.
+fn focus_ended(cfg: &Config) {
+    hooks::run_command(
+        &cfg.get_string("on focus end").unwrap_or_default(),
+        "on_focus_end",
+        &[],
+    );
+}
@@ -0,0 +1,81 @@
+use crate::config_edit::Config;
+use crate::toast::Toasts;
+
+/// A bundle of settings applied at once with a key, from the "presets" list.
+pub struct Preset {
+    pub key: char,
+    pub name: String,
+    /// Keys and values, the values written as in the editor
+    pub settings: Vec<(String, String)>,
+}
+
+/// Read the "presets" list. Items are objects
+/// `{"key": "1", "name": "minimal", "settings": {"clock border": "hours", ...}}`;
+/// without a key the n-th item is bound to the digit n, and values may be
+/// JSON strings, numbers or booleans.
+pub fn presets(cfg: &Config) -> Vec<Preset> {
+    cfg.get_list("presets")
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let key = match item.get("key").and_then(|k| k.as_str()) {
+                Some(key) => key.chars().next()?,
+                None => char::from_digit(i as u32 + 1, 10)?,
+            };
+            let name = item
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map_or_else(|| format!("preset {key}"), str::to_string);
+            let settings = item
+                .get("settings")?
+                .as_object()?
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    };
+                    (key.clone(), value)
+                })
+                .collect();
+            Some(Preset {
+                key,
+                name,
+                settings,
+            })
+        })
+        .collect()
+}
+
+/// Apply the preset bound to the key code `ch`, if any, and confirm it with a
+/// toast.
+pub fn apply_key(cfg: &mut Config, ch: i32, toasts: &mut Toasts) {
+    if let Some(preset) = presets(cfg)
+        .into_iter()
+        .find(|p| u32::try_from(ch).ok() == Some(p.key as u32))
+    {
+        apply(cfg, &preset, toasts);
+    }
+}
+
+/// Apply the preset called `name`, for an alarm, or tell there is none.
+pub fn apply_named(cfg: &mut Config, name: &str, toasts: &mut Toasts) {
+    match presets(cfg).into_iter().find(|p| p.name == name.trim()) {
+        Some(preset) => apply(cfg, &preset, toasts),
+        None => toasts.show(format!("No preset \"{}\"", name.trim())),
+    }
+}
+
+/// Apply the settings of `preset` as session overrides, like `--theme`: the
+/// saved settings stay.
+fn apply(cfg: &mut Config, preset: &Preset, toasts: &mut Toasts) {
+    let count = preset
+        .settings
+        .iter()
+        .filter(|(key, value)| cfg.override_value(key, value).is_some())
+        .count();
+    toasts.show(format!(
+        "Preset \"{}\": {count} settings applied",
+        preset.name
+    ));
+}
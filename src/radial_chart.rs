@@ -0,0 +1,114 @@
+use chrono::Timelike;
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::thread;
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+use tac::geometry::polar_to_cartesian_ellipse;
+
+use crate::border::BorderStyle;
+use crate::config_edit::Config;
+use crate::dial::{self, BORDER_PAIR, DIGITS_PAIR};
+use crate::minute_track::{self, LEVEL_PAIRS};
+
+/// Take the chart data from standard input when it is piped, e.g.
+/// `sensors-to-json | tac`: each line is a series, such as a JSON array of
+/// numbers, replacing the previous one. With `wait` (for `--print`) the
+/// whole input is read before going on; otherwise a thread reads it while
+/// the clock runs, and the keyboard is read from the terminal instead.
+pub fn read_stdin(wait: bool) {
+    // SAFETY: isatty only looks at the descriptor
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+        return;
+    }
+    if wait {
+        io::stdin()
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .for_each(|line| push_line(&line));
+        return;
+    }
+    let Ok(tty) = File::open("/dev/tty") else {
+        return;
+    };
+    // SAFETY: plain descriptor calls; the pipe moves to a new descriptor owned
+    // by `pipe` below, and stdin becomes a copy of the terminal for ncurses
+    let data = unsafe { libc::dup(libc::STDIN_FILENO) };
+    if data < 0 || unsafe { libc::dup2(tty.as_raw_fd(), libc::STDIN_FILENO) } < 0 {
+        return;
+    }
+    let pipe = unsafe { File::from_raw_fd(data) };
+    thread::spawn(move || {
+        BufReader::new(pipe)
+            .lines()
+            .map_while(Result::ok)
+            .for_each(|line| push_line(&line));
+    });
+}
+
+fn push_line(line: &str) {
+    if !line.trim().is_empty() {
+        minute_track::push(Some(minute_track::parse(line)));
+    }
+}
+
+/// Buckets around the dial for a series of `len` values: hours, hours of a
+/// day or minutes.
+fn buckets(len: usize) -> usize {
+    match len {
+        0..=12 => 12,
+        13..=24 => 24,
+        _ => 60,
+    }
+}
+
+/// The "radial chart" display mode: the chart data (as for the minute track)
+/// drawn as one spoke per value, as long as the value over the largest one
+/// and colored like the minute track, under the hands of the clock. The
+/// series fills 12, 24 or 60 buckets clockwise from 12 o'clock depending on
+/// its length, and the largest value is written under the centre.
+pub fn draw<T: Timelike>(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    now: &T,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+) {
+    BorderStyle::from_config(cfg, Style::pair(BORDER_PAIR)).draw(frame, cx, cy, a, b);
+    let series = minute_track::series(cfg).unwrap_or_default();
+    let count = buckets(series.len());
+    let high = series.iter().flatten().copied().fold(0.0, f64::max);
+    for (i, value) in series.iter().enumerate() {
+        let Some(value) = value.filter(|&v| v > 0.0 && high > 0.0) else {
+            continue;
+        };
+        let share = value / high;
+        let level = ((share * LEVEL_PAIRS.len() as f64) as usize).min(LEVEL_PAIRS.len() - 1);
+        let (x, y) = polar_to_cartesian_ellipse(
+            cx,
+            cy,
+            2.0 * PI * i as f64 / count as f64,
+            (a - 1) as f64 * share,
+            (b - 1) as f64 * share,
+        );
+        frame.draw_line(cx, cy, x, y, "█", Style::pair(LEVEL_PAIRS[level]));
+    }
+    let caption = match series.is_empty() {
+        true => "no data".to_string(),
+        false => format!("max {high}"),
+    };
+    frame.put_str(
+        cx - text_width(&caption) / 2,
+        cy + (b / 2).max(1),
+        &caption,
+        Style::pair(DIGITS_PAIR),
+    );
+    for hand in dial::hands(cfg, now, cx, cy, a, b) {
+        frame.draw_path(&hand.points(), &hand.pattern, Style::pair(hand.pair));
+    }
+}
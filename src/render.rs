@@ -0,0 +1,129 @@
+use ncurses::*;
+
+use tac::framebuffer::{Attributes, Cell, FrameBuffer, Style};
+
+use crate::charset;
+use crate::palette;
+use crate::{debug_render, dial, heat_map, minute_track, pomodoro};
+
+/// Puts frames on the ncurses screen, remembering the last one.
+///
+/// Only the cells that differ from the previous frame are written, so
+/// refresh() has little to send: the cells around the moving hands and
+/// readouts, not the whole dial.
+#[derive(Default)]
+pub struct Renderer {
+    previous: Option<FrameBuffer>,
+}
+
+/// Attributes standing for the color of `pair` in monochrome mode: bold
+/// hands, a dim border and face, plain digits and readouts.
+fn stand_in(pair: i16) -> Attributes {
+    match pair {
+        dial::HOURS_PAIR | dial::MINUTES_PAIR | dial::PROGRESS_PAIR => Attributes::BOLD,
+        dial::BORDER_PAIR | dial::FACE_PAIR => Attributes::DIM,
+        pomodoro::WORK_PAIR | debug_render::ANALYTIC_PAIR => Attributes::BOLD,
+        pomodoro::BREAK_PAIR | heat_map::COOL_PAIR => Attributes::DIM,
+        heat_map::HOT_PAIR | debug_render::PLOTTED_PAIR => Attributes::REVERSE,
+        _ => match minute_track::LEVEL_PAIRS.iter().position(|&p| p == pair) {
+            Some(0 | 1) => Attributes::DIM,
+            Some(3 | 4) => Attributes::BOLD,
+            _ => Attributes::NONE,
+        },
+    }
+}
+
+/// ncurses attributes of a cell style: its color pair, or in monochrome mode
+/// the attributes standing for it.
+fn attributes(style: Style) -> attr_t {
+    let monochrome = palette::is_monochrome();
+    let wanted = match monochrome {
+        true => style.attributes | stand_in(style.pair),
+        false => style.attributes,
+    };
+    let mut attributes = 0;
+    for (attribute, curses) in [
+        (Attributes::BOLD, A_BOLD()),
+        (Attributes::DIM, A_DIM()),
+        (Attributes::REVERSE, A_REVERSE()),
+        (Attributes::BLINK, A_BLINK()),
+    ] {
+        if wanted.contains(attribute) {
+            attributes |= curses;
+        }
+    }
+    if !monochrome && style.pair != 0 && has_colors() {
+        attributes |= COLOR_PAIR(style.pair);
+    }
+    attributes
+}
+
+/// Write a cell; characters beyond ASCII go through `mvaddstr`, which also
+/// takes care of the two columns of a wide character. With the ASCII
+/// character set, the right half of a wide character becomes a blank.
+fn write_cell(x: i32, y: i32, cell: &Cell) {
+    let attributes = attributes(cell.style);
+    let ch = match cell.is_continuation() {
+        true => ' ',
+        false => charset::glyph(cell.ch),
+    };
+    if ch.is_ascii() {
+        mvaddch(y, x, ch as chtype | attributes as chtype);
+    } else {
+        attron(attributes);
+        mvaddstr(y, x, ch.encode_utf8(&mut [0; 4]));
+        attroff(attributes);
+    }
+}
+
+/// Write `frame` with its top left corner at (left,top), over whatever screen
+/// is showing, coloring with `pair(p)` the cells the frame colors with pair `p`.
+pub fn draw_at(frame: &FrameBuffer, left: i32, top: i32, pair: impl Fn(i16) -> i16) {
+    for y in 0..frame.height() {
+        for (x, cell) in frame.row(y).iter().enumerate() {
+            if !cell.is_continuation() || charset::is_ascii() {
+                // Monochrome mode tells the pairs apart, not their colors
+                let style = match palette::is_monochrome() {
+                    true => cell.style,
+                    false => Style {
+                        pair: pair(cell.style.pair),
+                        ..cell.style
+                    },
+                };
+                write_cell(left + x as i32, top + y, &Cell { style, ..*cell });
+            }
+        }
+    }
+}
+
+impl Renderer {
+    /// Send `frame` to the terminal, writing only the cells that changed.
+    pub fn present(&mut self, frame: &FrameBuffer) {
+        let previous = self
+            .previous
+            .take()
+            .filter(|p| p.width() == frame.width() && p.height() == frame.height());
+        if previous.is_none() {
+            erase();
+        }
+        for y in 0..frame.height() {
+            let old = previous.as_ref().map(|p| p.row(y));
+            for (x, cell) in frame.row(y).iter().enumerate() {
+                // The right half of a wide character comes with its left half,
+                // unless it is replaced by a single column
+                let written = !cell.is_continuation() || charset::is_ascii();
+                if written && old.map(|row| row[x]) != Some(*cell) {
+                    write_cell(x as i32, y, cell);
+                }
+            }
+        }
+        refresh();
+        self.previous = Some(frame.clone());
+    }
+
+    /// Forget the previous frame, e.g. after another screen (settings
+    /// editor, alarm list) has drawn over the terminal.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+}
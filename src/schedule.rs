@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+
+/// Frame period of the continuous second hand (≈33 fps).
+const SMOOTH_FRAME_MS: u32 = 30;
+
+/// Frame period while something blinks faster than once a second.
+const ANIMATION_FRAME_MS: u32 = 100;
+
+/// How often the screen has to change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pace {
+    /// Nothing moves between two seconds.
+    EachSecond,
+    /// A blink or bell sequence not aligned on seconds is running.
+    Animation,
+    /// The second hand sweeps continuously.
+    Smooth,
+}
+
+/// Milliseconds from `now` to the next frame: the next multiple of the frame
+/// period within the second, and never later than the next whole second, so
+/// that the hands and readouts change right on the tick. `max_fps` makes the
+/// period longer when it is too short.
+pub fn delay_until_next_frame(now: DateTime<Utc>, pace: Pace, max_fps: Option<u32>) -> i32 {
+    let period = match pace {
+        Pace::EachSecond => 1000,
+        Pace::Animation => ANIMATION_FRAME_MS,
+        Pace::Smooth => SMOOTH_FRAME_MS,
+    };
+    let period = max_fps.map_or(period, |fps| period.max(1000 / fps.max(1)));
+    // Leap seconds are reported as 1000 ms and more
+    let millis = now.timestamp_subsec_millis() % 1000;
+    let to_next_period = period - millis % period;
+    to_next_period.min(1000 - millis) as i32
+}
@@ -0,0 +1,82 @@
+//! Leaving the terminal as it was found when the clock stops some other way
+//! than its quit key. Signals only raise flags, polled between frames and by
+//! the screens waiting for a key, so that the terminal is restored by the
+//! usual code rather than from a signal handler; a panic restores it from
+//! its hook before the message is printed.
+
+use ncurses::{curs_set, endwin, refresh, CURSOR_VISIBILITY};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::input;
+
+/// SIGINT, SIGTERM or SIGHUP came: the clock should quit.
+static QUIT: AtomicBool = AtomicBool::new(false);
+/// SIGTSTP (Ctrl-Z) came: the clock should stop until continued.
+static SUSPEND: AtomicBool = AtomicBool::new(false);
+/// SIGCONT came: what the terminal shows may no longer be the clock.
+static RESUMED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn note(signal: libc::c_int) {
+    let flag = match signal {
+        libc::SIGTSTP => &SUSPEND,
+        libc::SIGCONT => &RESUMED,
+        _ => &QUIT,
+    };
+    flag.store(true, Ordering::SeqCst);
+}
+
+/// Catch the signals that stop or pause the clock, in place of the handlers
+/// of ncurses, and restore the terminal on a panic. Called once ncurses has
+/// started.
+pub fn install() {
+    for signal in [
+        libc::SIGINT,
+        libc::SIGTERM,
+        libc::SIGHUP,
+        libc::SIGTSTP,
+        libc::SIGCONT,
+    ] {
+        // No SA_RESTART: a signal interrupts the wait for a key
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = note as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(signal, &action, std::ptr::null_mut());
+        }
+    }
+    let print_panic = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        input::bracketed_paste(false);
+        curs_set(CURSOR_VISIBILITY::CURSOR_VISIBLE);
+        endwin();
+        print_panic(info);
+    }));
+}
+
+/// Whether a signal asked the clock to quit.
+pub fn quit_requested() -> bool {
+    QUIT.load(Ordering::SeqCst)
+}
+
+/// If Ctrl-Z was pressed, give the terminal back to the shell and stop until
+/// continued, then take the screen again. Returns whether it did, for the
+/// caller to restore what `endwin` undid (bracketed paste, the mouse).
+pub fn suspend_if_asked() -> bool {
+    if !SUSPEND.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+    input::bracketed_paste(false);
+    endwin();
+    unsafe {
+        libc::raise(libc::SIGSTOP);
+    }
+    refresh();
+    true
+}
+
+/// Whether the clock was continued since the last call: stopped by another
+/// program, the screen must be drawn again from scratch.
+pub fn take_resumed() -> bool {
+    RESUMED.swap(false, Ordering::SeqCst)
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::config_edit::Config;
+
+/// Settings of the size and place of the dial that can be kept per terminal
+/// size.
+const KEYS: [&str; 4] = [
+    "clock width",
+    "clock height",
+    "clock column offset",
+    "clock row offset",
+];
+
+/// One item of the "terminal sizes" config list: the adjustments made on a
+/// terminal of one size, e.g. `{"size": "200x50", "clock width": 6}`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct SizeTweaks {
+    /// Columns and rows, "COLSxROWS"
+    size: String,
+    /// Values of [`KEYS`] adjusted at this size
+    #[serde(flatten)]
+    settings: BTreeMap<String, i64>,
+}
+
+fn size_name(cols: i32, rows: i32) -> String {
+    format!("{cols}x{rows}")
+}
+
+/// Read the "terminal sizes" list; items that do not parse are skipped.
+fn tweaks(cfg: &Config) -> Vec<SizeTweaks> {
+    cfg.get_list("terminal sizes")
+        .into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()
+}
+
+/// Applies the adjustments made at the size of the terminal.
+#[derive(Default)]
+pub struct SizeLayout {
+    /// Columns and rows they were last applied at
+    size: Option<(i32, i32)>,
+}
+
+impl SizeLayout {
+    /// When the terminal is new or resized to `cols`×`rows`, use the
+    /// adjustments made at that size as session overrides, the saved settings
+    /// for the keys adjusted at other sizes only. Changes made for the session
+    /// only then last until the next resize.
+    pub fn apply(&mut self, cfg: &mut Config, cols: i32, rows: i32) {
+        if self.size == Some((cols, rows)) {
+            return;
+        }
+        self.size = Some((cols, rows));
+        if !cfg.get_bool("layout per terminal size") {
+            return;
+        }
+        let size = size_name(cols, rows);
+        let settings = tweaks(cfg)
+            .into_iter()
+            .find(|tweaks| tweaks.size == size)
+            .map(|tweaks| tweaks.settings)
+            .unwrap_or_default();
+        for key in KEYS {
+            match settings.get(key) {
+                Some(value) => {
+                    cfg.override_value(key, &value.to_string());
+                }
+                None => cfg.drop_override(key),
+            }
+        }
+    }
+
+    /// Apply again on the next frame, once the settings have changed.
+    pub fn invalidate(&mut self) {
+        self.size = None;
+    }
+}
+
+/// Set `key`, one of [`KEYS`], adjusted from the keyboard or the mouse: for
+/// this session only when `session_only`, else for a `cols`×`rows` terminal
+/// only with "layout per terminal size" on, for every size otherwise.
+pub fn set(cfg: &mut Config, (cols, rows): (i32, i32), key: &str, value: i64, session_only: bool) {
+    if session_only {
+        cfg.override_value(key, &value.to_string());
+        return;
+    }
+    if !cfg.get_bool("layout per terminal size") {
+        cfg.set_int(key, value);
+        return;
+    }
+    let size = size_name(cols, rows);
+    let mut list = tweaks(cfg);
+    let index = match list.iter().position(|tweaks| tweaks.size == size) {
+        Some(index) => index,
+        None => {
+            list.push(SizeTweaks {
+                size,
+                ..SizeTweaks::default()
+            });
+            list.len() - 1
+        }
+    };
+    list[index].settings.insert(key.to_string(), value);
+    let items = list
+        .iter()
+        .filter_map(|tweaks| serde_json::to_value(tweaks).ok())
+        .collect();
+    cfg.set_list("terminal sizes", items);
+    cfg.override_value(key, &value.to_string());
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+use chrono_tz::Tz;
+use std::f64::consts::PI;
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+use tac::geometry::{ellipse_points, polar_to_cartesian_ellipse};
+
+use crate::choices::{DateWindow, DisplaySeconds, NineOClockSubDial};
+use crate::config_edit::Config;
+use crate::dial::{BORDER_PAIR, DIGITS_PAIR, HOURS_PAIR, MINUTES_PAIR, SECONDS_PAIR};
+
+/// Initials of the days, Monday at 12 o'clock of the day of week sub-dial.
+const DAYS: [&str; 7] = ["M", "T", "W", "T", "F", "S", "S"];
+
+/// A hand of a sub-dial: part of the turn done, length relative to the
+/// radius, and color pair.
+struct SubHand {
+    turn: f64,
+    length: f64,
+    pair: i16,
+}
+
+/// Draw the sub-dials enabled in the config inside the dial centred at
+/// (cx,cy) with radii `a` and `b`: seconds at 6 o'clock, 24 hours or a
+/// second time zone at 9 o'clock and the day of the week at 3 o'clock.
+pub fn draw<Z: TimeZone>(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    now: &DateTime<Z>,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+) {
+    // A quarter of the dial
+    let (sa, sb) = (a / 4, b / 4);
+    if !fits(b) {
+        return;
+    }
+    if cfg.get_bool("seconds sub-dial") {
+        let mut second = now.second() as f64;
+        if cfg.get::<DisplaySeconds>().is_continuous() {
+            second += now.nanosecond() as f64 / 1e9;
+        }
+        let hand = SubHand {
+            turn: second / 60.0,
+            length: 0.8,
+            pair: SECONDS_PAIR,
+        };
+        draw_dial(
+            frame,
+            cx,
+            cy + b / 2,
+            sa,
+            sb,
+            &["60", "15", "30", "45"],
+            &[hand],
+        );
+    }
+    match cfg.get::<NineOClockSubDial>() {
+        NineOClockSubDial::Off => {}
+        NineOClockSubDial::TwentyFourHours => {
+            let hand = SubHand {
+                turn: (now.hour() * 60 + now.minute()) as f64 / 1440.0,
+                length: 0.8,
+                pair: HOURS_PAIR,
+            };
+            draw_dial(
+                frame,
+                cx - a / 2,
+                cy,
+                sa,
+                sb,
+                &["24", "6", "12", "18"],
+                &[hand],
+            );
+        }
+        NineOClockSubDial::SecondTimeZone => {
+            let zone: Tz = cfg
+                .get_string("sub-dial time zone")
+                .and_then(|name| name.trim().parse().ok())
+                .unwrap_or(Tz::UTC);
+            let there = now.with_timezone(&zone);
+            let minutes = (there.hour() % 12 * 60 + there.minute()) as f64;
+            let hands = [
+                SubHand {
+                    turn: there.minute() as f64 / 60.0,
+                    length: 0.8,
+                    pair: MINUTES_PAIR,
+                },
+                SubHand {
+                    turn: minutes / 720.0,
+                    length: 0.5,
+                    pair: HOURS_PAIR,
+                },
+            ];
+            draw_dial(
+                frame,
+                cx - a / 2,
+                cy,
+                sa,
+                sb,
+                &["12", "3", "6", "9"],
+                &hands,
+            );
+        }
+    }
+    if cfg.get_bool("day of week sub-dial") {
+        let day = now.weekday().num_days_from_monday() as f64 + now.hour() as f64 / 24.0;
+        let hand = SubHand {
+            turn: day / 7.0,
+            length: 0.8,
+            pair: HOURS_PAIR,
+        };
+        draw_dial(frame, cx + a / 2, cy, sa, sb, &DAYS, &[hand]);
+    }
+}
+
+/// Whether sub-dials fit in a dial of vertical radius `b`: they take a
+/// quarter of it, too small to read under 2 rows.
+fn fits(b: i32) -> bool {
+    b / 4 >= 2
+}
+
+/// Whether the second hand is on the seconds sub-dial rather than on the
+/// main dial (the small seconds of a watch), for a dial of vertical radius `b`.
+pub fn carries_seconds(cfg: &Config, b: i32) -> bool {
+    cfg.get_bool("seconds sub-dial") && fits(b)
+}
+
+/// A small dial centred at (cx,cy) with radii `a` and `b`: a dotted rim,
+/// `labels` evenly spaced clockwise from 12 o'clock, and `hands` drawn in order.
+fn draw_dial(
+    frame: &mut FrameBuffer,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+    labels: &[&str],
+    hands: &[SubHand],
+) {
+    for (x, y) in ellipse_points(cx, cy, a, b) {
+        frame.put(x, y, '.', Style::pair(BORDER_PAIR));
+    }
+    // Labels need a row between them and the centre
+    if b >= 3 {
+        for (i, label) in labels.iter().enumerate() {
+            let angle = 2.0 * PI * i as f64 / labels.len() as f64;
+            let (x, y) = polar_to_cartesian_ellipse(cx, cy, angle, a as f64 * 0.7, b as f64 * 0.7);
+            frame.put_str(
+                x - text_width(label) / 2,
+                y,
+                label,
+                Style::pair(DIGITS_PAIR),
+            );
+        }
+    }
+    for hand in hands {
+        let (x, y) = polar_to_cartesian_ellipse(
+            cx,
+            cy,
+            2.0 * PI * hand.turn,
+            a as f64 * hand.length,
+            b as f64 * hand.length,
+        );
+        frame.draw_line(cx, cy, x, y, "o", Style::pair(hand.pair));
+    }
+}
+
+/// Draw the "date window": the day of the month in a box, a little inside
+/// the dial at 3 or 6 o'clock, over the sub-dials. The caller draws the
+/// hands afterwards, so they sweep over the window as on a watch.
+pub fn draw_date_window<Z: TimeZone>(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    now: &DateTime<Z>,
+    cx: i32,
+    cy: i32,
+    a: i32,
+    b: i32,
+) {
+    // The box needs a row above and below it inside the hour numbers
+    if b < 5 {
+        return;
+    }
+    let (x, y) = match cfg.get::<DateWindow>() {
+        DateWindow::Off => return,
+        DateWindow::ThreeOClock => (cx + (a as f64 * 0.6).round() as i32, cy),
+        DateWindow::SixOClock => (cx, cy + (b as f64 * 0.6).round() as i32),
+    };
+    let border = Style::pair(BORDER_PAIR);
+    frame.put_str(x - 2, y - 1, "┌──┐", border);
+    frame.put_str(x - 2, y, "│", border);
+    frame.put_str(x + 1, y, "│", border);
+    frame.put_str(x - 2, y + 1, "└──┘", border);
+    frame.put_str(
+        x - 1,
+        y,
+        &format!("{:>2}", now.day()),
+        Style::pair(DIGITS_PAIR),
+    );
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+use tac::clock::{Clock, SystemClock};
+
+use crate::config_edit::Config;
+
+/// Time zone configured in the "timezone" entry (an IANA name such as
+/// "Asia/Kolkata"), or `None` to use the system local time.
+pub fn configured_timezone(cfg: &Config) -> Option<Tz> {
+    cfg.get_string("timezone")
+        .and_then(|name| name.trim().parse().ok())
+}
+
+/// How far the clock of this run is from the system clock, set by `--date`.
+static SHIFT: OnceLock<TimeDelta> = OnceLock::new();
+
+/// The clock of this run: the system clock, or with `--date` the given
+/// moment, from which it goes on ticking. Everything showing or acting on
+/// the time reads this one, so that a past or future moment is shown whole.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SessionClock;
+
+impl Clock for SessionClock {
+    fn now(&self) -> DateTime<Utc> {
+        SystemClock.now() + SHIFT.get().copied().unwrap_or_else(TimeDelta::zero)
+    }
+}
+
+/// Make the [`SessionClock`] start at `moment`; only the first call counts.
+pub fn start_at(moment: DateTime<Utc>) {
+    SHIFT.get_or_init(|| moment - SystemClock.now());
+}
+
+/// The instant `local` names in the displayed zone (the configured time zone
+/// or local time), the earlier one when the clocks are turned back; `None`
+/// for a time skipped when they are put forward.
+pub fn moment(cfg: &Config, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match configured_timezone(cfg) {
+        Some(tz) => tz
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|t| t.to_utc()),
+        None => Local
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|t| t.to_utc()),
+    }
+}
+
+/// Current time of `clock` in the zone the clock displays: the configured
+/// time zone (or local time), shifted by "local time offset minutes". Every
+/// component (hands, readouts, date, alarms) reads the time from here so they
+/// always agree, including on the date when the offset crosses midnight.
+pub fn effective_now(cfg: &Config, clock: &dyn Clock) -> DateTime<FixedOffset> {
+    let utc = clock.now();
+    let now = match configured_timezone(cfg) {
+        Some(tz) => utc.with_timezone(&tz).fixed_offset(),
+        None => utc.with_timezone(&Local).fixed_offset(),
+    };
+    let shift = cfg.get_int("local time offset minutes") * 60;
+    let seconds = i64::from(now.offset().fix().local_minus_utc()) + shift;
+    // Offsets are limited to ±24 h; ignore anything beyond that
+    match i32::try_from(seconds).ok().and_then(FixedOffset::east_opt) {
+        Some(zone) => now.with_timezone(&zone),
+        None => now,
+    }
+}
+
+/// Name of the displayed zone: the configured one, else the system zone when it can be found.
+fn zone_name(cfg: &Config) -> Option<String> {
+    configured_timezone(cfg)
+        .map(|tz| tz.name().to_string())
+        .or_else(|| iana_time_zone::get_timezone().ok())
+}
+
+/// One-line description of the displayed zone, e.g. "Paris (UTC+01:00, CET)".
+/// The offset comes from `now`, so it follows DST changes and the configured
+/// offset; the abbreviation is only given when no extra offset is applied.
+pub fn zone_caption(cfg: &Config, now: &DateTime<FixedOffset>) -> String {
+    let name = zone_name(cfg);
+    let city = name
+        .as_deref()
+        .map(|n| n.rsplit('/').next().unwrap_or(n).replace('_', " "))
+        .unwrap_or_else(|| "Local time".into());
+    let utc_offset = now.format("UTC%:z").to_string();
+
+    let tz: Option<Tz> = name.and_then(|n| n.parse().ok());
+    match tz {
+        Some(tz) if cfg.get_int("local time offset minutes") == 0 => {
+            let abbreviation = now.with_timezone(&tz).format("%Z").to_string();
+            format!("{} ({}, {})", city, utc_offset, abbreviation)
+        }
+        _ => format!("{} ({})", city, utc_offset),
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::{Attributes, Style};
+
+use crate::layout::{Layout, Slot};
+
+/// How long a toast stays on screen, the last part of it dimmed.
+const TOAST_DURATION: Duration = Duration::from_millis(2500);
+const FADE_DURATION: Duration = Duration::from_millis(700);
+
+/// Toasts shown at once; older ones are dropped.
+const MAX_TOASTS: usize = 3;
+
+/// Brief notifications ("Frame saved", "Alarm in 10 min") shown in the bottom
+/// right corner, newest at the bottom. Any subsystem given a `&mut Toasts`
+/// can show one.
+#[derive(Default)]
+pub struct Toasts {
+    /// Message and when it goes away
+    toasts: Vec<(String, Instant)>,
+}
+
+impl Toasts {
+    pub fn show(&mut self, message: impl Into<String>) {
+        self.show_for(message, TOAST_DURATION);
+    }
+
+    /// Show a toast for longer (or shorter) than usual.
+    pub fn show_for(&mut self, message: impl Into<String>, duration: Duration) {
+        self.toasts
+            .push((message.into(), Instant::now() + duration));
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Whether a toast is on screen (the fade needs more than one frame per second).
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Forget expired toasts and place the other ones in the layout.
+    pub fn add_to(&mut self, layout: &mut Layout) {
+        let now = Instant::now();
+        self.toasts.retain(|&(_, until)| now < until);
+        // Same priority for all: the newest, added first, gets the corner
+        for &(ref message, until) in self.toasts.iter().rev() {
+            let mut style = Style::default().with(Attributes::REVERSE);
+            if until - now < FADE_DURATION {
+                style = style.with(Attributes::DIM);
+            }
+            layout.add(Slot::SouthEast, 50, format!(" {message} "), style);
+        }
+    }
+}
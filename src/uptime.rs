@@ -0,0 +1,133 @@
+use std::f64::consts::PI;
+use std::fs;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::choices::UptimeGauge;
+use crate::config_edit::Config;
+use crate::dial::{BORDER_PAIR, DIGITS_PAIR, PROGRESS_PAIR};
+
+/// When the clock started, for the "clock" gauge and for systems whose
+/// uptime cannot be read.
+static START: OnceLock<Instant> = OnceLock::new();
+
+/// Note the start of the clock; called once before the first frame.
+pub fn start() {
+    START.get_or_init(Instant::now);
+}
+
+fn since_start() -> Duration {
+    START.get_or_init(Instant::now).elapsed()
+}
+
+/// Time since the computer booted: /proc/uptime on Linux, the boot time
+/// from sysctl on macOS and the BSDs, the time since the clock started
+/// anywhere else.
+fn system_uptime() -> Duration {
+    proc_uptime()
+        .or_else(boot_time_uptime)
+        .unwrap_or_else(since_start)
+}
+
+fn proc_uptime() -> Option<Duration> {
+    let text = fs::read_to_string("/proc/uptime").ok()?;
+    let seconds: f64 = text.split_whitespace().next()?.parse().ok()?;
+    (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+fn boot_time_uptime() -> Option<Duration> {
+    use std::mem;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let mut mib = [libc::CTL_KERN, libc::KERN_BOOTTIME];
+    // SAFETY: timeval is plain data, and sysctl writes at most `size` bytes into it
+    let mut boot: libc::timeval = unsafe { mem::zeroed() };
+    let mut size = mem::size_of::<libc::timeval>();
+    let failed = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            mib.len() as libc::c_uint,
+            &mut boot as *mut libc::timeval as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    } != 0;
+    if failed || boot.tv_sec <= 0 {
+        return None;
+    }
+    let boot = UNIX_EPOCH + Duration::from_secs(boot.tv_sec as u64);
+    SystemTime::now().duration_since(boot).ok()
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+fn boot_time_uptime() -> Option<Duration> {
+    None
+}
+
+/// Short text for a duration: "3d4h" past a day, "5h12" past an hour,
+/// "42m" below.
+fn label(time: Duration) -> String {
+    let minutes = time.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d{}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{:02}", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Draw the "uptime gauge", shaped like the power reserve of a watch: an
+/// arc around 12 o'clock between the center and the hour numbers, filled
+/// from the left as far as the uptime goes on a scale of "uptime gauge
+/// hours", with the uptime written under it. A full gauge stays full.
+pub fn draw(frame: &mut FrameBuffer, cfg: &Config, cx: i32, cy: i32, a: i32, b: i32) {
+    let time = match cfg.get::<UptimeGauge>() {
+        UptimeGauge::Off => return,
+        UptimeGauge::System => system_uptime(),
+        UptimeGauge::Clock => since_start(),
+    };
+    // The arc and its label need a few rows above the center
+    if b < 6 {
+        return;
+    }
+    let hours = cfg.get_int("uptime gauge hours").max(1) as f64;
+    let fraction = (time.as_secs_f64() / 3600.0 / hours).min(1.0);
+    let (ra, rb) = (a as f64 * 0.55, b as f64 * 0.55);
+    let (start, end) = (-PI / 3.0, PI / 3.0);
+    frame.draw_arc(cx, cy, ra, rb, start, end, '.', Style::pair(BORDER_PAIR));
+    if fraction > 0.0 {
+        frame.draw_arc(
+            cx,
+            cy,
+            ra,
+            rb,
+            start,
+            start + (end - start) * fraction,
+            '+',
+            Style::pair(PROGRESS_PAIR),
+        );
+    }
+    let text = label(time);
+    frame.put_str(
+        cx - text_width(&text) / 2,
+        cy - rb.round() as i32 + 1,
+        &text,
+        Style::pair(DIGITS_PAIR),
+    );
+}
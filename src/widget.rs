@@ -0,0 +1,208 @@
+//! Analog clock face as a ratatui [`Widget`].
+//!
+//! ```no_run
+//! use chrono::Local;
+//! use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+//! use tac::widget::AnalogClock;
+//!
+//! let area = Rect::new(0, 0, 40, 20);
+//! let mut buf = Buffer::empty(area);
+//! AnalogClock::new(Local::now().time())
+//!     .show_seconds(true)
+//!     .colors(&[(tac::face::HOURS_PAIR, "#ff8800")])
+//!     .render(area, &mut buf);
+//! ```
+
+use chrono::{NaiveTime, Timelike};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Widget;
+use std::f64::consts::PI;
+
+use crate::face::{
+    self, Hand, BORDER_PAIR, DEFAULT_COLORS, DIGITS_PAIR, HAND_TAIL_LENGTH, HOURS_PAIR,
+    HOUR_HAND_LENGTH, MINUTES_PAIR, MINUTE_HAND_LENGTH, SECONDS_PAIR, SECOND_HAND_LENGTH,
+};
+use crate::framebuffer::{self, Attributes, FrameBuffer};
+
+/// Clock face with hour, minute and optional second hands, drawn by the
+/// [`face`] code of the `tac` binary into a [`FrameBuffer`]: an elliptic
+/// border twice as wide as high, filling as much of the area as possible.
+#[derive(Clone, Debug)]
+pub struct AnalogClock {
+    time: NaiveTime,
+    show_seconds: bool,
+    numbers: bool,
+    /// Style of the cells of each color pair of [`face`]
+    styles: Vec<(i16, Style)>,
+}
+
+/// Foreground color of a color written as in the config of the `tac`
+/// binary: a basic name ("cyan"), a palette index ("208") or "#rrggbb".
+fn parse_color(text: &str) -> Option<Color> {
+    text.trim().to_ascii_lowercase().parse().ok()
+}
+
+impl AnalogClock {
+    /// A clock showing `time`, with the default colors of the `tac` binary.
+    pub fn new(time: NaiveTime) -> Self {
+        Self {
+            time,
+            show_seconds: true,
+            numbers: false,
+            styles: Vec::new(),
+        }
+        .colors(&DEFAULT_COLORS)
+    }
+
+    /// Show or hide the second hand.
+    pub fn show_seconds(mut self, show: bool) -> Self {
+        self.show_seconds = show;
+        self
+    }
+
+    /// Draw hour numbers (1–12) instead of stars inside the border.
+    pub fn numbers(mut self, numbers: bool) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Set the foreground colors of color pairs of [`face`], written as in the
+    /// color settings of the `tac` config, e.g. its "hours color" for
+    /// [`HOURS_PAIR`]. Colors that don't parse are left as they were.
+    pub fn colors(mut self, colors: &[(i16, &str)]) -> Self {
+        for &(pair, text) in colors {
+            if let Some(color) = parse_color(text) {
+                self = self.pair_style(pair, Style::default().fg(color));
+            }
+        }
+        self
+    }
+
+    /// Style of the cells drawn in the color pair `pair` of [`face`].
+    pub fn pair_style(mut self, pair: i16, style: Style) -> Self {
+        self.styles.retain(|&(p, _)| p != pair);
+        self.styles.push((pair, style));
+        self
+    }
+
+    pub fn border_style(self, style: Style) -> Self {
+        self.pair_style(BORDER_PAIR, style)
+    }
+
+    pub fn digits_style(self, style: Style) -> Self {
+        self.pair_style(DIGITS_PAIR, style)
+    }
+
+    pub fn hour_style(self, style: Style) -> Self {
+        self.pair_style(HOURS_PAIR, style)
+    }
+
+    pub fn minute_style(self, style: Style) -> Self {
+        self.pair_style(MINUTES_PAIR, style)
+    }
+
+    pub fn second_style(self, style: Style) -> Self {
+        self.pair_style(SECONDS_PAIR, style)
+    }
+
+    /// Style of a cell of the frame: its pair's, with its attributes.
+    fn cell_style(&self, style: framebuffer::Style) -> Style {
+        let base = self
+            .styles
+            .iter()
+            .find(|&&(pair, _)| pair == style.pair)
+            .map_or_else(Style::default, |&(_, style)| style);
+        [
+            (Attributes::BOLD, Modifier::BOLD),
+            (Attributes::DIM, Modifier::DIM),
+            (Attributes::REVERSE, Modifier::REVERSED),
+            (Attributes::BLINK, Modifier::SLOW_BLINK),
+        ]
+        .into_iter()
+        .filter(|&(attribute, _)| style.attributes.contains(attribute))
+        .fold(base, |base, (_, modifier)| base.add_modifier(modifier))
+    }
+
+    /// The face, drawn like the dial of the binary with its default settings.
+    fn frame(&self, cols: i32, rows: i32) -> Option<FrameBuffer> {
+        // Same sizing rule as the binary: a = 2·b, everything fits in the area
+        let b = ((rows - 1) / 2).min((cols / 2 - 1) / 2);
+        if b < 1 {
+            return None;
+        }
+        let (center, radii) = ((cols / 2, rows / 2), (2 * b, b));
+        let mut frame = FrameBuffer::new(cols, rows);
+        let border = framebuffer::Style::pair(BORDER_PAIR);
+        frame.draw_ellipse(center.0, center.1, radii.0, radii.1, '*', border);
+        let digits = framebuffer::Style::pair(DIGITS_PAIR);
+        face::draw_hour_markers(&mut frame, center, radii, digits, |i| {
+            Some(if self.numbers {
+                i.to_string()
+            } else {
+                "*".into()
+            })
+        });
+
+        // Angles: 0 rad = 12 o'clock, increase clockwise.
+        let hour = (self.time.hour() % 12) as f64;
+        let minute = self.time.minute() as f64;
+        let second = self.time.second() as f64;
+        let hand = |angle: f64, length: f64, tail: f64, pattern: &str, pair: i16| {
+            Hand::new(
+                center,
+                radii,
+                angle,
+                (-tail * length, length),
+                pattern,
+                pair,
+            )
+        };
+        let mut hands = Vec::with_capacity(3);
+        if self.show_seconds {
+            let angle = 2.0 * PI * second / 60.0;
+            hands.push(hand(angle, SECOND_HAND_LENGTH, 0.0, ".", SECONDS_PAIR));
+        }
+        let angle = 2.0 * PI * (minute + second / 60.0) / 60.0;
+        hands.push(hand(
+            angle,
+            MINUTE_HAND_LENGTH,
+            HAND_TAIL_LENGTH,
+            "minutes",
+            MINUTES_PAIR,
+        ));
+        let angle = 2.0 * PI * (hour + minute / 60.0) / 12.0;
+        hands.push(hand(
+            angle,
+            HOUR_HAND_LENGTH,
+            HAND_TAIL_LENGTH,
+            "HOURS",
+            HOURS_PAIR,
+        ));
+        face::draw_hands(&mut frame, &hands);
+        Some(frame)
+    }
+}
+
+impl Widget for AnalogClock {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(frame) = self.frame(area.width as i32, area.height as i32) else {
+            return;
+        };
+        // Only the drawn cells: the rest of the area keeps its background
+        for y in 0..frame.height() {
+            for (x, cell) in frame.row(y).iter().enumerate() {
+                if *cell == framebuffer::Cell::BLANK || cell.is_continuation() {
+                    continue;
+                }
+                let position = Position::new(area.x + x as u16, area.y + y as u16);
+                if let Some(target) = buf.cell_mut(position) {
+                    target
+                        .set_char(cell.ch)
+                        .set_style(self.cell_style(cell.style));
+                }
+            }
+        }
+    }
+}
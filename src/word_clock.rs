@@ -0,0 +1,170 @@
+use chrono::{DateTime, FixedOffset, Timelike};
+
+use tac::framebuffer::{Attributes, FrameBuffer, Style};
+
+use crate::choices::WordClockLanguage;
+use crate::config_edit::Config;
+use crate::dial::{BORDER_PAIR, HOURS_PAIR};
+
+/// A word of the grid: row, first column and length.
+type Word = (usize, usize, usize);
+
+const ENGLISH: [&str; 10] = [
+    "ITLISASTIME",
+    "ACQUARTERDC",
+    "TWENTYFIVEX",
+    "HALFBTENFTO",
+    "PASTERUNINE",
+    "ONESIXTHREE",
+    "FOURFIVETWO",
+    "EIGHTELEVEN",
+    "SEVENTWELVE",
+    "TENSEOCLOCK",
+];
+
+/// ONE to TWELVE in the English grid.
+const ENGLISH_HOURS: [Word; 12] = [
+    (5, 0, 3),
+    (6, 8, 3),
+    (5, 6, 5),
+    (6, 0, 4),
+    (6, 4, 4),
+    (5, 3, 3),
+    (8, 0, 5),
+    (7, 0, 5),
+    (4, 7, 4),
+    (9, 0, 3),
+    (7, 5, 6),
+    (8, 5, 6),
+];
+
+const FRENCH: [&str; 10] = [
+    "ILNESTODEUX",
+    "QUATRETROIS",
+    "NEUFUNESEPT",
+    "HUITSIXCINQ",
+    "MIDIXMINUIT",
+    "ONZERHEURES",
+    "MOINSOLEDIX",
+    "ETRQUARTPMD",
+    "VINGT-CINQU",
+    "ETSDEMIEPAM",
+];
+
+/// UNE to ONZE in the French grid.
+const FRENCH_HOURS: [Word; 11] = [
+    (2, 4, 3),
+    (0, 7, 4),
+    (1, 6, 5),
+    (1, 0, 6),
+    (3, 7, 4),
+    (3, 4, 3),
+    (2, 7, 4),
+    (3, 0, 4),
+    (2, 0, 4),
+    (4, 2, 3),
+    (5, 0, 4),
+];
+
+/// Words of "IT IS TWENTY PAST SEVEN", the minutes rounded down to five.
+fn english(hour: u32, minute: u32) -> Vec<Word> {
+    let (it, is) = ((0, 0, 2), (0, 3, 2));
+    let (quarter, twenty, five, half, ten) =
+        ((1, 2, 7), (2, 0, 6), (2, 6, 4), (3, 0, 4), (3, 5, 3));
+    let (to, past, oclock) = ((3, 9, 2), (4, 0, 4), (9, 5, 6));
+    let mut words = vec![it, is];
+    let (minutes, hour) = match minute / 5 {
+        0 => (vec![], hour),
+        1 => (vec![five, past], hour),
+        2 => (vec![ten, past], hour),
+        3 => (vec![quarter, past], hour),
+        4 => (vec![twenty, past], hour),
+        5 => (vec![twenty, five, past], hour),
+        6 => (vec![half, past], hour),
+        7 => (vec![twenty, five, to], hour + 1),
+        8 => (vec![twenty, to], hour + 1),
+        9 => (vec![quarter, to], hour + 1),
+        10 => (vec![ten, to], hour + 1),
+        _ => (vec![five, to], hour + 1),
+    };
+    words.extend(minutes);
+    words.push(ENGLISH_HOURS[((hour + 11) % 12) as usize]);
+    if minute < 5 {
+        words.push(oclock);
+    }
+    words
+}
+
+/// Words of "IL EST SEPT HEURES VINGT", the minutes rounded down to five.
+fn french(hour: u32, minute: u32) -> Vec<Word> {
+    let (il, est) = ((0, 0, 2), (0, 3, 3));
+    let (moins, le, dix) = ((6, 0, 5), (6, 6, 2), (6, 8, 3));
+    let (et_quart, quart) = ((7, 0, 2), (7, 3, 5));
+    let (vingt, vingt_cinq, cinq) = ((8, 0, 5), (8, 0, 10), (8, 6, 4));
+    let (et_demie, demie) = ((9, 0, 2), (9, 3, 5));
+    let mut words = vec![il, est];
+    let (minutes, hour) = match minute / 5 {
+        0 => (vec![], hour),
+        1 => (vec![cinq], hour),
+        2 => (vec![dix], hour),
+        3 => (vec![et_quart, quart], hour),
+        4 => (vec![vingt], hour),
+        5 => (vec![vingt_cinq], hour),
+        6 => (vec![et_demie, demie], hour),
+        7 => (vec![moins, vingt_cinq], hour + 1),
+        8 => (vec![moins, vingt], hour + 1),
+        9 => (vec![moins, le, quart], hour + 1),
+        10 => (vec![moins, dix], hour + 1),
+        _ => (vec![moins, cinq], hour + 1),
+    };
+    match hour % 24 {
+        0 => words.push((4, 5, 6)),
+        12 => words.push((4, 0, 4)),
+        h => {
+            words.push(FRENCH_HOURS[((h + 11) % 12) as usize]);
+            // HEURE, or HEURES after any hour but one
+            words.push((5, 5, if h % 12 == 1 { 5 } else { 6 }));
+        }
+    }
+    words.extend(minutes);
+    words
+}
+
+/// Draw the letter grid of the "word clock language", centred, the words
+/// telling the time in the color of the hour hand and the other letters
+/// dimmed in the color of the dial.
+pub fn draw(frame: &mut FrameBuffer, cfg: &Config, now: &DateTime<FixedOffset>) {
+    let (grid, words) = match cfg.get::<WordClockLanguage>() {
+        WordClockLanguage::English => (ENGLISH, english(now.hour(), now.minute())),
+        WordClockLanguage::French => (FRENCH, french(now.hour(), now.minute())),
+    };
+    let lit = |row: usize, column: usize| {
+        words
+            .iter()
+            .any(|&(r, first, len)| r == row && (first..first + len).contains(&column))
+    };
+    // One space between letters, one blank row between lines when there is room
+    let width = 2 * grid[0].len() as i32 - 1;
+    let spacing = if frame.height() >= 2 * grid.len() as i32 {
+        2
+    } else {
+        1
+    };
+    let left = (frame.width() - width) / 2;
+    let top = (frame.height() - spacing * grid.len() as i32) / 2;
+    for (row, line) in grid.iter().enumerate() {
+        for (column, letter) in line.chars().enumerate() {
+            let style = if lit(row, column) {
+                Style::pair(HOURS_PAIR).with(Attributes::BOLD)
+            } else {
+                Style::pair(BORDER_PAIR).with(Attributes::DIM)
+            };
+            frame.put(
+                left + 2 * column as i32,
+                top + spacing * row as i32,
+                letter,
+                style,
+            );
+        }
+    }
+}
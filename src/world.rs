@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use tac::framebuffer::{text_width, FrameBuffer, Style};
+
+use crate::config_edit::Config;
+use crate::dial;
+
+/// One clock of the world clock mode.
+pub struct WorldClock {
+    pub timezone: Tz,
+    pub label: String,
+    /// Vertical radius, in rows; the size of the grid cells when `None`
+    pub size: Option<i32>,
+    /// Column and row of the centre, negative ones counted from the right and
+    /// bottom edges; a place in the grid when `None`
+    pub position: Option<(i32, i32)>,
+}
+
+/// Read the "world clocks" list. Items are either an IANA name
+/// (`"Europe/Paris"`) or an object `{"timezone": "Europe/Paris", "label": "Paris"}`,
+/// optionally with a `"size"` and a `"column"` and `"row"`; unknown time zones
+/// are skipped. Without a label the city part of the name is used.
+pub fn world_clocks(cfg: &Config) -> Vec<WorldClock> {
+    cfg.get_list("world clocks")
+        .iter()
+        .filter_map(|item| {
+            let number = |key: &str| item.get(key).and_then(|n| n.as_i64()).map(|n| n as i32);
+            let (name, label) = match item {
+                serde_json::Value::String(name) => (name.as_str(), None),
+                serde_json::Value::Object(fields) => (
+                    fields.get("timezone")?.as_str()?,
+                    fields.get("label").and_then(|l| l.as_str()),
+                ),
+                _ => return None,
+            };
+            let timezone: Tz = name.parse().ok()?;
+            let label = match label {
+                Some(label) => label.to_string(),
+                None => name.rsplit('/').next().unwrap_or(name).replace('_', " "),
+            };
+            Some(WorldClock {
+                timezone,
+                label,
+                size: number("size").filter(|&size| size >= 1),
+                position: number("column").zip(number("row")),
+            })
+        })
+        .collect()
+}
+
+/// Number of grid columns giving the biggest dials for `count` clocks,
+/// together with the resulting vertical radius.
+fn best_grid(count: i32, rows: i32, cols: i32) -> (i32, i32) {
+    (1..=count)
+        .map(|columns| {
+            let lines = (count + columns - 1) / columns;
+            let cell_rows = rows / lines;
+            let cell_cols = cols / columns;
+            // One row of each cell is kept for the label
+            let b = ((cell_rows - 1) / 2 - 1).min((cell_cols / 2 - 1) / 2);
+            (columns, b)
+        })
+        .max_by_key(|&(_, b)| b)
+        .unwrap_or((1, 0))
+}
+
+/// Draw one labelled clock per time zone, all showing `utc`: the clocks with
+/// a position where it says, the others in a grid splitting the screen.
+pub fn draw_world_clocks(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    clocks: &[WorldClock],
+    utc: DateTime<Utc>,
+) {
+    let (rows, cols) = (frame.height(), frame.width());
+    // Placed clocks without a size get the one they would have in a full grid
+    let (_, default_b) = best_grid(clocks.len() as i32, rows, cols);
+    let (placed, free): (Vec<&WorldClock>, Vec<&WorldClock>) =
+        clocks.iter().partition(|clock| clock.position.is_some());
+
+    let count = free.len() as i32;
+    let (columns, grid_b) = best_grid(count, rows, cols);
+    let lines = (count + columns - 1) / columns.max(1);
+    let cell_rows = rows / lines.max(1);
+    let cell_cols = cols / columns.max(1);
+    for (i, clock) in free.iter().enumerate() {
+        let i = i as i32;
+        let cx = (i % columns) * cell_cols + cell_cols / 2;
+        let cy = (i / columns) * cell_rows + (cell_rows - 1) / 2;
+        let b = clock.size.map_or(grid_b, |size| size.min(grid_b));
+        draw_world_clock(frame, cfg, clock, utc, cx, cy, b);
+    }
+
+    for clock in placed {
+        let Some((column, row)) = clock.position else {
+            continue;
+        };
+        let cx = if column < 0 { cols + column } else { column };
+        let cy = if row < 0 { rows + row } else { row };
+        draw_world_clock(
+            frame,
+            cfg,
+            clock,
+            utc,
+            cx,
+            cy,
+            clock.size.unwrap_or(default_b),
+        );
+    }
+}
+
+/// One clock centred at (cx,cy) with vertical radius `b`, its label and
+/// time below.
+fn draw_world_clock(
+    frame: &mut FrameBuffer,
+    cfg: &Config,
+    clock: &WorldClock,
+    utc: DateTime<Utc>,
+    cx: i32,
+    cy: i32,
+    b: i32,
+) {
+    if b < 1 {
+        return;
+    }
+    let now = utc.with_timezone(&clock.timezone);
+    dial::draw_clock(frame, cfg, &now, cx, cy, 2 * b, b);
+
+    let caption = format!("{} {}", clock.label, now.format("%H:%M"));
+    let width = text_width(&caption);
+    frame.put_str(cx - width / 2, cy + b + 1, &caption, Style::default());
+}
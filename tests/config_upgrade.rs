@@ -0,0 +1,127 @@
+//! Config files written by older versions keep their settings once upgraded.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A config file in a directory of its own, removed at the end of the test.
+struct TempConfig(PathBuf);
+
+impl TempConfig {
+    fn new(name: &str, text: &str) -> Self {
+        TempConfig::named(name, "config.json", text)
+    }
+
+    fn named(name: &str, file: &str, text: &str) -> Self {
+        let dir = env::temp_dir().join(format!("tac-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(file);
+        fs::write(&path, text).unwrap();
+        TempConfig(path)
+    }
+
+    /// The file as TOML, as written by the clock.
+    fn toml(&self) -> toml::Table {
+        fs::read_to_string(&self.0).unwrap().parse().unwrap()
+    }
+
+    /// The entry of `key` in the file, as written by the clock.
+    fn entry(&self, key: &str) -> serde_json::Value {
+        let text = fs::read_to_string(&self.0).unwrap();
+        let file: serde_json::Value = serde_json::from_str(&text).unwrap();
+        file["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["key"] == key)
+            .unwrap_or_else(|| panic!("no \"{key}\" entry"))
+            .clone()
+    }
+}
+
+impl Drop for TempConfig {
+    fn drop(&mut self) {
+        if let Some(dir) = self.0.parent() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Run the clock once on the config, which upgrades the file.
+fn print_clock(config: &TempConfig) {
+    let status = Command::new(env!("CARGO_BIN_EXE_tac"))
+        .arg("--config")
+        .arg(&config.0)
+        .arg("--print")
+        .env("HOME", config.0.parent().unwrap())
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+}
+
+#[test]
+fn shortcuts_stored_as_text_keep_their_key() {
+    let config = TempConfig::new(
+        "text-shortcut",
+        r#"{"entries": [
+            {"key": "quit", "value": {"kind": "text", "value": "x", "maximum_size": 1}}
+        ]}"#,
+    );
+    print_clock(&config);
+    let quit = config.entry("quit");
+    assert_eq!(quit["value"]["kind"], "shortcut");
+    assert_eq!(quit["value"]["value"], "x");
+}
+
+#[test]
+fn hand_lengths_in_percent_become_fractions() {
+    let config = TempConfig::new(
+        "hand-percent",
+        r#"{"version": 1, "entries": [
+            {"key": "hour hand length", "value": {"kind": "integer", "value": 60}}
+        ]}"#,
+    );
+    print_clock(&config);
+    let length = config.entry("hour hand length");
+    assert_eq!(length["value"]["kind"], "float");
+    assert_eq!(length["value"]["value"], 0.6);
+}
+
+#[test]
+fn toml_hand_lengths_in_percent_become_fractions() {
+    let config = TempConfig::named(
+        "toml-hand-percent",
+        "config.toml",
+        "version = 2\nhour_hand_length = 60\n",
+    );
+    print_clock(&config);
+    let file = config.toml();
+    assert_eq!(file["version"].as_integer(), Some(3));
+    assert_eq!(file["hour_hand_length"].as_float(), Some(0.6));
+    // Left out of the file: the default, not a migrated one
+    assert_eq!(file["minute_hand_length"].as_float(), Some(0.9));
+}
+
+#[test]
+fn toml_files_at_the_current_version_are_left_alone() {
+    let text = "version = 3\ncircle_color = \"RED\"\n";
+    let config = TempConfig::named("toml-current", "config.toml", text);
+    print_clock(&config);
+    assert_eq!(fs::read_to_string(&config.0).unwrap(), text);
+}
+
+#[test]
+fn entries_of_another_kind_are_reset() {
+    let config = TempConfig::new(
+        "wrong-kind",
+        r#"{"version": 3, "entries": [
+            {"key": "quit", "value": {"kind": "boolean", "value": true}}
+        ]}"#,
+    );
+    print_clock(&config);
+    let quit = config.entry("quit");
+    assert_eq!(quit["value"]["kind"], "shortcut");
+    assert_eq!(quit["value"]["value"], "q");
+}
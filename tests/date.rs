@@ -0,0 +1,144 @@
+//! Leap years, month ends and year ends in the calendar arithmetic of the
+//! date complications.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use proptest::prelude::*;
+
+use tac::date::{add_months, days_in_month, is_leap_year, month_weeks, next_midnight};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+    date(year, month, day)
+        .and_hms_opt(hour, minute, second)
+        .unwrap()
+}
+
+#[test]
+fn leap_years_skip_centuries_but_not_every_fourth() {
+    assert!(is_leap_year(2024));
+    assert!(is_leap_year(2000));
+    assert!(is_leap_year(1600));
+    assert!(!is_leap_year(2023));
+    assert!(!is_leap_year(1900));
+    assert!(!is_leap_year(2100));
+    // Proleptic years before year 1 follow the same rule
+    assert!(is_leap_year(0));
+    assert!(is_leap_year(-4));
+}
+
+#[test]
+fn february_has_29_days_in_leap_years_only() {
+    assert_eq!(days_in_month(2024, 2), 29);
+    assert_eq!(days_in_month(2000, 2), 29);
+    assert_eq!(days_in_month(2023, 2), 28);
+    assert_eq!(days_in_month(1900, 2), 28);
+    assert_eq!(days_in_month(2024, 4), 30);
+    assert_eq!(days_in_month(2024, 12), 31);
+    assert_eq!(days_in_month(2024, 0), 0);
+    assert_eq!(days_in_month(2024, 13), 0);
+}
+
+#[test]
+fn month_ends_clamp_to_the_shorter_month() {
+    assert_eq!(add_months(date(2024, 1, 31), 1), Some(date(2024, 2, 29)));
+    assert_eq!(add_months(date(2023, 1, 31), 1), Some(date(2023, 2, 28)));
+    assert_eq!(add_months(date(2024, 3, 31), 1), Some(date(2024, 4, 30)));
+    assert_eq!(add_months(date(2024, 3, 31), -1), Some(date(2024, 2, 29)));
+    assert_eq!(add_months(date(2024, 2, 29), 12), Some(date(2025, 2, 28)));
+    assert_eq!(add_months(date(2024, 2, 29), 48), Some(date(2028, 2, 29)));
+}
+
+#[test]
+fn months_roll_over_year_ends() {
+    assert_eq!(add_months(date(2024, 12, 15), 1), Some(date(2025, 1, 15)));
+    assert_eq!(add_months(date(2025, 1, 15), -1), Some(date(2024, 12, 15)));
+    assert_eq!(add_months(date(2024, 11, 30), 14), Some(date(2026, 1, 30)));
+    assert_eq!(add_months(date(1, 1, 1), -1), Some(date(0, 12, 1)));
+    assert_eq!(add_months(NaiveDate::MAX, 1), None);
+}
+
+#[test]
+fn next_midnight_crosses_month_and_year_ends() {
+    assert_eq!(
+        next_midnight(at(2024, 2, 28, 12, 0, 0)),
+        Some(at(2024, 2, 29, 0, 0, 0))
+    );
+    assert_eq!(
+        next_midnight(at(2024, 2, 29, 23, 59, 59)),
+        Some(at(2024, 3, 1, 0, 0, 0))
+    );
+    assert_eq!(
+        next_midnight(at(2023, 2, 28, 23, 0, 0)),
+        Some(at(2023, 3, 1, 0, 0, 0))
+    );
+    assert_eq!(
+        next_midnight(at(2024, 12, 31, 23, 59, 59)),
+        Some(at(2025, 1, 1, 0, 0, 0))
+    );
+    // Midnight itself is the start of its day: the next one is a day away
+    assert_eq!(
+        next_midnight(at(2025, 1, 1, 0, 0, 0)),
+        Some(at(2025, 1, 2, 0, 0, 0))
+    );
+}
+
+#[test]
+fn month_weeks_start_on_monday() {
+    // February 2021 starts on a Monday and fills exactly four weeks
+    let weeks = month_weeks(2021, 2);
+    assert_eq!(weeks.len(), 4);
+    assert_eq!(weeks[0][0], Some(1));
+    assert_eq!(weeks[3][6], Some(28));
+
+    // February 2024 starts on a Thursday and ends on the 29th, a Thursday
+    let weeks = month_weeks(2024, 2);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(
+        weeks[0],
+        [None, None, None, Some(1), Some(2), Some(3), Some(4)]
+    );
+    assert_eq!(
+        weeks[4],
+        [Some(26), Some(27), Some(28), Some(29), None, None, None]
+    );
+
+    // December 2024 ends on a Tuesday, in a sixth row
+    let weeks = month_weeks(2024, 12);
+    assert_eq!(weeks.len(), 6);
+    assert_eq!(weeks[5][1], Some(31));
+
+    assert!(month_weeks(2024, 13).is_empty());
+}
+
+proptest! {
+    #[test]
+    fn added_months_land_on_a_day_of_the_month(
+        year in -2000..4000i32,
+        ordinal in 1..=365u32,
+        months in -1200..1200i32,
+    ) {
+        let start = NaiveDate::from_yo_opt(year, ordinal).unwrap();
+        let moved = add_months(start, months).unwrap();
+        let total = |d: NaiveDate| d.year() as i64 * 12 + d.month0() as i64;
+        prop_assert_eq!(total(moved) - total(start), months as i64);
+        prop_assert_eq!(moved.day(), start.day().min(days_in_month(moved.year(), moved.month())));
+    }
+
+    #[test]
+    fn month_weeks_hold_each_day_once_in_its_weekday(year in -2000..4000i32, month in 1..=12u32) {
+        let weeks = month_weeks(year, month);
+        let days: Vec<(usize, u32)> = weeks
+            .iter()
+            .flat_map(|week| week.iter().enumerate().filter_map(|(column, day)| Some((column, (*day)?))))
+            .collect();
+        prop_assert_eq!(days.len() as u32, days_in_month(year, month));
+        for (i, (column, day)) in days.into_iter().enumerate() {
+            prop_assert_eq!(day, i as u32 + 1);
+            let weekday = NaiveDate::from_ymd_opt(year, month, day).unwrap().weekday();
+            prop_assert_eq!(column as u32, weekday.num_days_from_monday());
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Golden tests of `ellipse_points`: each file of `tests/golden` holds the
+//! expected drawing of one ellipse. Run with `UPDATE_GOLDEN=1` to rewrite
+//! them after an intended change of the algorithm.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use tac::geometry::ellipse_points;
+
+/// (horizontal radius, vertical radius) of the recorded ellipses: round,
+/// wide like the dial, tall, very flat and degenerate ones.
+const CASES: &[(i32, i32)] = &[
+    (0, 4),
+    (7, 0),
+    (1, 1),
+    (2, 3),
+    (3, 9),
+    (5, 2),
+    (10, 5),
+    (15, 15),
+    (20, 10),
+    (25, 10),
+    (33, 12),
+    (40, 12),
+    (72, 1),
+];
+
+/// Draw the ellipse with '*' on a grid just big enough to hold it.
+fn render(a: i32, b: i32) -> String {
+    let mut grid = vec![vec![' '; (2 * a + 1) as usize]; (2 * b + 1) as usize];
+    for (x, y) in ellipse_points(a, b, a, b) {
+        grid[y as usize][x as usize] = '*';
+    }
+    grid.iter()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n")
+        .collect()
+}
+
+fn golden_path(a: i32, b: i32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("ellipse_{a}x{b}.txt"))
+}
+
+#[test]
+fn ellipses_match_golden_files() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    for &(a, b) in CASES {
+        let drawing = render(a, b);
+        let path = golden_path(a, b);
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &drawing).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+        assert_eq!(
+            drawing,
+            expected,
+            "ellipse {a}x{b} differs from {}",
+            path.display()
+        );
+    }
+}
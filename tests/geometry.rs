@@ -0,0 +1,108 @@
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+use tac::geometry::{arc_points, ellipse_points, ellipse_spans, line_points, textured_line};
+
+/// Screen coordinates, negative ones included (dials can be partly off screen).
+fn coordinate() -> impl Strategy<Value = i32> {
+    -500..500
+}
+
+proptest! {
+    #[test]
+    fn line_plots_both_endpoints(x0 in coordinate(), y0 in coordinate(), x1 in coordinate(), y1 in coordinate()) {
+        let points = line_points(x0, y0, x1, y1);
+        prop_assert!(points.contains(&(x0, y0)));
+        prop_assert!(points.contains(&(x1, y1)));
+    }
+
+    #[test]
+    fn line_cells_touch_and_stay_in_bounding_box(x0 in coordinate(), y0 in coordinate(), x1 in coordinate(), y1 in coordinate()) {
+        let points = line_points(x0, y0, x1, y1);
+        for &(x, y) in &points {
+            prop_assert!(x0.min(x1) <= x && x <= x0.max(x1));
+            prop_assert!(y0.min(y1) <= y && y <= y0.max(y1));
+        }
+        for pair in points.windows(2) {
+            let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+            prop_assert!(dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0));
+        }
+    }
+
+    #[test]
+    fn line_is_in_reading_order(x0 in coordinate(), y0 in coordinate(), x1 in coordinate(), y1 in coordinate()) {
+        let points = line_points(x0, y0, x1, y1);
+        let first = points[0];
+        let expected = if x0 != x1 {
+            if x0 < x1 { (x0, y0) } else { (x1, y1) }
+        } else if y0 < y1 {
+            (x0, y0)
+        } else {
+            (x1, y1)
+        };
+        prop_assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn pattern_cycles_along_the_line(x0 in coordinate(), y0 in coordinate(), x1 in coordinate(), y1 in coordinate(), pattern in "[a-zA-Z.*]{1,8}") {
+        let cells = textured_line(x0, y0, x1, y1, &pattern);
+        let chars: Vec<char> = pattern.chars().collect();
+        prop_assert_eq!(cells.len(), line_points(x0, y0, x1, y1).len());
+        for (i, &(_, ch)) in cells.iter().enumerate() {
+            prop_assert_eq!(ch, chars[i % chars.len()]);
+        }
+    }
+
+    #[test]
+    fn empty_pattern_draws_nothing(x0 in coordinate(), y0 in coordinate(), x1 in coordinate(), y1 in coordinate()) {
+        prop_assert!(textured_line(x0, y0, x1, y1, "").is_empty());
+    }
+
+    #[test]
+    fn ellipse_is_symmetric(cx in coordinate(), cy in coordinate(), a in 0..200i32, b in 0..100i32) {
+        let points: HashSet<(i32, i32)> = ellipse_points(cx, cy, a, b).into_iter().collect();
+        for &(x, y) in &points {
+            prop_assert!(points.contains(&(2 * cx - x, y)));
+            prop_assert!(points.contains(&(x, 2 * cy - y)));
+            prop_assert!(points.contains(&(2 * cx - x, 2 * cy - y)));
+        }
+    }
+
+    #[test]
+    fn ellipse_stays_in_bounding_box(cx in coordinate(), cy in coordinate(), a in 0..200i32, b in 0..100i32) {
+        for (x, y) in ellipse_points(cx, cy, a, b) {
+            prop_assert!((cx - a..=cx + a).contains(&x));
+            prop_assert!((cy - b..=cy + b).contains(&y));
+        }
+    }
+
+    #[test]
+    fn ellipse_reaches_its_four_extremities(cx in coordinate(), cy in coordinate(), a in 1..200i32, b in 1..100i32) {
+        let points = ellipse_points(cx, cy, a, b);
+        prop_assert!(points.contains(&(cx + a, cy)));
+        prop_assert!(points.contains(&(cx - a, cy)));
+        prop_assert!(points.contains(&(cx, cy + b)));
+        prop_assert!(points.contains(&(cx, cy - b)));
+    }
+
+    #[test]
+    fn ellipse_spans_stay_inside_the_outline(cx in coordinate(), cy in coordinate(), a in 0..200i32, b in 0..100i32) {
+        let points = ellipse_points(cx, cy, a, b);
+        for (y, first, last) in ellipse_spans(cx, cy, a, b) {
+            prop_assert!(first <= last);
+            prop_assert!(points.iter().any(|&(x, row)| row == y && x < first));
+            prop_assert!(points.iter().any(|&(x, row)| row == y && x > last));
+        }
+    }
+
+    #[test]
+    fn arc_cells_touch(cx in coordinate(), cy in coordinate(), b in 1..100i32, stretch in 1..4i32, start in 0.0..6.3f64, span in 0.0..6.3f64) {
+        // Dial shapes: flatter ellipses go back over their own cells
+        let a = b * stretch;
+        let points = arc_points(cx, cy, a as f64, b as f64, start, start + span);
+        for pair in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            prop_assert!((x1 - x0).abs() <= 1 && (y1 - y0).abs() <= 1);
+        }
+    }
+}
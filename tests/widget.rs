@@ -0,0 +1,50 @@
+//! The ratatui widget draws the dial of the binary in its configured colors.
+#![cfg(feature = "ratatui")]
+
+use chrono::NaiveTime;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::Widget;
+
+use tac::face::{HOURS_PAIR, MINUTES_PAIR};
+use tac::widget::AnalogClock;
+
+fn render(clock: AnalogClock) -> Buffer {
+    let area = Rect::new(0, 0, 40, 20);
+    let mut buf = Buffer::empty(area);
+    clock.render(area, &mut buf);
+    buf
+}
+
+/// Foreground colors of the cells showing `ch`.
+fn colors_of(buf: &Buffer, ch: &str) -> Vec<Color> {
+    buf.content()
+        .iter()
+        .filter(|cell| cell.symbol() == ch)
+        .map(|cell| cell.fg)
+        .collect()
+}
+
+#[test]
+fn hands_have_the_default_colors_of_the_binary() {
+    let buf =
+        render(AnalogClock::new(NaiveTime::from_hms_opt(3, 0, 0).unwrap()).show_seconds(false));
+    // The hour hand points right, its label read left to right
+    assert!(colors_of(&buf, "H").iter().all(|&fg| fg == Color::Red));
+    assert!(!colors_of(&buf, "H").is_empty());
+    assert!(colors_of(&buf, "m").iter().all(|&fg| fg == Color::Yellow));
+}
+
+#[test]
+fn colors_are_read_like_the_config_of_the_binary() {
+    let clock = AnalogClock::new(NaiveTime::from_hms_opt(3, 0, 0).unwrap())
+        .colors(&[(HOURS_PAIR, "#ff8800"), (MINUTES_PAIR, "208")]);
+    let buf = render(clock);
+    assert!(colors_of(&buf, "H")
+        .iter()
+        .all(|&fg| fg == Color::Rgb(255, 136, 0)));
+    assert!(colors_of(&buf, "m")
+        .iter()
+        .all(|&fg| fg == Color::Indexed(208)));
+}